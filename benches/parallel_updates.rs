@@ -0,0 +1,93 @@
+//! Measures how long it takes to update a large, data-driven set of actions, serially and in parallel, and how many
+//! heap allocations it takes to set their config. Run it with `cargo bench --bench parallel_updates`.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::ineffable_settings::IneffableSettings;
+
+/// The number of actions to update every tick.
+const ACTIONS: usize = 20_000;
+/// The number of ticks to measure.
+const TICKS: u32 = 500;
+
+/// Counts every heap allocation, so the benchmark can report how many allocations setting a config takes.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn main() {
+    let (serial, allocations) = measure(usize::MAX);
+    let (parallel, _) = measure(0);
+    println!("Setting a config for {ACTIONS} actions took {allocations} allocations.");
+    println!("Updating {ACTIONS} actions, averaged over {TICKS} ticks:");
+    println!("  serial:   {serial:?} per tick");
+    println!("  parallel: {parallel:?} per tick");
+    println!(
+        "  speedup:  {:.2}x",
+        serial.as_secs_f64() / parallel.as_secs_f64()
+    );
+}
+
+/// Returns the average duration of a tick, including bevy's own overhead, and the number of allocations it took to
+/// set the config.
+fn measure(parallel_threshold: usize) -> (Duration, usize) {
+    let mut app = App::new();
+    app.add_plugins((
+        MinimalPlugins,
+        AssetPlugin::default(),
+        InputPlugin,
+        IneffablePlugin,
+    ));
+    let keys = [KeyCode::KeyW, KeyCode::KeyA, KeyCode::KeyS, KeyCode::KeyD];
+    let mut builder = InputConfig::builder();
+    for index in 0..ACTIONS {
+        let action_id = format!("Action{index}");
+        let key = keys[index % keys.len()];
+        let sequence = PulseBinding::sequence(key)
+            .followed_by(KeyCode::Space)
+            .with_timing(Duration::from_millis(500));
+        app.register_dynamic_action("Benchmark", &action_id, InputKind::Pulse);
+        builder = builder
+            .bind_dynamic("Benchmark", &action_id, PulseBinding::just_pressed(key))
+            .bind_dynamic("Benchmark", &action_id, sequence);
+    }
+    let config = builder.build();
+    app.world_mut()
+        .resource_mut::<IneffableSettings>()
+        .parallel_threshold = parallel_threshold;
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    app.world_mut()
+        .run_system_once(move |mut ineffable: IneffableCommands| {
+            ineffable.set_config_silent(&config);
+        });
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    app.update();
+
+    let start = Instant::now();
+    for tick in 0..TICKS {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        input.press(keys[tick as usize % keys.len()]);
+        app.update();
+    }
+    (start.elapsed() / TICKS, allocations)
+}
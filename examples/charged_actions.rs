@@ -98,7 +98,7 @@ fn player_wiggle(
     } else if !player.wiggle_timer.finished() {
         // A timer is running, so we're currently animating the wiggle.
         player.wiggle_timer.tick(time.delta());
-        let cos_wave = (player.wiggle_timer.fraction() * TAU * NR_BOUNCES).cos() * -1.;
+        let cos_wave = -(player.wiggle_timer.fraction() * TAU * NR_BOUNCES).cos();
         let amplitude_modifier = player.wiggle_timer.fraction_remaining();
         let fading_cos_wave = cos_wave * amplitude_modifier;
         1.0 + fading_cos_wave * player.wiggle_strength
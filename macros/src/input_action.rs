@@ -3,7 +3,7 @@ use quote::{quote, quote_spanned, ToTokens};
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
 use syn::spanned::Spanned;
-use syn::{Data, DeriveInput, Fields, Token, Variant};
+use syn::{Data, DeriveInput, Fields, LitStr, Token, Variant};
 
 pub(crate) fn implement_input_action(ast: &DeriveInput) -> syn::Result<TokenStream> {
     let enum_name = &ast.ident;
@@ -21,6 +21,8 @@ pub(crate) fn implement_input_action(ast: &DeriveInput) -> syn::Result<TokenStre
     let mut match_arms_name = Vec::new();
     let mut match_arms_index = Vec::new();
     let mut match_arms_kind = Vec::new();
+    let mut match_arms_label = Vec::new();
+    let mut match_arms_category = Vec::new();
     let mut match_arms_iter = Vec::new();
     let mut match_arms_phantom = Vec::new();
     variants.iter().enumerate().try_for_each(|(index, variant)| {
@@ -41,13 +43,32 @@ pub(crate) fn implement_input_action(ast: &DeriveInput) -> syn::Result<TokenStre
         match_arms_index.push(quote! { #path { .. } => #index, });
 
         let properties = variant.attributes()?;
-        if properties.len() != 1 {
+        let (kinds, properties): (Vec<_>, Vec<_>) = properties.into_iter().partition(VariantAttribute::is_kind);
+        if kinds.len() != 1 {
             return Err(syn::Error::new(enum_name.span(), format!(
                 "On enums that derive InputAction, every Variant must have exactly one #[ineffable(<kind>)] attribute.\n\
-                On `{}`, encountered {} such attributes.", path.to_token_stream(), properties.len()),
+                On `{}`, encountered {} such attributes.", path.to_token_stream(), kinds.len()),
             ));
         }
-        match properties.first().expect("Should be safe to unwrap.") {
+        let mut label = None;
+        let mut category = None;
+        for property in properties {
+            let (target, name, value) = match property {
+                VariantAttribute::Label(value) => (&mut label, "label", value),
+                VariantAttribute::Category(value) => (&mut category, "category", value),
+                _ => unreachable!("Kinds were filtered out above."),
+            };
+            if target.replace(value.value()).is_some() {
+                return Err(syn::Error::new(value.span(), format!(
+                    "The `{name}` of `{}` was given more than once.", path.to_token_stream()),
+                ));
+            }
+        }
+        let label = label.unwrap_or_else(|| variant_name_literal.clone());
+        let category = category.unwrap_or_else(|| enum_name_literal.clone());
+        match_arms_label.push(quote! { #path { .. } => #label, });
+        match_arms_category.push(quote! { #path { .. } => #category, });
+        match kinds.first().expect("Should be safe to unwrap.") {
             VariantAttribute::SingleAxis(_) => {
                 match_arms_kind.push(quote! { #path { .. } => bevy_ineffable::input_action::InputKind::SingleAxis, });
                 match_arms_phantom.push((variant_lowercase, path, Ident::new("SingleAxis", variant.ident.span())));
@@ -64,6 +85,7 @@ pub(crate) fn implement_input_action(ast: &DeriveInput) -> syn::Result<TokenStre
                 match_arms_kind.push(quote! { #path { .. } => bevy_ineffable::input_action::InputKind::Continuous, });
                 match_arms_phantom.push((variant_lowercase, path, Ident::new("Continuous", variant.ident.span())));
             }
+            VariantAttribute::Label(_) | VariantAttribute::Category(_) => unreachable!("Only kinds are left."),
         };
         Ok(())
     })?;
@@ -105,6 +127,18 @@ pub(crate) fn implement_input_action(ast: &DeriveInput) -> syn::Result<TokenStre
                     _ => unreachable!(),
                 }
             }
+            fn label(&self) -> &'static str {
+                match self {
+                    #(#match_arms_label)*
+                    _ => unreachable!(),
+                }
+            }
+            fn category(&self) -> &'static str {
+                match self {
+                    #(#match_arms_category)*
+                    _ => unreachable!(),
+                }
+            }
             fn iter() -> impl Iterator<Item=Self> where Self: Sized {
                 let mut count = 0;
                 std::iter::from_fn(move || {
@@ -135,6 +169,8 @@ pub(crate) mod kw {
     custom_keyword!(single_axis);
     custom_keyword!(pulse);
     custom_keyword!(continuous);
+    custom_keyword!(label);
+    custom_keyword!(category);
 }
 
 #[allow(dead_code)]
@@ -143,6 +179,20 @@ pub(crate) enum VariantAttribute {
     DualAxis(kw::dual_axis),
     Pulse(kw::pulse),
     Continuous(kw::continuous),
+    /// A human-friendly name for the action, e.g.: `label = "Fire Weapon"`
+    Label(LitStr),
+    /// The section of a controls menu that the action belongs in, e.g.: `category = "Combat"`
+    Category(LitStr),
+}
+
+impl VariantAttribute {
+    /// True iff this attribute declares the `InputKind` of the variant.
+    fn is_kind(&self) -> bool {
+        !matches!(
+            self,
+            VariantAttribute::Label(_) | VariantAttribute::Category(_)
+        )
+    }
 }
 
 impl Parse for VariantAttribute {
@@ -156,6 +206,14 @@ impl Parse for VariantAttribute {
             Ok(VariantAttribute::Pulse(input.parse()?))
         } else if lookahead.peek(kw::continuous) {
             Ok(VariantAttribute::Continuous(input.parse()?))
+        } else if lookahead.peek(kw::label) {
+            input.parse::<kw::label>()?;
+            input.parse::<Token![=]>()?;
+            Ok(VariantAttribute::Label(input.parse()?))
+        } else if lookahead.peek(kw::category) {
+            input.parse::<kw::category>()?;
+            input.parse::<Token![=]>()?;
+            Ok(VariantAttribute::Category(input.parse()?))
         } else {
             Err(lookahead.error())
         }
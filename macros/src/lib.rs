@@ -25,6 +25,11 @@ mod input_action;
 ///     #[ineffable(continuous)] //<== continuous: returns true as long as the input is active.
 ///     #[ineffable(pulse)] //<== pulse: returns true for one tick when the input activates.
 ///
+/// After the kind, the attribute can optionally give the variant a human-friendly `label` and a `category`, for use in
+/// a controls menu. The label defaults to the name of the variant, and the category to the name of the enum:
+///
+///     #[ineffable(pulse, label = "Fire Weapon", category = "Combat")]
+///
 /// # Examples
 ///
 /// ```ignore
@@ -46,6 +51,9 @@ mod input_action;
 ///     /// Returns true for one tick when the mouse is clicked.
 ///     #[ineffable(pulse)]
 ///     Shoot,
+///     /// Shown as "Throw Grenade" in the "Combat" category of a controls menu.
+///     #[ineffable(pulse, label = "Throw Grenade", category = "Combat")]
+///     Grenade,
 /// }
 /// ```
 #[proc_macro_derive(InputAction, attributes(ineffable))]
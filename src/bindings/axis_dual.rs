@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use bevy::log::error;
-use bevy::prelude::Reflect;
+use bevy::prelude::{GamepadButtonType, Reflect};
 use serde::{Deserialize, Serialize};
 
 use crate::bindings::{InputBinding, SingleAxisBinding};
@@ -15,6 +15,42 @@ impl DualAxisBinding {
     pub fn builder() -> DualAxisBindingBuilder {
         DualAxisBindingBuilder::default()
     }
+
+    /// Binds the four directions of a gamepad's D-pad, so that it can drive movement just like a stick.
+    /// This is a shortcut for two hold bindings, and behaves like binding WASD: each axis is -1, 0 or 1, and
+    /// opposing directions cancel each other out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(dual_axis)]
+    ///     Movement,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(ineff!(PlayerInput::Movement), DualAxisBinding::dpad())
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn dpad() -> IBWrp<DualAxis> {
+        DualAxisBindingBuilder::new()
+            .set_x(
+                SingleAxisBinding::hold()
+                    .set_negative(GamepadButtonType::DPadLeft)
+                    .set_positive(GamepadButtonType::DPadRight)
+                    .build(),
+            )
+            .set_y(
+                SingleAxisBinding::hold()
+                    .set_negative(GamepadButtonType::DPadDown)
+                    .set_positive(GamepadButtonType::DPadUp)
+                    .build(),
+            )
+            .build()
+    }
 }
 
 #[derive(Debug, Default)]
@@ -17,9 +17,32 @@ pub enum SingleAxisBinding {
         inversion: Inversion,
         #[serde(default)]
         sensitivity: Sensitivity,
+        #[serde(default)]
+        calibration: Option<Calibration>,
+        /// If true, negative values are clamped to zero, so the output is always between 0.0 and 1.0.
+        #[serde(default)]
+        unipolar: bool,
+    },
+    /// Holding the first chord points the axis in the negative direction, holding the second in the positive one.
+    /// Inverting it swaps those directions.
+    Hold(Chord, Chord, #[serde(default)] Inversion),
+    /// Pulsing the first binding toggles the negative direction on or off, pulsing the second does the same for the
+    /// positive direction. Inverting it swaps those directions.
+    Toggle(PulseBinding, PulseBinding, #[serde(default)] Inversion),
+    /// Adds up the movement of a delta-based analog input, like the scroll wheel, into a value that persists between
+    /// movements. Useful for a throttle that the player ramps up and down with the scroll wheel. The movement is
+    /// scaled by the sensitivity of the binding, the sensitivity of the action and the global sensitivity.
+    Accumulate {
+        input: AnalogInput,
+        /// How much each unit of movement adds to the value.
+        #[serde(default)]
+        sensitivity: Sensitivity,
+        /// The lowest and highest values that the accumulated value can reach.
+        clamp: (f32, f32),
+        /// How fast the value returns to zero, in units per second. Zero means that the value holds indefinitely.
+        #[serde(default)]
+        decay: f32,
     },
-    Hold(Chord, Chord),
-    Toggle(PulseBinding, PulseBinding),
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq, Hash)]
@@ -33,7 +56,7 @@ impl Inversion {
     pub fn maybe_invert(&self, value: f32) -> f32 {
         match self {
             Inversion::NotInverted => value,
-            Inversion::Inverted => -1.0 * value,
+            Inversion::Inverted => -value,
         }
     }
     pub fn multiplier(&self) -> f32 {
@@ -42,6 +65,13 @@ impl Inversion {
             Inversion::Inverted => -1.0,
         }
     }
+    #[must_use]
+    pub fn flipped(&self) -> Self {
+        match self {
+            Inversion::NotInverted => Inversion::Inverted,
+            Inversion::Inverted => Inversion::NotInverted,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
@@ -51,6 +81,11 @@ impl Sensitivity {
     pub fn multiplier(&self) -> f32 {
         self.0
     }
+    /// Returns this sensitivity, multiplied by the given factor.
+    #[must_use]
+    pub(crate) fn scaled(&self, factor: f32) -> Self {
+        Sensitivity(self.0 * factor)
+    }
 }
 
 impl Default for Sensitivity {
@@ -59,6 +94,41 @@ impl Default for Sensitivity {
     }
 }
 
+/// Rescales the usable range of an analog input, for when it can't reach full deflection. This is common on worn
+/// analog sticks, where the maximum output never quite reaches 1.0.
+///
+/// Values at or beyond the bound of their direction map to -1.0 or 1.0, values in between are scaled accordingly.
+/// This is also known as an outer dead zone: the part of the range beyond the bound where moving the input further no
+/// longer changes the output. It is not to be confused with an anti dead zone, which boosts small inputs instead.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+pub struct Calibration {
+    /// The most negative value that the input can reach. Must be smaller than zero.
+    pub negative_bound: f32,
+    /// The most positive value that the input can reach. Must be greater than zero.
+    pub positive_bound: f32,
+}
+
+impl Calibration {
+    /// Applies the calibration to a raw value. A bound of zero is invalid and leaves its direction unchanged.
+    pub fn apply(&self, value: f32) -> f32 {
+        let bound = if value < 0. {
+            self.negative_bound.abs()
+        } else {
+            self.positive_bound.abs()
+        };
+        if bound < f32::EPSILON {
+            value
+        } else {
+            (value / bound).clamp(-1., 1.)
+        }
+    }
+    /// True iff either of the bounds is zero, which makes the calibration meaningless.
+    #[must_use]
+    pub fn has_zero_bound(&self) -> bool {
+        self.negative_bound.abs() < f32::EPSILON || self.positive_bound.abs() < f32::EPSILON
+    }
+}
+
 // =====================================================================================================================
 // ===== Builder stuff:
 // =====================================================================================================================
@@ -86,6 +156,14 @@ impl SingleAxisBinding {
     pub fn toggle() -> SingleAxisToggleBuilder {
         SingleAxisToggleBuilder::default()
     }
+    /// Creates and returns a new builder for a single axis accumulate binding.
+    ///
+    /// `Accumulate` takes a single analog input, and adds up its movement every tick. The sum stays put when the
+    /// input stops moving, unless it decays.
+    #[must_use]
+    pub fn accumulate(input: AnalogInput) -> SingleAxisAccumulateBuilder {
+        SingleAxisAccumulateBuilder::new(input)
+    }
 }
 
 #[derive(Debug)]
@@ -93,6 +171,8 @@ pub struct SingleAxisAnalogBuilder {
     input: AnalogInput,
     inversion: Inversion,
     sensitivity: Sensitivity,
+    calibration: Option<Calibration>,
+    unipolar: bool,
 }
 
 impl SingleAxisAnalogBuilder {
@@ -102,15 +182,13 @@ impl SingleAxisAnalogBuilder {
             input,
             inversion: Inversion::default(),
             sensitivity: Sensitivity::default(),
+            calibration: None,
+            unipolar: false,
         }
     }
     #[must_use]
     pub fn invert(mut self) -> Self {
-        self.inversion = if matches!(self.inversion, Inversion::Inverted) {
-            Inversion::NotInverted
-        } else {
-            Inversion::Inverted
-        };
+        self.inversion = self.inversion.flipped();
         self
     }
     #[must_use]
@@ -118,12 +196,58 @@ impl SingleAxisAnalogBuilder {
         self.sensitivity.0 = sensitivity;
         self
     }
+    /// Values whose magnitude is at or above the given bound map to -1.0 or 1.0. The range below it is rescaled, so
+    /// that the full output range is available even if the input can't reach full deflection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(single_axis)]
+    ///     Steer,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(PlayerInput::Steer),
+    ///         SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::LeftStickX))
+    ///             .set_outer_dead_zone(0.9)
+    ///             .build(),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn set_outer_dead_zone(self, bound: f32) -> Self {
+        self.set_calibration(-bound, bound)
+    }
+    /// Same as `set_outer_dead_zone()`, but with separate bounds for the negative and positive directions.
+    /// Useful when a stick reaches further in one direction than in the other.
+    #[must_use]
+    pub fn set_calibration(mut self, negative_bound: f32, positive_bound: f32) -> Self {
+        self.calibration = Some(Calibration {
+            negative_bound,
+            positive_bound,
+        });
+        self
+    }
+    /// Restricts the output of this binding to the range 0.0 to 1.0, for controls that only go in one direction,
+    /// such as a throttle. Negative values are clamped to zero, and so are values that end up negative after inversion.
+    #[must_use]
+    pub fn unipolar(mut self) -> Self {
+        self.unipolar = true;
+        self
+    }
     #[must_use]
     pub fn build(self) -> IBWrp<SingleAxis> {
         let binding = InputBinding::SingleAxis(SingleAxisBinding::Analog {
             input: self.input,
             inversion: self.inversion,
             sensitivity: self.sensitivity,
+            calibration: self.calibration,
+            unipolar: self.unipolar,
         });
         IBWrp::<SingleAxis>(binding, PhantomData)
     }
@@ -133,6 +257,7 @@ impl SingleAxisAnalogBuilder {
 pub struct SingleAxisHoldBuilder {
     negative: Option<Chord>,
     positive: Option<Chord>,
+    inversion: Inversion,
 }
 
 impl SingleAxisHoldBuilder {
@@ -150,11 +275,38 @@ impl SingleAxisHoldBuilder {
         self.positive = Some(input.into().into());
         self
     }
+    /// Swaps the negative and positive directions, for example to respect a player's preference without having to
+    /// change the chords themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlaneInput {
+    ///     #[ineffable(single_axis)]
+    ///     Pitch,
+    ///     #[ineffable(single_axis)]
+    ///     InvertedPitch,
+    /// }
+    /// let pitch = || SingleAxisBinding::hold().set_negative(KeyCode::KeyS).set_positive(KeyCode::KeyW);
+    /// let config = InputConfig::builder()
+    ///     .bind(ineff!(PlaneInput::Pitch), pitch().build())
+    ///     .bind(ineff!(PlaneInput::InvertedPitch), pitch().invert().build())
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn invert(mut self) -> Self {
+        self.inversion = self.inversion.flipped();
+        self
+    }
     #[must_use]
     pub fn build(self) -> IBWrp<SingleAxis> {
         let binding = InputBinding::SingleAxis(SingleAxisBinding::Hold(
             self.negative.unwrap_or_default(),
             self.positive.unwrap_or_default(),
+            self.inversion,
         ));
         IBWrp::<SingleAxis>(binding, PhantomData)
     }
@@ -164,6 +316,7 @@ impl SingleAxisHoldBuilder {
 pub struct SingleAxisToggleBuilder {
     negative: Option<PulseBinding>,
     positive: Option<PulseBinding>,
+    inversion: Inversion,
 }
 
 impl SingleAxisToggleBuilder {
@@ -181,11 +334,18 @@ impl SingleAxisToggleBuilder {
         self.positive = Some(Self::unwrap_pulse(input));
         self
     }
+    /// Swaps the negative and positive directions, without having to change the pulse bindings themselves.
+    #[must_use]
+    pub fn invert(mut self) -> Self {
+        self.inversion = self.inversion.flipped();
+        self
+    }
     #[must_use]
     pub fn build(self) -> IBWrp<SingleAxis> {
         let binding = InputBinding::SingleAxis(SingleAxisBinding::Toggle(
             self.negative.unwrap_or(PulseBinding::Dummy),
             self.positive.unwrap_or(PulseBinding::Dummy),
+            self.inversion,
         ));
         IBWrp::<SingleAxis>(binding, PhantomData)
     }
@@ -203,3 +363,74 @@ impl SingleAxisToggleBuilder {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct SingleAxisAccumulateBuilder {
+    input: AnalogInput,
+    sensitivity: Sensitivity,
+    clamp: (f32, f32),
+    decay: f32,
+}
+
+impl SingleAxisAccumulateBuilder {
+    #[must_use]
+    pub fn new(input: AnalogInput) -> Self {
+        Self {
+            input,
+            sensitivity: Sensitivity::default(),
+            clamp: (-1., 1.),
+            decay: 0.,
+        }
+    }
+    /// How much each unit of movement adds to the value. For the scroll wheel, one unit is usually one notch.
+    #[must_use]
+    pub fn set_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.sensitivity.0 = sensitivity;
+        self
+    }
+    /// The accumulated value stays between these bounds, which may be given in either order. Defaults to -1.0 and 1.0.
+    #[must_use]
+    pub fn set_clamp(mut self, min: f32, max: f32) -> Self {
+        self.clamp = (min, max);
+        self
+    }
+    /// The value returns to zero at this rate, in units per second. By default, the value doesn't decay.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum BoatInput {
+    ///     #[ineffable(single_axis)]
+    ///     Throttle,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(BoatInput::Throttle),
+    ///         SingleAxisBinding::accumulate(AnalogInput::ScrollWheelY)
+    ///             .set_sensitivity(0.25)
+    ///             .set_clamp(0., 1.)
+    ///             .set_decay(1.)
+    ///             .build(),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn set_decay(mut self, decay: f32) -> Self {
+        self.decay = decay;
+        self
+    }
+    #[must_use]
+    pub fn build(self) -> IBWrp<SingleAxis> {
+        let binding = InputBinding::SingleAxis(SingleAxisBinding::Accumulate {
+            input: self.input,
+            sensitivity: self.sensitivity,
+            clamp: self.clamp,
+            decay: self.decay,
+        });
+        IBWrp::<SingleAxis>(binding, PhantomData)
+    }
+}
@@ -1,24 +1,46 @@
 use std::marker::PhantomData;
+use std::time::Duration;
 
 use bevy::log::error;
 use bevy::prelude::Reflect;
 use serde::{Deserialize, Serialize};
 
 use crate::bindings::{Chord, ChordLike, InputBinding, PulseBinding};
+use crate::config::DurationInMillis;
 use crate::phantom::{Continuous, IBWrp, Pulse};
 
 #[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq)]
 pub enum ContinuousBinding {
     #[default]
     Dummy,
-    Hold(Chord),
+    Hold(Chord, #[serde(default)] HoldOptions),
     Toggle(PulseBinding),
 }
 
+/// Optional settings that change how a `ContinuousBinding::Hold` behaves.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+pub struct HoldOptions {
+    /// The input must be held down for this long before the action activates. Releasing the input deactivates the
+    /// action immediately. Useful for filtering out accidental brushes against a key.
+    ///
+    /// Unlike the post-acceptance-delay, this only affects this binding and doesn't block any other input.
+    #[serde(default)]
+    pub activation_delay: Option<DurationInMillis>,
+    /// The action stays active for this long after the input is released. Pressing the input again within this
+    /// window keeps the action active without interruption. Useful for coyote-time and other input forgiveness.
+    ///
+    /// The charge time doesn't grow during the grace period.
+    #[serde(default)]
+    pub release_grace: Option<DurationInMillis>,
+}
+
 impl ContinuousBinding {
     pub fn hold(input: impl Into<ChordLike>) -> IBWrp<Continuous> {
-        let binding = InputBinding::Continuous(ContinuousBinding::Hold(input.into().into()));
-        IBWrp::<Continuous>(binding, PhantomData)
+        ContinuousHoldBuilder::new(input).build()
+    }
+    /// Creates and returns a new builder for a hold binding, for when you need more control than `hold()` offers.
+    pub fn hold_builder(input: impl Into<ChordLike>) -> ContinuousHoldBuilder {
+        ContinuousHoldBuilder::new(input)
     }
     pub fn toggle(input: IBWrp<Pulse>) -> IBWrp<Continuous> {
         if let InputBinding::Pulse(pulse) = input.0 {
@@ -38,3 +60,80 @@ impl ContinuousBinding {
         }
     }
 }
+
+#[derive(Debug)]
+pub struct ContinuousHoldBuilder {
+    input: Chord,
+    options: HoldOptions,
+}
+
+impl ContinuousHoldBuilder {
+    #[must_use]
+    pub fn new(input: impl Into<ChordLike>) -> Self {
+        Self {
+            input: input.into().into(),
+            options: HoldOptions::default(),
+        }
+    }
+    /// The input must be held down for this long before the action activates.
+    /// The action deactivates as soon as the input is released.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Sprint,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(PlayerInput::Sprint),
+    ///         ContinuousBinding::hold_builder(KeyCode::ShiftLeft)
+    ///             .activation_delay(Duration::from_millis(100))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn activation_delay(mut self, delay: Duration) -> Self {
+        self.options.activation_delay = Some(delay.as_millis() as u64);
+        self
+    }
+    /// The action stays active for this long after the input is released, and deactivates once the grace period
+    /// expires. Pressing the input again during the grace period cancels it, and the action stays active.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Jump,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(PlayerInput::Jump),
+    ///         ContinuousBinding::hold_builder(KeyCode::Space)
+    ///             .release_grace(Duration::from_millis(100))
+    ///             .build(),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn release_grace(mut self, grace: Duration) -> Self {
+        self.options.release_grace = Some(grace.as_millis() as u64);
+        self
+    }
+    #[must_use]
+    pub fn build(self) -> IBWrp<Continuous> {
+        let binding = InputBinding::Continuous(ContinuousBinding::Hold(self.input, self.options));
+        IBWrp::<Continuous>(binding, PhantomData)
+    }
+}
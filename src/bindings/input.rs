@@ -1,7 +1,8 @@
 use bevy::prelude::Reflect;
 use serde::{Deserialize, Serialize};
+use smallvec::smallvec;
 
-use crate::bindings::{BinaryInput, ContinuousBinding, PulseBinding, SingleAxisBinding};
+use crate::bindings::{BinaryInput, Chord, ContinuousBinding, PulseBinding, SingleAxisBinding};
 use crate::input_action::InputKind;
 
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
@@ -47,6 +48,15 @@ impl From<ChordLike> for Vec<BinaryInput> {
     }
 }
 
+impl From<ChordLike> for Chord {
+    fn from(value: ChordLike) -> Self {
+        match value {
+            ChordLike::Single(input) => smallvec![input],
+            ChordLike::Multiple(inputs) => Chord::from_vec(inputs),
+        }
+    }
+}
+
 impl<A: Into<BinaryInput>> From<A> for ChordLike {
     fn from(a: A) -> Self {
         ChordLike::Single(a.into())
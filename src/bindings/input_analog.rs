@@ -1,7 +1,7 @@
 use bevy::prelude::{GamepadAxisType, Reflect};
 use serde::{Deserialize, Serialize};
 
-use crate::bindings::{BinaryInput, Threshold};
+use crate::bindings::{BinaryInput, InputDeviceCategory, Threshold};
 
 /// Input methods that indicate a direction and magnitude along a single axis.
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq, Hash)]
@@ -17,6 +17,27 @@ pub enum AnalogInput {
     /// The amount of vertical movement by the mouse since the last tick.
     MouseMotionY,
 
+    /// The change in distance between the first two fingers on the touch screen since the last tick.
+    /// Positive when the fingers spread apart (zooming in), negative when they pinch together.
+    /// Is zero while fewer than two fingers are touching the screen.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum CameraInput {
+    ///     #[ineffable(single_axis)]
+    ///     Zoom,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(ineff!(CameraInput::Zoom), SingleAxisBinding::analog(AnalogInput::TouchPinch).build())
+    ///     .build();
+    /// ```
+    TouchPinch,
+
     /// Axis types specific to the GamePad.
     GamePad(GamepadAxisType),
     /// The amount by which the bottom-left trigger is pushed in. Is a value between zero and one.
@@ -30,7 +51,82 @@ pub enum AnalogInput {
 }
 
 impl AnalogInput {
+    /// True iff this input reports the change since last tick, rather than a value that is sustained over time.
+    /// The mouse wheel, mouse motion and touch pinch are delta-based; gamepad sticks and triggers are not.
+    ///
+    /// Delta-based inputs can't be held down, so the validator warns when they are used in hold bindings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ineffable::bindings::{AnalogInput, Threshold};
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum CameraInput {
+    ///     #[ineffable(continuous)]
+    ///     Zoom,
+    /// }
+    /// let scroll = AnalogInput::ScrollWheelY.at_threshold(Threshold::preset_pos());
+    /// let (_config, report) = InputConfig::builder()
+    ///     .bind(ineff!(CameraInput::Zoom), ContinuousBinding::hold(scroll))
+    ///     .build_validated();
+    /// assert!(!report.is_empty());
+    /// ```
+    #[must_use]
+    pub fn is_delta_based(&self) -> bool {
+        matches!(
+            self,
+            AnalogInput::ScrollWheelX
+                | AnalogInput::ScrollWheelY
+                | AnalogInput::MouseMotionX
+                | AnalogInput::MouseMotionY
+                | AnalogInput::TouchPinch
+        )
+    }
+
+    /// The kind of device this input comes from. The touch screen doesn't have a category of its own, so it counts
+    /// as `All`.
+    pub(crate) fn device_category(&self) -> InputDeviceCategory {
+        match self {
+            AnalogInput::ScrollWheelX
+            | AnalogInput::ScrollWheelY
+            | AnalogInput::MouseMotionX
+            | AnalogInput::MouseMotionY => InputDeviceCategory::Mouse,
+            AnalogInput::TouchPinch => InputDeviceCategory::All,
+            AnalogInput::GamePad(_)
+            | AnalogInput::GamePadLeftTrigger2
+            | AnalogInput::GamePadRightTrigger2 => InputDeviceCategory::Gamepad,
+        }
+    }
+
     /// Converts an `AnalogInput` to a `BinaryInput` by applying a `Threshold`. Useful for the builder.
+    ///
+    /// The result can be used anywhere a key or button can, including as part of a chord.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::{GamepadButtonType, KeyCode};
+    /// # use bevy_ineffable::bindings::{AnalogInput, Threshold};
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Aim,
+    ///     #[ineffable(pulse)]
+    ///     Shoot,
+    /// }
+    /// let trigger = AnalogInput::GamePadLeftTrigger2.at_threshold(Threshold::preset_pos());
+    /// let _ = InputConfig::builder()
+    ///     // Use the left trigger as a button:
+    ///     .bind(ineff!(PlayerInput::Aim), ContinuousBinding::hold(trigger.clone()))
+    ///     // Or combine it with other buttons in a chord:
+    ///     .bind(
+    ///         ineff!(PlayerInput::Shoot),
+    ///         PulseBinding::just_pressed((trigger, GamepadButtonType::South)),
+    ///     )
+    ///     .build();
+    /// ```
     pub fn at_threshold(self, threshold: Threshold) -> BinaryInput {
         BinaryInput::Axis(self, threshold)
     }
@@ -2,13 +2,29 @@ use std::slice::Iter;
 
 use bevy::prelude::{GamepadButtonType, KeyCode, MouseButton, Reflect};
 use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 
 use crate::bindings::input_analog::AnalogInput;
+use crate::bindings::InputDeviceCategory;
 
 /// A chord is a set of unique inputs that have to be activated at the same time.
 /// Example: Ctrl-S to save a document.
 /// An empty chord is considered a dummy, it will never activate.
-pub type Chord = Vec<BinaryInput>;
+///
+/// Almost every chord is a single input, so a chord of one input is stored inline, without a heap allocation. This
+/// halves the number of allocations it takes to set a config, see `benches/parallel_updates.rs`.
+/// Chords are (de)serialised exactly like a list.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::bindings::{BinaryInput, Chord};
+/// let chord = Chord::from_vec(vec![BinaryInput::Key(KeyCode::ControlLeft), BinaryInput::Key(KeyCode::KeyS)]);
+/// assert_eq!(ron::to_string(&chord).unwrap(), ron::to_string(&chord.to_vec()).unwrap());
+/// assert_eq!(ron::from_str::<Chord>("[Key(ControlLeft), Key(KeyS)]").unwrap(), chord);
+/// ```
+pub type Chord = SmallVec<[BinaryInput; 1]>;
 
 /// Something that can provide a discrete, binary signal: on or off.
 /// Example: a button that can either be pressed down or not.
@@ -97,6 +113,31 @@ impl Threshold {
 /// For example, let's say you want to use any Enter to perform an action, whether it is the main one or the
 /// numpad one. You want the player to be able to use them interchangeably. You could make two bindings, or you could
 /// use `KeyGroup::Enter`, which matches both.
+///
+/// # Examples
+///
+/// Key groups work anywhere a key does. Here, the modifier keys drive a single axis that selects the precision of a
+/// tool: hold either Shift for fine mode, or either Alt for coarse mode. Holding both cancels out.
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::bindings::KeyGroup;
+/// # use bevy_ineffable::prelude::*;
+/// #[derive(InputAction)]
+/// pub enum ToolInput {
+///     #[ineffable(single_axis)]
+///     Precision,
+/// }
+/// let config = InputConfig::builder()
+///     .bind(
+///         ineff!(ToolInput::Precision),
+///         SingleAxisBinding::hold()
+///             .set_negative(KeyGroup::Shift)
+///             .set_positive(KeyGroup::Alt)
+///             .build(),
+///     )
+///     .build();
+/// ```
 #[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq, Hash)]
 pub enum KeyGroup {
     /// Matches both `KeyCode::Return` and `KeyCode::NumpadEnter`.
@@ -134,6 +175,18 @@ pub enum KeyGroup {
     Number9,
 }
 
+impl BinaryInput {
+    /// The kind of device this input comes from.
+    pub(crate) fn device_category(&self) -> InputDeviceCategory {
+        match self {
+            BinaryInput::Key(_) | BinaryInput::KeyGroup(_) => InputDeviceCategory::Keyboard,
+            BinaryInput::MouseButton(_) => InputDeviceCategory::Mouse,
+            BinaryInput::Gamepad(_) => InputDeviceCategory::Gamepad,
+            BinaryInput::Axis(input, _) => input.device_category(),
+        }
+    }
+}
+
 impl KeyGroup {
     pub fn iter(&self) -> Iter<'_, KeyCode> {
         match self {
@@ -1,13 +1,40 @@
 use std::marker::PhantomData;
 use std::time::Duration;
 
-use bevy::prelude::Reflect;
+use bevy::prelude::{Reflect, Vec2};
 use serde::{Deserialize, Serialize};
 
-use crate::bindings::{Chord, ChordLike, InputBinding};
+use crate::bindings::{AnalogInput, Chord, ChordLike, InputBinding, Threshold};
 use crate::config::DurationInMillis;
 use crate::phantom::{IBWrp, Pulse};
 
+/// Describes which input makes a pulse action pulse.
+///
+/// A pulse action can have any number of bindings, of any variant. It pulses whenever at least one of them fires.
+/// If several fire on the same tick, that still counts as a single pulse.
+///
+/// # Examples
+///
+/// An action that is bound to both a single press and a cheat code that ends with that same key:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::prelude::*;
+/// #[derive(InputAction)]
+/// pub enum PlayerInput {
+///     #[ineffable(pulse)]
+///     Interact,
+/// }
+/// let cheat_code = PulseBinding::sequence(KeyCode::KeyI)
+///     .followed_by(KeyCode::KeyD)
+///     .followed_by(KeyCode::KeyE)
+///     .with_timing(Duration::from_millis(500));
+/// let config = InputConfig::builder()
+///     .bind(ineff!(PlayerInput::Interact), PulseBinding::just_pressed(KeyCode::KeyE))
+///     .bind(ineff!(PlayerInput::Interact), cheat_code)
+///     .build();
+/// ```
 #[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq)]
 pub enum PulseBinding {
     #[default]
@@ -15,7 +42,181 @@ pub enum PulseBinding {
     JustPressed(Chord),
     JustReleased(Chord),
     DoubleClick(Chord),
-    Sequence(DurationInMillis, Vec<Chord>),
+    Sequence(
+        DurationInMillis,
+        Vec<Chord>,
+        #[serde(default)] SequenceOptions,
+    ),
+    /// Pulses once for every whole notch of movement along the analog input, in the direction of the threshold.
+    /// The magnitude of the threshold is the size of a notch. Movement that doesn't add up to a whole notch is kept
+    /// for the next tick, until the input changes direction.
+    Notches(AnalogInput, Threshold),
+    /// Pulses once when the player completes a quick swipe in the given direction, either by dragging a finger
+    /// across the touch screen, or by moving the mouse while holding the left mouse button.
+    Swipe(SwipeOptions),
+    /// Pulses whenever any key, mouse button or gamepad button of the given category is newly pressed.
+    /// Mouse motion, scrolling and analog sticks never trigger it.
+    AnyInput(#[serde(default)] InputDeviceCategory),
+}
+
+/// The kinds of input devices that a `PulseBinding::AnyInput` listens to.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum InputDeviceCategory {
+    /// Keyboard keys, mouse buttons and gamepad buttons.
+    #[default]
+    All,
+    Keyboard,
+    Mouse,
+    Gamepad,
+}
+
+/// Describes the swipe gesture that a `PulseBinding::Swipe` recognises.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+pub struct SwipeOptions {
+    /// The direction that the swipe must predominantly move in.
+    pub direction: SwipeDirection,
+    /// The minimum distance that the swipe must travel in its direction, in logical pixels.
+    pub min_distance: f32,
+    /// The maximum amount of time between touching the screen (or pressing the mouse button) and letting go.
+    pub max_duration: DurationInMillis,
+}
+
+/// The direction of a swipe gesture, as seen on the screen.
+#[derive(Debug, Serialize, Deserialize, Reflect, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SwipeDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl SwipeDirection {
+    /// Returns the unit vector pointing in this direction, in window coordinates. The y-axis points down.
+    #[must_use]
+    pub fn as_vec2(self) -> Vec2 {
+        match self {
+            SwipeDirection::Up => Vec2::NEG_Y,
+            SwipeDirection::Down => Vec2::Y,
+            SwipeDirection::Left => Vec2::NEG_X,
+            SwipeDirection::Right => Vec2::X,
+        }
+    }
+}
+
+/// Optional settings that change how a `PulseBinding::Sequence` behaves.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+pub struct SequenceOptions {
+    /// What happens when the player presses the wrong input halfway through the sequence.
+    #[serde(default)]
+    pub mode: SequenceMode,
+    /// Overrides the maximum delay between individual steps. The entry at index `i` is the maximum delay between
+    /// steps `i` and `i + 1`. Any step that doesn't have an entry here falls back to the sequence's global timeout.
+    ///
+    /// For example, a combo can offer a generous window at the start and demand a tight window for the finisher.
+    #[serde(default)]
+    pub step_timeouts: Vec<DurationInMillis>,
+    /// The minimum amount of time that the input at each step must be held down, before the sequence advances.
+    /// The entry at index `i` belongs to step `i`. Any step that doesn't have an entry here, or whose entry is zero,
+    /// is a plain tap.
+    ///
+    /// For example, fighting-game motions like "hold back, then press punch".
+    #[serde(default)]
+    pub step_holds: Vec<DurationInMillis>,
+    /// What happens to the player's progress after the sequence fires.
+    #[serde(default)]
+    pub reset_policy: SequenceResetPolicy,
+}
+
+/// Determines how a sequence reacts to inputs that don't match the step the player is currently at.
+///
+/// # Examples
+///
+/// Two sequences `A B C`, the second of which is strict:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::prelude::*;
+/// #[derive(InputAction)]
+/// pub enum Combo {
+///     #[ineffable(pulse)]
+///     Tolerant,
+///     #[ineffable(pulse)]
+///     Strict,
+/// }
+/// let sequence = || {
+///     PulseBinding::sequence(KeyCode::KeyA)
+///         .followed_by(KeyCode::KeyB)
+///         .followed_by(KeyCode::KeyC)
+/// };
+/// let config = InputConfig::builder()
+///     .bind(
+///         ineff!(Combo::Tolerant),
+///         sequence().with_timing(Duration::from_millis(1000)),
+///     )
+///     .bind(
+///         ineff!(Combo::Strict),
+///         sequence().strict().with_timing(Duration::from_millis(1000)),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceMode {
+    /// Inputs that don't match the current step are ignored. The player can bump into other keys halfway through
+    /// the sequence without losing their progress, as long as they don't exceed the maximum delay between inputs.
+    #[default]
+    Tolerant,
+    /// Pressing an input that belongs to a different step of this sequence resets the player's progress. Inputs that
+    /// are not part of the sequence at all are still ignored.
+    ///
+    /// If the wrong input happens to be the first step of the sequence, it counts as the start of a new attempt.
+    Strict,
+}
+
+/// Determines what happens to the player's progress after a sequence fires.
+///
+/// # Examples
+///
+/// Two sequences `A B A B`, the second of which uses a sliding window:
+///
+/// ```
+/// # use std::time::Duration;
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::prelude::*;
+/// #[derive(InputAction)]
+/// pub enum Rhythm {
+///     #[ineffable(pulse)]
+///     FullReset,
+///     #[ineffable(pulse)]
+///     SlidingWindow,
+/// }
+/// let sequence = || {
+///     PulseBinding::sequence(KeyCode::KeyA)
+///         .followed_by(KeyCode::KeyB)
+///         .followed_by(KeyCode::KeyA)
+///         .followed_by(KeyCode::KeyB)
+/// };
+/// let config = InputConfig::builder()
+///     .bind(
+///         ineff!(Rhythm::FullReset),
+///         sequence().with_timing(Duration::from_millis(1000)),
+///     )
+///     .bind(
+///         ineff!(Rhythm::SlidingWindow),
+///         sequence().sliding_window().with_timing(Duration::from_millis(1000)),
+///     )
+///     .build();
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum SequenceResetPolicy {
+    /// The player has to enter the whole sequence again before it fires again.
+    #[default]
+    FullReset,
+    /// The last inputs of the sequence are kept, as far as they also form the start of the sequence.
+    /// For example, after `A B A B` fires, the final `A B` counts as the first half of the next attempt.
+    ///
+    /// Useful for rhythm games and repeating combos, where overlapping re-entries should fire again.
+    SlidingWindow,
 }
 
 impl PulseBinding {
@@ -31,6 +232,151 @@ impl PulseBinding {
         let binding = InputBinding::Pulse(PulseBinding::DoubleClick(input.into().into()));
         IBWrp::<Pulse>(binding, PhantomData)
     }
+    /// Pulses once for every notch that the analog input moves in the direction of the threshold. Meant for the
+    /// scroll wheel: flicking it three notches in a single frame pulses three times, rather than once.
+    ///
+    /// Use `Ineffable::pulse_count()` to find out how many notches were scrolled this tick.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::{AnalogInput, Threshold};
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum Weapons {
+    ///     #[ineffable(pulse)]
+    ///     Next,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(Weapons::Next),
+    ///         PulseBinding::notches(AnalogInput::ScrollWheelY, Threshold::new(1.)),
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn notches(input: AnalogInput, notch: Threshold) -> IBWrp<Pulse> {
+        let binding = InputBinding::Pulse(PulseBinding::Notches(input, notch));
+        IBWrp::<Pulse>(binding, PhantomData)
+    }
+    /// Pulses once when the player swipes in the given direction. The swipe must travel at least `min_distance`
+    /// logical pixels, and the finger or left mouse button must be released within `max_duration`.
+    ///
+    /// The swipe must mostly move in the given direction: the distance travelled in that direction must be greater
+    /// than the distance travelled sideways. The binding pulses when the swipe ends, so only once per swipe.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::SwipeDirection;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum Spells {
+    ///     #[ineffable(pulse)]
+    ///     Fireball,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(Spells::Fireball),
+    ///         PulseBinding::swipe(SwipeDirection::Right, 100., Duration::from_millis(1000)),
+    ///     )
+    ///     .build();
+    /// ```
+    pub fn swipe(
+        direction: SwipeDirection,
+        min_distance: f32,
+        max_duration: Duration,
+    ) -> IBWrp<Pulse> {
+        let binding = InputBinding::Pulse(PulseBinding::Swipe(SwipeOptions {
+            direction,
+            min_distance,
+            max_duration: max_duration.as_millis() as u64,
+        }));
+        IBWrp::<Pulse>(binding, PhantomData)
+    }
+    /// Pulses whenever any button of the given category is newly pressed, like on a "Press any button" screen.
+    /// Only discrete button presses count: moving the mouse or a drifting stick doesn't. Neither do buttons that are
+    /// consumed by an `InputContext` higher on the stack.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::InputDeviceCategory;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum TitleScreenInput {
+    ///     #[ineffable(pulse)]
+    ///     Continue,
+    ///     #[ineffable(pulse)]
+    ///     ContinueWithMouse,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(ineff!(TitleScreenInput::Continue), PulseBinding::any_input(InputDeviceCategory::All))
+    ///     .bind(ineff!(TitleScreenInput::ContinueWithMouse), PulseBinding::any_input(InputDeviceCategory::Mouse))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn any_input(category: InputDeviceCategory) -> IBWrp<Pulse> {
+        let binding = InputBinding::Pulse(PulseBinding::AnyInput(category));
+        IBWrp::<Pulse>(binding, PhantomData)
+    }
+    /// Creates and returns a new builder for a sequence binding.
+    ///
+    /// A sequence is a series of chords that must be pressed one after another. Every step is a chord in its own
+    /// right: a step consisting of multiple inputs only advances the sequence once all of them are held down at the
+    /// same time.
+    ///
+    /// # Examples
+    ///
+    /// Press `A` and `B` together, then press `C`:
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum Combo {
+    ///     #[ineffable(pulse)]
+    ///     Special,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(Combo::Special),
+    ///         PulseBinding::sequence((KeyCode::KeyA, KeyCode::KeyB))
+    ///             .followed_by(KeyCode::KeyC)
+    ///             .with_timing(Duration::from_millis(1000)),
+    ///     )
+    ///     .build();
+    /// ```
+    ///
+    /// The validator warns about a sequence that only repeats the same chord, because a double-click is a better fit:
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::KeyCode;
+    /// # use bevy_ineffable::prelude::*;
+    /// # use bevy_ineffable::reporting::InputConfigProblem;
+    /// # #[derive(InputAction)]
+    /// # pub enum Combo {
+    /// #     #[ineffable(pulse)]
+    /// #     Special,
+    /// # }
+    /// let (_config, report) = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(Combo::Special),
+    ///         PulseBinding::sequence(KeyCode::KeyA)
+    ///             .followed_by(KeyCode::KeyA)
+    ///             .with_timing(Duration::from_millis(300)),
+    ///     )
+    ///     .build_validated();
+    /// assert!(matches!(
+    ///     report.problems()[0].problem,
+    ///     InputConfigProblem::SequenceRepeatsSameChord { repeats: 2, .. }
+    /// ));
+    /// ```
     pub fn sequence(input: impl Into<ChordLike>) -> SequenceBuilder {
         SequenceBuilder::new(input)
     }
@@ -39,6 +385,12 @@ impl PulseBinding {
 #[derive(Debug)]
 pub struct SequenceBuilder {
     chords: Vec<Chord>,
+    /// The maximum delay before each step, if it deviates from the global timing.
+    /// The first element is always `None`, because there is no delay before the first step.
+    step_timeouts: Vec<Option<Duration>>,
+    /// The minimum hold duration of each step, if it is not a plain tap.
+    step_holds: Vec<Option<Duration>>,
+    options: SequenceOptions,
 }
 
 impl SequenceBuilder {
@@ -46,18 +398,119 @@ impl SequenceBuilder {
     pub fn new(input: impl Into<ChordLike>) -> Self {
         SequenceBuilder {
             chords: vec![input.into().into()],
+            step_timeouts: vec![None],
+            step_holds: vec![None],
+            options: SequenceOptions::default(),
         }
     }
     #[must_use]
     pub fn followed_by(mut self, input: impl Into<ChordLike>) -> Self {
         self.chords.push(input.into().into());
+        self.step_timeouts.push(None);
+        self.step_holds.push(None);
+        self
+    }
+    /// Like `followed_by()`, but the given input must follow the previous step within the given maximum delay,
+    /// instead of the global timing given to `with_timing()`.
+    ///
+    /// # Examples
+    ///
+    /// Take your time pressing `A` and `B`, but then press `C` within 100 milliseconds:
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum Combo {
+    ///     #[ineffable(pulse)]
+    ///     Finisher,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(Combo::Finisher),
+    ///         PulseBinding::sequence(KeyCode::KeyA)
+    ///             .followed_by(KeyCode::KeyB)
+    ///             .followed_within(Duration::from_millis(100), KeyCode::KeyC)
+    ///             .with_timing(Duration::from_millis(1000)),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn followed_within(mut self, max_delay: Duration, input: impl Into<ChordLike>) -> Self {
+        self.chords.push(input.into().into());
+        self.step_timeouts.push(Some(max_delay));
+        self.step_holds.push(None);
+        self
+    }
+    /// The most recently added step must be held down for at least the given duration, instead of being tapped.
+    /// The sequence advances as soon as the input has been held long enough; it does not wait for the release.
+    ///
+    /// # Examples
+    ///
+    /// Hold back for 200 milliseconds, then press punch:
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum Combo {
+    ///     #[ineffable(pulse)]
+    ///     ChargedPunch,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(Combo::ChargedPunch),
+    ///         PulseBinding::sequence(KeyCode::ArrowLeft)
+    ///             .held_for(Duration::from_millis(200))
+    ///             .followed_by(KeyCode::KeyP)
+    ///             .with_timing(Duration::from_millis(500)),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn held_for(mut self, min_hold: Duration) -> Self {
+        if let Some(last) = self.step_holds.last_mut() {
+            *last = Some(min_hold);
+        }
+        self
+    }
+    /// Pressing an input that belongs to a different step of the sequence resets the player's progress.
+    /// By default, sequences are tolerant and ignore such inputs. See `SequenceMode` for details.
+    #[must_use]
+    pub fn strict(mut self) -> Self {
+        self.options.mode = SequenceMode::Strict;
         self
     }
+    /// After the sequence fires, keeps the inputs that also form the start of a new attempt.
+    /// See `SequenceResetPolicy` for details.
     #[must_use]
-    pub fn with_timing(self, timing: Duration) -> IBWrp<Pulse> {
+    pub fn sliding_window(mut self) -> Self {
+        self.options.reset_policy = SequenceResetPolicy::SlidingWindow;
+        self
+    }
+    #[must_use]
+    pub fn with_timing(mut self, timing: Duration) -> IBWrp<Pulse> {
+        if self.step_timeouts.iter().any(Option::is_some) {
+            self.options.step_timeouts = self
+                .step_timeouts
+                .iter()
+                .skip(1)
+                .map(|step| step.unwrap_or(timing).as_millis() as u64)
+                .collect();
+        }
+        if self.step_holds.iter().any(Option::is_some) {
+            self.options.step_holds = self
+                .step_holds
+                .iter()
+                .map(|step| step.map_or(0, |hold| hold.as_millis() as u64))
+                .collect();
+        }
         let binding = InputBinding::Pulse(PulseBinding::Sequence(
             timing.as_millis() as u64,
             self.chords,
+            self.options,
         ));
         IBWrp::<Pulse>(binding, PhantomData)
     }
@@ -2,19 +2,26 @@
 
 use bevy::asset::{AssetPath, AssetServer};
 use bevy::ecs::system::SystemParam;
-use bevy::prelude::{Commands, Res, ResMut};
+use bevy::log::error;
+use bevy::prelude::{Commands, Query, Res, ResMut};
 
-use crate::config::simple_asset_loading::{CurrentlyLoading, MergeMode};
+use crate::components::IneffableInput;
+use crate::config::simple_asset_loading::{CurrentlyLoading, MergeMode, SettingsMergePolicy};
 use crate::config::InputConfig;
+use crate::input_action::InputAction;
+use crate::phantom::IAWrp;
 use crate::prelude::Ineffable;
-use crate::processed::bound_action::BoundAction;
-use crate::processed::processor::{collect_inputs, validate};
+use crate::processed::processor::{process_config, validate, validate_ignoring_profiles};
 use crate::reporting::InputConfigReport;
 use crate::resources::ineffable_settings::IneffableSettings;
 use crate::resources::meta_data::IneffableMetaData;
+use crate::resources::{ActionDelta, InputSnapshot};
 
 /// Use this as a system parameter to validate and set `InputConfig`s.
 ///
+/// Setting a config also updates all `IneffableInput` components, so this can't be used in the same system as a
+/// query that accesses `IneffableInput`.
+///
 /// # Examples
 ///
 /// ```
@@ -40,8 +47,11 @@ pub struct IneffableCommands<'w, 's> {
     /// Used to validate the config.
     meta_data: Res<'w, IneffableMetaData>,
     processed_actions: ResMut<'w, Ineffable>,
+    /// Per-entity input state, which is rebuilt whenever a new config is set.
+    players: Query<'w, 's, &'static mut IneffableInput>,
     settings: ResMut<'w, IneffableSettings>,
-    asset_server: Res<'w, AssetServer>,
+    /// Only available when the `AssetPlugin` was added.
+    asset_server: Option<Res<'w, AssetServer>>,
 }
 
 impl IneffableCommands<'_, '_> {
@@ -68,6 +78,34 @@ impl IneffableCommands<'_, '_> {
         report
     }
 
+    /// Validates the given `InputConfig`, and only sets it if the report contains no errors. Warnings don't stop the
+    /// config from being set. Either way, the report is dumped to the log, just like with `set_config()`.
+    ///
+    /// Returns the report if the config was rejected. In that case, the current config stays in place. This is useful
+    /// for configs that were written by players, which should not replace working bindings with broken ones.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ineffable::prelude::*;
+    /// fn apply_player_config(mut ineffable: IneffableCommands) {
+    ///     let config = InputConfig::default();
+    ///     if let Err(report) = ineffable.try_set_config(&config) {
+    ///         // The previous config is still in place. The report lists what is wrong with the new one.
+    ///         assert!(report.has_errors());
+    ///     }
+    /// }
+    /// ```
+    pub fn try_set_config(&mut self, config: &InputConfig) -> Result<(), InputConfigReport> {
+        let report = self.validate(config);
+        report.dump_to_log();
+        if report.has_errors() {
+            return Err(report);
+        }
+        self.set_config_silent(config);
+        Ok(())
+    }
+
     /// Sets the new `InputConfig`.
     ///
     /// This overrides any `InputConfig`s that were set before. To use settings from multiple configs at the same time,
@@ -76,30 +114,256 @@ impl IneffableCommands<'_, '_> {
     /// Consider using `set_config()` instead, for it will warn you if you make a mistake with your
     /// keybinding configuration. This function will silently swallow any bugs.
     pub fn set_config_silent(&mut self, config: &InputConfig) {
-        let helper = collect_inputs(&self.meta_data, config);
-        self.processed_actions.groups = config
-            .bindings
-            .iter()
-            .filter(|(group_id, _)| self.meta_data.group_exists(group_id))
-            .map(|(group_id, group_data)| {
-                let mut bound_actions = Vec::new();
-                for meta in self.meta_data.actions(group_id) {
-                    if let Some(action) = group_data.get(&meta.action_id) {
-                        bound_actions.push(BoundAction::new(meta, action, &helper));
-                    } else {
-                        bound_actions.push(BoundAction::new(meta, &[], &helper));
-                    }
-                }
-                (group_id.clone(), bound_actions)
-            })
-            .collect();
+        self.apply_config(config);
+        self.processed_actions.current_config = Some(config.clone());
+        self.processed_actions.base_config = Some(config.clone());
+        self.processed_actions.active_profile = None;
+    }
+
+    /// Switches to one of the named profiles in the current config, without reloading any assets.
+    /// The profile is merged on top of the config that was set, and the result is validated and applied. Afterwards,
+    /// `Ineffable::current_config()` returns the merged config. Activating another profile later starts from the
+    /// config that was set again, so profiles don't stack.
+    ///
+    /// Returns None and does nothing if no config was set, or if it has no profile with the given name.
+    /// To go back to the config without any profile, call `set_config()` again.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ineffable::prelude::*;
+    /// fn on_left_handed_selected(mut ineffable: IneffableCommands) {
+    ///     if ineffable.activate_profile("Lefty").is_none() {
+    ///         // The current config has no profile with that name.
+    ///     }
+    /// }
+    /// ```
+    pub fn activate_profile(&mut self, name: &str) -> Option<InputConfigReport> {
+        let config = self
+            .processed_actions
+            .base_config
+            .as_ref()?
+            .with_profile(name)?;
+        // The profiles themselves were already validated when the config was set.
+        let report = validate_ignoring_profiles(&self.meta_data, &config);
+        report.dump_to_log();
+        self.apply_config(&config);
+        self.processed_actions.current_config = Some(config);
+        self.processed_actions.active_profile = Some(name.to_string());
+        Some(report)
+    }
+
+    /// Processes the config and installs the result, for the global state and for every player.
+    fn apply_config(&mut self, config: &InputConfig) {
+        self.processed_actions.groups = process_config(&self.meta_data, config);
+        self.processed_actions.action_indices = self.meta_data.action_indices();
+        for mut player in &mut self.players {
+            player
+                .state
+                .groups
+                .clone_from(&self.processed_actions.groups);
+            player
+                .state
+                .action_indices
+                .clone_from(&self.processed_actions.action_indices);
+            player.initialised = true;
+        }
         self.settings.set(config);
     }
-    pub fn load_configs<'a>(&mut self, mut paths: Vec<(MergeMode, impl Into<AssetPath<'a>>)>) {
+
+    /// Remembers the given `InputConfig` as the defaults, so that `reset_to_defaults()` can go back to it later.
+    /// This does not install the config; call `set_config()` for that.
+    ///
+    /// When loading configs through `load_configs()`, the config with `MergeMode::Base` automatically becomes the
+    /// default. Use this function if you set up your defaults some other way, for example by building them in code
+    /// or by parsing a RON string that is embedded in the executable.
+    pub fn set_default_config(&mut self, config: &InputConfig) {
+        self.processed_actions.default_config = Some(config.clone());
+    }
+
+    /// Re-applies the default `InputConfig`, discarding any overrides (such as the player's custom bindings).
+    ///
+    /// The defaults are validated and a report is written to the log, just like in `set_config()`.
+    /// Returns None and does nothing if no defaults are known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ineffable::prelude::*;
+    /// fn on_reset_button_clicked(mut ineffable: IneffableCommands) {
+    ///     ineffable.reset_to_defaults();
+    /// }
+    /// ```
+    pub fn reset_to_defaults(&mut self) -> Option<InputConfigReport> {
+        let defaults = self.processed_actions.default_config.clone()?;
+        Some(self.set_config(&defaults))
+    }
+
+    /// Clears the transient input state of every action, for every player: toggles are switched off, sequences and
+    /// double-clicks start over, and charge times and cooldowns are reset. The bindings themselves stay the same.
+    /// Use this when teleporting the player or loading a save, so that nothing carries over.
+    ///
+    /// Inputs that are physically held down remain held: they don't count as pressed again. A continuous action that is
+    /// held down stays active, without reporting `just_activated()` a second time, but its charge time starts over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ineffable::prelude::*;
+    /// fn on_save_loaded(mut ineffable: IneffableCommands) {
+    ///     ineffable.reset_state();
+    /// }
+    /// ```
+    pub fn reset_state(&mut self) {
+        self.processed_actions.reset_state();
+        for mut player in &mut self.players {
+            player.state.reset_state();
+        }
+    }
+
+    /// Enables or disables a single action, for example to stop the player from jumping while they're stunned.
+    ///
+    /// A disabled action always reports its neutral state: zero for axes, inactive for continuous actions and no
+    /// pulses, also in `Ineffable::iter_actions()`. Disabling an active continuous action does not make it report
+    /// `just_deactivated()`. While disabled, the action is not updated at all: it doesn't consume inputs from the
+    /// contexts below it, and it doesn't start the post-acceptance-delay. Its state is frozen until it is enabled
+    /// again, so re-enabling it while the key that activated it is still held down does not make it report
+    /// `just_activated()`.
+    ///
+    /// This applies to the global `Ineffable` resource and to the `IneffableInput` components of all current players.
+    /// It lasts until the action is enabled again, even if a new config is set in the meantime.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(pulse)]
+    ///     Jump,
+    /// }
+    /// fn on_stunned(mut ineffable: IneffableCommands) {
+    ///     ineffable.set_action_enabled(ineff!(PlayerInput::Jump), false);
+    /// }
+    /// ```
+    pub fn set_action_enabled<I: InputAction, K>(&mut self, action: IAWrp<I, K>, enabled: bool) {
+        let action_id = action.0.action_id();
+        self.processed_actions
+            .set_action_enabled(I::group_id(), action_id, enabled);
+        for mut player in &mut self.players {
+            player
+                .state
+                .set_action_enabled(I::group_id(), action_id, enabled);
+        }
+    }
+
+    /// Directly sets the state of a single action, bypassing its bindings. This is the building block for scripted
+    /// input, such as cutscenes, macros or tests.
+    ///
+    /// The delta only lasts for the current tick: ineffable overwrites it with the state of the bindings in the next
+    /// update. Call this after ineffable has updated its input state, so anywhere in the `Update` schedule. To keep a
+    /// continuous action active, apply `ContinuousDelta::Start` every tick. Pulses ignore the action's cooldown.
+    ///
+    /// Only the global `Ineffable` resource is changed, not the `IneffableInput` components of individual players.
+    /// Returns false if the action is not bound, or if the delta does not match the `InputKind` of the action.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::Vec2;
+    /// # use bevy_ineffable::prelude::*;
+    /// # use bevy_ineffable::resources::{ActionDelta, ContinuousDelta};
+    /// // Makes the player walk forward during a cutscene. Runs every tick, after ineffable has updated.
+    /// fn cutscene_walk(mut ineffable: IneffableCommands) {
+    ///     ineffable.apply_delta("PlayerInput", "Walk", ActionDelta::DualAxis(Vec2::new(0., 1.)));
+    ///     ineffable.apply_delta("PlayerInput", "Sprint", ActionDelta::Continuous(ContinuousDelta::Start));
+    /// }
+    /// ```
+    pub fn apply_delta(&mut self, group_id: &str, action_id: &str, delta: ActionDelta) -> bool {
+        self.processed_actions
+            .apply_delta(group_id, action_id, delta)
+    }
+
+    /// Captures the transient input state of all `InputAction`s in the global `Ineffable` resource, including the
+    /// progress of the post-acceptance-delay. Restore it later with `restore()`. See `InputSnapshot` for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// # use bevy_ineffable::resources::InputSnapshot;
+    /// #[derive(Resource)]
+    /// struct ConfirmedInput(InputSnapshot);
+    ///
+    /// fn save_confirmed_input(ineffable: IneffableCommands, mut confirmed: ResMut<ConfirmedInput>) {
+    ///     confirmed.0 = ineffable.snapshot();
+    /// }
+    ///
+    /// fn roll_back(mut ineffable: IneffableCommands, confirmed: Res<ConfirmedInput>) {
+    ///     ineffable.restore(&confirmed.0);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn snapshot(&self) -> InputSnapshot {
+        InputSnapshot {
+            post_acceptance_delay: self.settings.post_acceptance_delay.clone(),
+            ..self.processed_actions.snapshot()
+        }
+    }
+
+    /// Restores the global input state from a snapshot that was taken earlier with `snapshot()`.
+    ///
+    /// The state is applied to the bindings that are currently installed, so the snapshot can be restored after
+    /// rebinding keys. If a post-acceptance-delay is configured, it continues where it was in the snapshot.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy_ineffable::prelude::*;
+    /// // Setting a config resets the state of every action. Restoring a snapshot afterwards keeps
+    /// // toggles switched on and charges charging, but with the new bindings.
+    /// fn rebind_without_losing_state(mut ineffable: IneffableCommands) {
+    ///     let rebound = InputConfig::default();
+    ///     let snapshot = ineffable.snapshot();
+    ///     ineffable.set_config(&rebound);
+    ///     ineffable.restore(&snapshot);
+    /// }
+    /// ```
+    pub fn restore(&mut self, snapshot: &InputSnapshot) {
+        self.processed_actions.restore(snapshot);
+        if let Some(delay) = &mut self.settings.post_acceptance_delay {
+            delay.restore(snapshot.post_acceptance_delay.as_ref());
+        }
+    }
+
+    /// Loads the given configs as assets, then merges them in order and sets the result.
+    /// The config with `MergeMode::Base` also becomes the default config, see `reset_to_defaults()`.
+    ///
+    /// This requires the `IneffablePlugin`. With the `IneffableMinimalPlugin`, use `set_config()` instead.
+    pub fn load_configs<'a>(&mut self, paths: Vec<(MergeMode, impl Into<AssetPath<'a>>)>) {
+        self.load_configs_with_policy(paths, SettingsMergePolicy::default());
+    }
+
+    /// Same as `load_configs()`, but uses the given `SettingsMergePolicy` when merging the settings of the configs.
+    /// With `SettingsMergePolicy::KeepBase`, later configs can override bindings without clobbering the settings
+    /// defined in earlier configs.
+    pub fn load_configs_with_policy<'a>(
+        &mut self,
+        mut paths: Vec<(MergeMode, impl Into<AssetPath<'a>>)>,
+        settings_policy: SettingsMergePolicy,
+    ) {
+        let Some(asset_server) = &self.asset_server else {
+            error!("Loading configs requires the AssetPlugin and the IneffablePlugin. Use set_config() instead.");
+            return;
+        };
         let handles = paths
             .drain(..)
-            .map(|(merge_mode, path)| (merge_mode, self.asset_server.load(path)))
+            .map(|(merge_mode, path)| (merge_mode, asset_server.load(path)))
             .collect();
-        self.commands.insert_resource(CurrentlyLoading { handles });
+        self.commands.insert_resource(CurrentlyLoading {
+            handles,
+            settings_policy,
+        });
     }
 }
@@ -0,0 +1,136 @@
+//! Contains the `IneffableInput` component, which gives an entity its own input state. Useful for local multiplayer,
+//! where every player should only respond to their own input devices.
+
+use std::ops::{Deref, DerefMut};
+
+use bevy::prelude::{Component, Gamepad, Reflect};
+use serde::{Deserialize, Serialize};
+
+use crate::resources::Ineffable;
+
+/// Attach this to a player entity to give it its own input state, separate from the global `Ineffable` resource.
+///
+/// The component uses the same `InputConfig` as the global resource, but only listens to the input devices that are
+/// assigned to it. It can be queried exactly like the resource:
+/// `player_input.is_active(ineff!(ExampleInput::Example))`.
+///
+/// The post-acceptance-delay is shared between all players.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::components::InputDevices;
+/// # use bevy_ineffable::prelude::*;
+/// #[derive(InputAction)]
+/// pub enum PlayerInput {
+///     #[ineffable(continuous)]
+///     Jump,
+/// }
+/// fn spawn_players(mut commands: Commands, gamepads: Res<Gamepads>) {
+///     // Player one uses the keyboard, player two uses the first gamepad.
+///     commands.spawn(IneffableInput::new(InputDevices::keyboard_mouse()));
+///     if let Some(gamepad) = gamepads.iter().next() {
+///         commands.spawn(IneffableInput::new(InputDevices::gamepad(gamepad)));
+///     }
+/// }
+/// fn jump(players: Query<&IneffableInput>) {
+///     for input in &players {
+///         if input.is_active(ineff!(PlayerInput::Jump)) {
+///             // Make this player jump.
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Default, Component, Reflect, Clone)]
+pub struct IneffableInput {
+    /// The input devices that this entity listens to.
+    pub devices: InputDevices,
+    pub(crate) state: Ineffable,
+    /// Whether the state was built from the current config yet.
+    pub(crate) initialised: bool,
+}
+
+impl IneffableInput {
+    #[must_use]
+    pub fn new(devices: InputDevices) -> Self {
+        Self {
+            devices,
+            ..Self::default()
+        }
+    }
+}
+
+impl Deref for IneffableInput {
+    type Target = Ineffable;
+
+    fn deref(&self) -> &Self::Target {
+        &self.state
+    }
+}
+
+impl DerefMut for IneffableInput {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.state
+    }
+}
+
+/// Determines which input devices an `IneffableInput` listens to.
+///
+/// The default listens to all devices, just like the global `Ineffable` resource.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq, Hash)]
+pub struct InputDevices {
+    /// Whether this entity listens to the keyboard and mouse.
+    pub keyboard_mouse: bool,
+    /// Which gamepads this entity listens to.
+    pub gamepads: GamepadAssignment,
+}
+
+impl Default for InputDevices {
+    fn default() -> Self {
+        Self {
+            keyboard_mouse: true,
+            gamepads: GamepadAssignment::Any,
+        }
+    }
+}
+
+impl InputDevices {
+    /// Only listen to the keyboard and mouse, and ignore all gamepads.
+    #[must_use]
+    pub fn keyboard_mouse() -> Self {
+        Self {
+            keyboard_mouse: true,
+            gamepads: GamepadAssignment::NoGamepad,
+        }
+    }
+    /// Only listen to the given gamepad, and ignore the keyboard and mouse.
+    #[must_use]
+    pub fn gamepad(gamepad: Gamepad) -> Self {
+        Self {
+            keyboard_mouse: false,
+            gamepads: GamepadAssignment::Specific(gamepad),
+        }
+    }
+    /// True iff the given gamepad is assigned.
+    #[must_use]
+    pub fn uses_gamepad(&self, gamepad: Gamepad) -> bool {
+        match self.gamepads {
+            GamepadAssignment::Any => true,
+            GamepadAssignment::Specific(assigned) => assigned == gamepad,
+            GamepadAssignment::NoGamepad => false,
+        }
+    }
+}
+
+/// Determines which gamepads an `IneffableInput` listens to.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadAssignment {
+    /// Listen to all connected gamepads.
+    #[default]
+    Any,
+    /// Only listen to this gamepad.
+    Specific(Gamepad),
+    /// Don't listen to any gamepads.
+    NoGamepad,
+}
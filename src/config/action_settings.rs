@@ -0,0 +1,49 @@
+use bevy::reflect::Reflect;
+use serde::{Deserialize, Serialize};
+
+use crate::config::DurationInMillis;
+
+/// Settings that apply to a single `InputAction`, rather than to all of them.
+///
+/// Any setting that is not set falls back to the global setting in the `InputConfig`.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq)]
+pub struct ActionSettings {
+    /// Overrides the global chord blocking setting for this action.
+    ///
+    /// When chord blocking is enabled, a chord is blocked while a more specific chord that contains it is held down.
+    /// For example, `Ctrl-S` blocks plain `S`. When it is disabled, overlapping chords both activate.
+    #[serde(default)]
+    pub chord_blocking: Option<bool>,
+    /// Multiplies the sensitivity of every analog binding of this action, on top of the sensitivity that is set in
+    /// the binding itself. Lets players tweak sensitivity without having to know which device is bound.
+    #[serde(default)]
+    pub sensitivity: Option<f32>,
+    /// If true, inverts every binding of this action, on top of the inversion that is set in the binding itself.
+    /// Keys and toggles swap their directions. For dual-axis actions, both axes are inverted.
+    #[serde(default)]
+    pub invert: Option<bool>,
+    /// The minimum time between two pulses of this action. Pulses that come sooner are ignored.
+    /// Only affects pulse actions.
+    #[serde(default)]
+    pub cooldown: Option<DurationInMillis>,
+    /// If true, the global post-acceptance delay does not apply to this action: its input is never blocked by the
+    /// delay, and it does not start the delay either. Meant for things like camera look, that should stay responsive.
+    #[serde(default)]
+    pub ignore_post_acceptance_delay: Option<bool>,
+}
+
+impl ActionSettings {
+    /// Creates new settings containing the settings in `other`, falling back to those in `self`.
+    #[must_use]
+    pub(crate) fn merge(&self, other: &ActionSettings) -> Self {
+        Self {
+            chord_blocking: other.chord_blocking.or(self.chord_blocking),
+            sensitivity: other.sensitivity.or(self.sensitivity),
+            invert: other.invert.or(self.invert),
+            cooldown: other.cooldown.or(self.cooldown),
+            ignore_post_acceptance_delay: other
+                .ignore_post_acceptance_delay
+                .or(self.ignore_post_acceptance_delay),
+        }
+    }
+}
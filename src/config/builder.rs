@@ -5,9 +5,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::bindings::InputBinding;
 use crate::config::input_config::InputConfig;
-use crate::config::DurationInMillis;
+use crate::config::{ActionSettings, DurationInMillis};
 use crate::input_action::InputAction;
-use crate::phantom::{IAWrp, IBWrp};
+use crate::phantom::{IAWrp, IBWrp, Pulse};
+use crate::processed::processor::validate_bindings;
+use crate::reporting::InputConfigReport;
 
 /// Builder to create an `InputConfig` programmatically.
 ///
@@ -17,7 +19,18 @@ use crate::phantom::{IAWrp, IBWrp};
 pub struct InputConfigBuilder {
     double_click_timing: Option<DurationInMillis>,
     post_acceptance_delay: Option<DurationInMillis>,
+    charge_retain_window: Option<DurationInMillis>,
+    chord_blocking: Option<bool>,
+    hold_to_toggle: Option<bool>,
+    global_sensitivity: Option<f32>,
+    mouse_sensitivity: Option<f32>,
+    mouse_sensitivity_x: Option<f32>,
+    mouse_sensitivity_y: Option<f32>,
+    motion_smoothing: Option<f32>,
+    last_device_wins: Option<bool>,
     bindings: HashMap<String, HashMap<String, Vec<InputBinding>>>,
+    action_settings: HashMap<String, HashMap<String, ActionSettings>>,
+    profiles: HashMap<String, InputConfig>,
 }
 
 impl InputConfigBuilder {
@@ -45,12 +58,386 @@ impl InputConfigBuilder {
 
     /// After detecting input, the system will ignore any further input for this amount of time.
     /// By default, the delay is turned off. Through this method, you can change it to any duration you like.
+    ///
+    /// Only new input is ignored. Letting go of a held input is still detected during the delay.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Sprint,
+    ///     #[ineffable(pulse)]
+    ///     Jump,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .post_acceptance_delay(Duration::from_millis(100))
+    ///     .bind(ineff!(PlayerInput::Sprint), ContinuousBinding::hold(KeyCode::ShiftLeft))
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::Space))
+    ///     .build();
+    /// ```
     #[must_use]
     pub fn post_acceptance_delay(mut self, post_acceptance_delay: Duration) -> Self {
         self.post_acceptance_delay = Some(post_acceptance_delay.as_millis() as u64);
         self
     }
 
+    /// When a continuous action deactivates, its charge time is retained for this amount of time. If the action
+    /// activates again within this window, it continues charging where it left off.
+    ///
+    /// This lets players roll from one bound key to another without a gap resetting the charge.
+    /// By default, the window is zero and deactivating an action always resets its charge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Charge,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .charge_retain_window(Duration::from_millis(100))
+    ///     .bind(ineff!(PlayerInput::Charge), ContinuousBinding::hold(KeyCode::KeyA))
+    ///     .bind(ineff!(PlayerInput::Charge), ContinuousBinding::hold(KeyCode::KeyB))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn charge_retain_window(mut self, charge_retain_window: Duration) -> Self {
+        self.charge_retain_window = Some(charge_retain_window.as_millis() as u64);
+        self
+    }
+
+    /// By default, a chord is blocked while a more specific chord that contains it is held down. For example,
+    /// binding one action to `Ctrl-S` and another to `S` means that pressing `Ctrl-S` only activates the first.
+    ///
+    /// Disabling chord blocking means that overlapping chords both activate: pressing `Ctrl-S` activates both actions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum EditorInput {
+    ///     #[ineffable(continuous)]
+    ///     Save,
+    ///     #[ineffable(continuous)]
+    ///     Down,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .chord_blocking(false)
+    ///     .bind(
+    ///         ineff!(EditorInput::Save),
+    ///         ContinuousBinding::hold((KeyCode::ControlLeft, KeyCode::KeyS)),
+    ///     )
+    ///     .bind(ineff!(EditorInput::Down), ContinuousBinding::hold(KeyCode::KeyS))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn chord_blocking(mut self, enabled: bool) -> Self {
+        self.chord_blocking = Some(enabled);
+        self
+    }
+
+    /// Same as `chord_blocking()`, but only for the given action. This overrides the global setting.
+    ///
+    /// Note that this determines whether the bindings of the given action can be blocked by other chords.
+    /// It does not stop the given action's bindings from blocking those of other actions.
+    #[must_use]
+    pub fn chord_blocking_for<I: InputAction, Kind>(
+        mut self,
+        action: IAWrp<I, Kind>,
+        enabled: bool,
+    ) -> Self {
+        self.action_settings_mut(&action).chord_blocking = Some(enabled);
+        self
+    }
+
+    /// Multiplies the sensitivity of all analog bindings of the given action, on top of the sensitivity set in each
+    /// binding. Only affects single-axis and dual-axis actions.
+    ///
+    /// Because this is stored separately from the bindings, a player config can contain only this setting.
+    /// Merged on top of the base config, it changes the sensitivity of whatever device the base config binds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(single_axis)]
+    ///     LookHorizontal,
+    /// }
+    /// let base = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(PlayerInput::LookHorizontal),
+    ///         SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::RightStickX)).build(),
+    ///     )
+    ///     .build();
+    /// let player = InputConfig::builder()
+    ///     .sensitivity_for(ineff!(PlayerInput::LookHorizontal), 2.)
+    ///     .invert_for(ineff!(PlayerInput::LookHorizontal), true)
+    ///     .build();
+    /// let config = base.merge_replace(&player);
+    /// ```
+    #[must_use]
+    pub fn sensitivity_for<I: InputAction, Kind>(
+        mut self,
+        action: IAWrp<I, Kind>,
+        sensitivity: f32,
+    ) -> Self {
+        self.action_settings_mut(&action).sensitivity = Some(sensitivity);
+        self
+    }
+
+    /// Inverts all bindings of the given action, on top of the inversion set in each binding. This includes keys and
+    /// toggles, which swap their directions.
+    /// Only affects single-axis and dual-axis actions. For dual-axis actions, both axes are inverted.
+    ///
+    /// See `sensitivity_for()` for an example.
+    #[must_use]
+    pub fn invert_for<I: InputAction, Kind>(
+        mut self,
+        action: IAWrp<I, Kind>,
+        invert: bool,
+    ) -> Self {
+        self.action_settings_mut(&action).invert = Some(invert);
+        self
+    }
+
+    /// Rate-limits the given pulse action: after it pulses, further pulses are ignored until the cooldown has
+    /// elapsed. Mashing the button does not make the action pulse any faster. Unlike auto-fire, holding the button
+    /// down does not make it pulse again once the cooldown is over.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(pulse)]
+    ///     Shoot,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(ineff!(PlayerInput::Shoot), PulseBinding::just_pressed(KeyCode::Space))
+    ///     .cooldown_for(ineff!(PlayerInput::Shoot), Duration::from_millis(250))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn cooldown_for<I: InputAction>(
+        mut self,
+        action: IAWrp<I, Pulse>,
+        cooldown: Duration,
+    ) -> Self {
+        self.action_settings_mut(&action).cooldown = Some(cooldown.as_millis() as u64);
+        self
+    }
+
+    /// Exempts the given action from the post-acceptance delay. Its input is not blocked while the delay is active, and
+    /// using it does not start the delay either. See `post_acceptance_delay()`.
+    ///
+    /// The delay is meant for discrete presses, like navigating a menu. Exempting actions like camera look keeps them
+    /// responsive, so that the accessibility setting is also usable in action games.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Look,
+    ///     #[ineffable(pulse)]
+    ///     Jump,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .post_acceptance_delay(Duration::from_millis(100))
+    ///     .ignore_post_acceptance_delay_for(ineff!(PlayerInput::Look), true)
+    ///     .bind(ineff!(PlayerInput::Look), ContinuousBinding::hold(KeyCode::KeyL))
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::Space))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn ignore_post_acceptance_delay_for<I: InputAction, Kind>(
+        mut self,
+        action: IAWrp<I, Kind>,
+        ignore: bool,
+    ) -> Self {
+        self.action_settings_mut(&action)
+            .ignore_post_acceptance_delay = Some(ignore);
+        self
+    }
+
+    /// An accessibility setting for players who can't hold down keys. When enabled, every hold binding behaves like a
+    /// toggle: pressing it once turns the action on, pressing it again turns it off.
+    /// Bindings that are already toggles keep working as before. By default, this is turned off.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Sprint,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .hold_to_toggle(true)
+    ///     .bind(ineff!(PlayerInput::Sprint), ContinuousBinding::hold(KeyCode::ShiftLeft))
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn hold_to_toggle(mut self, enabled: bool) -> Self {
+        self.hold_to_toggle = Some(enabled);
+        self
+    }
+
+    /// Scales every analog axis, whatever device it is bound to. This is a single "overall input speed" setting for
+    /// accessibility. It is multiplied with all other sensitivity settings, including the `Sensitivity` configured on
+    /// the bindings themselves. The default is 1.0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum CameraInput {
+    ///     #[ineffable(single_axis)]
+    ///     Yaw,
+    /// }
+    /// // The mouse moves the camera at half the speed that the binding on its own would.
+    /// let config = InputConfig::builder()
+    ///     .global_sensitivity(0.5)
+    ///     .bind(
+    ///         ineff!(CameraInput::Yaw),
+    ///         SingleAxisBinding::analog(AnalogInput::MouseMotionX).set_sensitivity(2.).build(),
+    ///     )
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn global_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.global_sensitivity = Some(sensitivity);
+        self
+    }
+
+    /// Scales the mouse motion reported by `AnalogInput::MouseMotionX` and `AnalogInput::MouseMotionY`.
+    /// This is the typical mouse-look sensitivity setting: players can tune it without touching the bindings.
+    /// Any `Sensitivity` configured on the bindings themselves is applied on top of it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum CameraInput {
+    ///     #[ineffable(single_axis)]
+    ///     Yaw,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .mouse_sensitivity(2.)
+    ///     .bind(ineff!(CameraInput::Yaw), SingleAxisBinding::analog(AnalogInput::MouseMotionX).build())
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn mouse_sensitivity(mut self, sensitivity: f32) -> Self {
+        self.mouse_sensitivity = Some(sensitivity);
+        self
+    }
+
+    /// Scales the horizontal and vertical mouse motion separately. These are multiplied with the value passed to
+    /// `mouse_sensitivity()`, if any.
+    #[must_use]
+    pub fn mouse_sensitivity_axes(mut self, x: f32, y: f32) -> Self {
+        self.mouse_sensitivity_x = Some(x);
+        self.mouse_sensitivity_y = Some(y);
+        self
+    }
+
+    /// Smooths out jittery mouse motion with an exponential moving average. Each tick, the reported motion is a mix
+    /// of the raw motion and the motion reported last tick. The `factor` is the weight of last tick's motion at 60
+    /// frames per second, and is adjusted for the actual length of each tick, so the smoothing feels the same at any
+    /// frame rate. Higher values are smoother, but take longer to catch up. It is clamped between 0.0 and 0.95.
+    /// A factor of zero (the default) turns smoothing off, reporting exactly the raw motion of each tick.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum CameraInput {
+    ///     #[ineffable(single_axis)]
+    ///     Yaw,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .motion_smoothing(0.5)
+    ///     .bind(ineff!(CameraInput::Yaw), SingleAxisBinding::analog(AnalogInput::MouseMotionX).build())
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn motion_smoothing(mut self, factor: f32) -> Self {
+        self.motion_smoothing = Some(factor);
+        self
+    }
+
+    /// When an axis is bound to several devices, for example both a stick and the keyboard, only listen to the
+    /// device that was used last. Pressing a key makes the keyboard win, and the stick is ignored until it is
+    /// deliberately moved away from where it was resting. This stops a slightly drifting stick from fighting
+    /// the keyboard, without forcing players to unbind either device.
+    /// It is disabled by default, in which case the binding with the greatest magnitude wins.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(single_axis)]
+    ///     Steering,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .last_device_wins(true)
+    ///     .bind(ineff!(PlayerInput::Steering), SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::LeftStickX)).build())
+    ///     .bind(ineff!(PlayerInput::Steering), SingleAxisBinding::hold().set_negative(KeyCode::KeyA).set_positive(KeyCode::KeyD).build())
+    ///     .build();
+    /// ```
+    #[must_use]
+    pub fn last_device_wins(mut self, enabled: bool) -> Self {
+        self.last_device_wins = Some(enabled);
+        self
+    }
+
+    /// Adds a named profile: an alternative control scheme that can be switched to with
+    /// `IneffableCommands::activate_profile()`. The profile only needs to contain the bindings and settings that
+    /// differ from the rest of the config.
+    #[must_use]
+    pub fn profile(mut self, name: impl Into<String>, profile: InputConfig) -> Self {
+        self.profiles.insert(name.into(), profile);
+        self
+    }
+
     /// Bind an input method to an `InputAction`.
     ///
     ///  * `action`:  - Provide it like this: `ineff!(ExampleInput::Example)`
@@ -87,6 +474,67 @@ impl InputConfigBuilder {
         self
     }
 
+    /// Bind an input method to an action by its group_id and action_id. Meant for dynamic actions that were
+    /// registered with `InputActionRegistrar::register_dynamic_action()`, and for building configs from external
+    /// data. Accepts both the wrapped bindings from the builders, and plain `InputBinding`s.
+    ///
+    /// Unlike `bind()`, there is no compile-time guarantee that the action exists, or that it is of the same
+    /// `InputKind` as the binding. Mistakes are reported when the config is validated. Prefer `bind()` whenever
+    /// possible.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::ecs::system::RunSystemOnce;
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(pulse)]
+    ///     Jump,
+    /// }
+    /// let jump = PulseBinding::just_pressed(KeyCode::Space).0;
+    /// let config = InputConfig::builder()
+    ///     .bind_dynamic("PlayerInput", "Jump", jump.clone())
+    ///     .build();
+    /// assert_eq!(config.bindings["PlayerInput"]["Jump"], vec![jump]);
+    ///
+    /// // Binding a continuous binding to a pulse action compiles, but doesn't pass validation.
+    /// let wrong_kind = InputConfig::builder()
+    ///     .bind_dynamic("PlayerInput", "Jump", ContinuousBinding::hold(KeyCode::Space))
+    ///     .build();
+    /// let mut app = App::new();
+    /// app.add_plugins((MinimalPlugins, IneffableMinimalPlugin))
+    ///     .register_input_action::<PlayerInput>();
+    /// app.world_mut().run_system_once(move |ineffable: IneffableCommands| {
+    ///     assert!(!ineffable.validate(&config).has_errors());
+    ///     assert!(ineffable.validate(&wrong_kind).has_errors());
+    /// });
+    /// ```
+    #[must_use]
+    pub fn bind_dynamic(
+        mut self,
+        group_id: impl Into<String>,
+        action_id: impl Into<String>,
+        binding: impl Into<InputBinding>,
+    ) -> Self {
+        let group = self.bindings.entry(group_id.into()).or_default();
+        let bindings = group.entry(action_id.into()).or_default();
+        bindings.push(binding.into());
+        self
+    }
+
+    fn action_settings_mut<I: InputAction, Kind>(
+        &mut self,
+        action: &IAWrp<I, Kind>,
+    ) -> &mut ActionSettings {
+        self.action_settings
+            .entry(I::group_id().to_string())
+            .or_default()
+            .entry(action.0.action_id().to_string())
+            .or_default()
+    }
+
     /// Build a new `InputConfig` with the settings currently in the builder.
     /// This does not consume the builder: it can be re-used.
     #[must_use]
@@ -95,6 +543,49 @@ impl InputConfigBuilder {
             bindings: self.bindings.clone(),
             double_click_timing: self.double_click_timing,
             post_acceptance_delay: self.post_acceptance_delay,
+            charge_retain_window: self.charge_retain_window,
+            chord_blocking: self.chord_blocking,
+            hold_to_toggle: self.hold_to_toggle,
+            global_sensitivity: self.global_sensitivity,
+            mouse_sensitivity: self.mouse_sensitivity,
+            mouse_sensitivity_x: self.mouse_sensitivity_x,
+            mouse_sensitivity_y: self.mouse_sensitivity_y,
+            motion_smoothing: self.motion_smoothing,
+            last_device_wins: self.last_device_wins,
+            action_settings: self.action_settings.clone(),
+            profiles: self.profiles.clone(),
         }
     }
+
+    /// Same as `build()`, but also scans the bindings for mistakes, such as dummy bindings or chords that contain
+    /// the same input twice. This lets you catch mistakes early while prototyping.
+    ///
+    /// Only the bindings themselves are checked. Whether the `InputAction`s were registered is not known at
+    /// this point, so for a complete report, use `IneffableCommands::validate()` or `IneffableCommands::set_config()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::KeyCode;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Sprint,
+    /// }
+    /// let (_config, report) = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(PlayerInput::Sprint),
+    ///         ContinuousBinding::hold((KeyCode::ShiftLeft, KeyCode::ShiftLeft)),
+    ///     )
+    ///     .build_validated();
+    /// // Oops, the chord contains the same key twice.
+    /// assert!(!report.is_empty());
+    /// ```
+    #[must_use]
+    pub fn build_validated(&self) -> (InputConfig, InputConfigReport) {
+        let config = self.build();
+        let report = validate_bindings(&config);
+        (config, report)
+    }
 }
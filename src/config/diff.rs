@@ -0,0 +1,72 @@
+//! Contains types that describe the differences between two `InputConfig`s.
+//!
+//! Useful for showing the player which of their bindings deviate from the defaults, or which changes they haven't
+//! saved yet.
+
+use serde::{Deserialize, Serialize};
+
+use crate::bindings::InputBinding;
+
+/// The differences between two `InputConfig`s. Obtained by calling `InputConfig::diff()`.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConfigDiff {
+    /// All actions whose bindings differ, sorted by group_id and then by action_id.
+    pub actions: Vec<ActionDiff>,
+    /// All settings that differ.
+    pub settings: Vec<SettingDiff>,
+}
+
+impl ConfigDiff {
+    /// True iff there are no differences at all between the two configs.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.actions.is_empty() && self.settings.is_empty()
+    }
+}
+
+/// Describes how the bindings of a single action differ between two configs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ActionDiff {
+    /// Which group the `InputAction` belongs to. This is the name of the enum.
+    pub group_id: String,
+    /// The name of the `InputAction`. This is the name of the enum variant.
+    pub action_id: String,
+    /// How the bindings differ.
+    pub change: BindingsChange,
+}
+
+/// How the bindings of a single action differ between two configs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub enum BindingsChange {
+    /// The action is only defined in the other config.
+    Added(Vec<InputBinding>),
+    /// The action is only defined in this config.
+    Removed(Vec<InputBinding>),
+    /// The action is defined in both configs, but its bindings differ.
+    Changed {
+        before: Vec<InputBinding>,
+        after: Vec<InputBinding>,
+    },
+}
+
+/// A setting that differs between two configs.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub enum SettingDiff {
+    DoubleClickTiming,
+    PostAcceptanceDelay,
+    ChargeRetainWindow,
+    ChordBlocking,
+    HoldToToggle,
+    GlobalSensitivity,
+    /// The global mouse sensitivity, or the sensitivity of one of its axes.
+    MouseSensitivity,
+    MotionSmoothing,
+    LastDeviceWins,
+    /// One or more of the named profiles differ.
+    Profiles,
+    /// The settings specific to the given action differ.
+    ActionSettings {
+        group_id: String,
+        action_id: String,
+    },
+}
@@ -1,4 +1,5 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
 use bevy::asset::Asset;
 use bevy::reflect::Reflect;
@@ -7,6 +8,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::bindings::InputBinding;
 use crate::config::builder::InputConfigBuilder;
+use crate::config::diff::{ActionDiff, BindingsChange, ConfigDiff, SettingDiff};
+use crate::config::simple_asset_loading::{MergeMode, SettingsMergePolicy};
+use crate::config::ActionSettings;
 
 /// Contains input settings and keybindings for the game.
 ///
@@ -22,7 +26,51 @@ pub struct InputConfig {
     #[serde(default)]
     pub post_acceptance_delay: Option<DurationInMillis>,
     #[serde(default)]
+    pub charge_retain_window: Option<DurationInMillis>,
+    /// Accessibility setting. If enabled, hold bindings behave like toggles: pressing them turns their action on or
+    /// off. Bindings that are already toggles are not affected.
+    #[serde(default)]
+    pub hold_to_toggle: Option<bool>,
+    /// If enabled (which is the default), a chord is blocked while a more specific chord that contains it is held
+    /// down. For example, `Ctrl-S` blocks plain `S`. If disabled, overlapping chords both activate.
+    ///
+    /// This can be overridden for individual actions in the `action_settings`.
+    #[serde(default)]
+    pub chord_blocking: Option<bool>,
+    /// Scales every analog axis, on top of all other sensitivity settings. Useful as a single "overall input speed"
+    /// slider. Defaults to 1.0.
+    #[serde(default)]
+    pub global_sensitivity: Option<f32>,
+    /// Scales the mouse motion on both axes. Is multiplied with the per-axis values below, and with any
+    /// `Sensitivity` configured on the bindings themselves.
+    #[serde(default)]
+    pub mouse_sensitivity: Option<f32>,
+    /// Scales the horizontal mouse motion.
+    #[serde(default)]
+    pub mouse_sensitivity_x: Option<f32>,
+    /// Scales the vertical mouse motion.
+    #[serde(default)]
+    pub mouse_sensitivity_y: Option<f32>,
+    /// Smooths out mouse motion with an exponential moving average. This is the weight given to the motion of
+    /// previous ticks at 60 frames per second, between 0.0 and 0.95. Zero (the default) turns smoothing off.
+    #[serde(default)]
+    pub motion_smoothing: Option<f32>,
+    /// If enabled, an axis that is bound to multiple devices only listens to the device that was used last. Once a
+    /// key is pressed, a slightly drifting stick is ignored until it is deliberately moved, and vice versa.
+    /// Disabled by default, in which case the binding with the greatest magnitude wins.
+    #[serde(default)]
+    pub last_device_wins: Option<bool>,
+    #[serde(default)]
     pub bindings: HashMap<String, HashMap<String, Vec<InputBinding>>>,
+    /// Settings that only apply to specific actions. Keyed by group_id, then by action_id.
+    #[serde(default)]
+    pub action_settings: HashMap<String, HashMap<String, ActionSettings>>,
+    /// Named alternative control schemes, such as "Lefty" or "Claw". Each profile only needs to contain what it
+    /// changes: when activated with `IneffableCommands::activate_profile()`, it is merged on top of this config.
+    // Not reflected, because reflecting a type that contains itself does not compile.
+    #[serde(default)]
+    #[reflect(ignore)]
+    pub profiles: HashMap<String, InputConfig>,
     // #[serde(default)]
     // pub macros: Vec<Macro>,
 }
@@ -59,6 +107,7 @@ impl InputConfig {
     /// This config is considered the base; earlier in the load order. The `other` config is later in the load order
     /// and will (partially) override this one. Any InputActions or miscellaneous settings that are present in the
     /// `other` config will take precedence, otherwise it will fall back to the value in this one.
+    /// To keep the settings of this config instead, use `merge_with()` and `SettingsMergePolicy::KeepBase`.
     ///
     /// # Examples
     ///
@@ -99,15 +148,91 @@ impl InputConfig {
     /// ```
     #[must_use]
     pub fn merge_replace(&self, other: &InputConfig) -> Self {
-        self.merge_inner(other, false)
+        self.merge_inner(other, false, &SettingsMergePolicy::PreferOther)
     }
 
     /// If the base and appending configs both define bindings for the same action, bindings are appended and all
     /// bindings end up in the final result. This is in contrast to the merge_replace() function, that replaces
     /// bindings in the base config, if the action is defined in the replace config.
+    ///
+    /// If the two configs disagree about the `InputKind` of an action, the merged config ends up with bindings of
+    /// multiple kinds for that action. The validator reports this.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Sprint,
+    /// }
+    /// let base = InputConfig::builder()
+    ///     .bind(ineff!(PlayerInput::Sprint), ContinuousBinding::hold(KeyCode::ShiftLeft))
+    ///     .build();
+    /// // This override was edited by hand, and binds Sprint as a pulse action.
+    /// let wrong = InputConfig::builder()
+    ///     .bind_dynamic("PlayerInput", "Sprint", PulseBinding::just_pressed(KeyCode::KeyR))
+    ///     .build();
+    ///
+    /// let merged = base.merge_append(&wrong);
+    /// ```
     #[must_use]
     pub fn merge_append(&self, other: &InputConfig) -> Self {
-        self.merge_inner(other, true)
+        self.merge_inner(other, true, &SettingsMergePolicy::PreferOther)
+    }
+
+    /// Merges the `other` config into this one, using the given `MergeMode` for the bindings and the given
+    /// `SettingsMergePolicy` for the settings. With `MergeMode::Base`, the result is simply a copy of `other`.
+    ///
+    /// `merge_replace()` and `merge_append()` always prefer the settings in the `other` config. Use this function
+    /// instead to append or replace bindings while preserving the settings in this config.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::prelude::KeyCode;
+    /// # use bevy_ineffable::config::simple_asset_loading::{MergeMode, SettingsMergePolicy};
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(pulse)]
+    ///     Jump,
+    /// }
+    /// let base = InputConfig::builder()
+    ///     .double_click_timing(Duration::from_millis(500))
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::Space))
+    ///     .build();
+    /// let profile = InputConfig::builder()
+    ///     .double_click_timing(Duration::from_millis(200))
+    ///     .post_acceptance_delay(Duration::from_millis(100))
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::KeyW))
+    ///     .build();
+    ///
+    /// let merged = base.merge_with(&profile, &MergeMode::Replace, &SettingsMergePolicy::KeepBase);
+    /// // The bindings were replaced, but the double-click timing of the base config was kept.
+    /// assert_eq!(merged.bindings, profile.bindings);
+    /// assert_eq!(merged.double_click_timing, Some(500));
+    /// // Settings that are missing from the base config are still taken from the profile.
+    /// assert_eq!(merged.post_acceptance_delay, Some(100));
+    ///
+    /// let merged = base.merge_with(&profile, &MergeMode::Replace, &SettingsMergePolicy::PreferOther);
+    /// assert_eq!(merged.double_click_timing, Some(200));
+    /// ```
+    #[must_use]
+    pub fn merge_with(
+        &self,
+        other: &InputConfig,
+        merge_mode: &MergeMode,
+        settings_policy: &SettingsMergePolicy,
+    ) -> Self {
+        match merge_mode {
+            MergeMode::Base => other.clone(),
+            MergeMode::Append => self.merge_inner(other, true, settings_policy),
+            MergeMode::Replace => self.merge_inner(other, false, settings_policy),
+        }
     }
 
     /// Deprecated, kept (for now) for backwards compatibility.
@@ -118,13 +243,14 @@ impl InputConfig {
     }
 
     #[must_use]
-    fn merge_inner(&self, other: &InputConfig, append: bool) -> Self {
+    fn merge_inner(
+        &self,
+        other: &InputConfig,
+        append: bool,
+        settings_policy: &SettingsMergePolicy,
+    ) -> Self {
         let mut value = self.clone();
-        for (group_id, action_id, action) in other.bindings.iter().flat_map(|(group_id, group)| {
-            group
-                .iter()
-                .map(move |(action_id, action)| (group_id, action_id, action))
-        }) {
+        for (group_id, action_id, action) in iter_nested(&other.bindings) {
             let actions = value.bindings.entry(group_id.clone()).or_default();
             let bindings = actions.entry(action_id.clone()).or_default();
             if !append {
@@ -132,12 +258,248 @@ impl InputConfig {
             }
             bindings.append(&mut action.clone());
         }
-        if other.post_acceptance_delay.is_some() {
-            value.post_acceptance_delay = other.post_acceptance_delay;
+        value.post_acceptance_delay =
+            settings_policy.pick(self.post_acceptance_delay, other.post_acceptance_delay);
+        value.double_click_timing =
+            settings_policy.pick(self.double_click_timing, other.double_click_timing);
+        value.charge_retain_window =
+            settings_policy.pick(self.charge_retain_window, other.charge_retain_window);
+        value.chord_blocking = settings_policy.pick(self.chord_blocking, other.chord_blocking);
+        value.hold_to_toggle = settings_policy.pick(self.hold_to_toggle, other.hold_to_toggle);
+        value.global_sensitivity =
+            settings_policy.pick(self.global_sensitivity, other.global_sensitivity);
+        value.mouse_sensitivity =
+            settings_policy.pick(self.mouse_sensitivity, other.mouse_sensitivity);
+        value.mouse_sensitivity_x =
+            settings_policy.pick(self.mouse_sensitivity_x, other.mouse_sensitivity_x);
+        value.mouse_sensitivity_y =
+            settings_policy.pick(self.mouse_sensitivity_y, other.mouse_sensitivity_y);
+        value.motion_smoothing =
+            settings_policy.pick(self.motion_smoothing, other.motion_smoothing);
+        value.last_device_wins =
+            settings_policy.pick(self.last_device_wins, other.last_device_wins);
+        for (group_id, action_id, settings) in iter_nested(&other.action_settings) {
+            let actions = value.action_settings.entry(group_id.clone()).or_default();
+            let merged = actions.get(action_id).map_or_else(
+                || settings.clone(),
+                |base| match settings_policy {
+                    SettingsMergePolicy::KeepBase => settings.merge(base),
+                    SettingsMergePolicy::PreferOther => base.merge(settings),
+                },
+            );
+            actions.insert(action_id.clone(), merged);
         }
-        if other.double_click_timing.is_some() {
-            value.double_click_timing = other.double_click_timing;
+        for (name, profile) in &other.profiles {
+            let merged = value.profiles.get(name).map_or_else(
+                || profile.clone(),
+                |base| base.merge_inner(profile, append, settings_policy),
+            );
+            value.profiles.insert(name.clone(), merged);
         }
         value
     }
+
+    /// Returns the config that results from activating the profile with the given name: the profile merged on top of
+    /// this config. Returns None if there is no such profile.
+    #[must_use]
+    pub fn with_profile(&self, name: &str) -> Option<InputConfig> {
+        self.profiles
+            .get(name)
+            .map(|profile| self.merge_replace(profile))
+    }
+
+    /// Lists the differences between this config and the `other` one. This config is considered the "before" and
+    /// the `other` config the "after". Bindings are compared per action, and settings are compared one by one.
+    ///
+    /// This is meant to support a "changed bindings" view in a settings UI. For example, the player's config can be
+    /// compared to the default config, to show which bindings deviate from the defaults.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::KeyCode;
+    /// # use bevy_ineffable::config::diff::{ActionDiff, BindingsChange};
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(continuous)]
+    ///     Sprint,
+    ///     #[ineffable(pulse)]
+    ///     Jump,
+    /// }
+    /// let defaults = InputConfig::builder()
+    ///     .bind(ineff!(PlayerInput::Sprint), ContinuousBinding::hold(KeyCode::ShiftLeft))
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::Space))
+    ///     .build();
+    /// let custom = InputConfig::builder()
+    ///     .bind(ineff!(PlayerInput::Sprint), ContinuousBinding::hold(KeyCode::ShiftLeft))
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::KeyW))
+    ///     .build();
+    ///
+    /// let diff = defaults.diff(&custom);
+    /// assert!(diff.settings.is_empty());
+    /// assert_eq!(diff.actions.len(), 1);
+    /// let ActionDiff { group_id, action_id, change } = &diff.actions[0];
+    /// assert_eq!((group_id.as_str(), action_id.as_str()), ("PlayerInput", "Jump"));
+    /// assert!(matches!(change, BindingsChange::Changed { .. }));
+    ///
+    /// assert!(defaults.diff(&defaults).is_empty());
+    /// ```
+    #[must_use]
+    pub fn diff(&self, other: &InputConfig) -> ConfigDiff {
+        let mut actions = Vec::new();
+        for (group_id, action_id, before) in iter_nested(&self.bindings) {
+            let change = match other.bindings.get(group_id).and_then(|g| g.get(action_id)) {
+                None => BindingsChange::Removed(before.clone()),
+                Some(after) if after != before => BindingsChange::Changed {
+                    before: before.clone(),
+                    after: after.clone(),
+                },
+                Some(_) => continue,
+            };
+            actions.push(ActionDiff {
+                group_id: group_id.clone(),
+                action_id: action_id.clone(),
+                change,
+            });
+        }
+        for (group_id, action_id, after) in iter_nested(&other.bindings) {
+            if self
+                .bindings
+                .get(group_id)
+                .and_then(|g| g.get(action_id))
+                .is_none()
+            {
+                actions.push(ActionDiff {
+                    group_id: group_id.clone(),
+                    action_id: action_id.clone(),
+                    change: BindingsChange::Added(after.clone()),
+                });
+            }
+        }
+        actions.sort_by(|a, b| (&a.group_id, &a.action_id).cmp(&(&b.group_id, &b.action_id)));
+
+        let mut settings = Vec::new();
+        if self.double_click_timing != other.double_click_timing {
+            settings.push(SettingDiff::DoubleClickTiming);
+        }
+        if self.post_acceptance_delay != other.post_acceptance_delay {
+            settings.push(SettingDiff::PostAcceptanceDelay);
+        }
+        if self.charge_retain_window != other.charge_retain_window {
+            settings.push(SettingDiff::ChargeRetainWindow);
+        }
+        if self.chord_blocking != other.chord_blocking {
+            settings.push(SettingDiff::ChordBlocking);
+        }
+        if self.hold_to_toggle != other.hold_to_toggle {
+            settings.push(SettingDiff::HoldToToggle);
+        }
+        if self.global_sensitivity != other.global_sensitivity {
+            settings.push(SettingDiff::GlobalSensitivity);
+        }
+        if (
+            self.mouse_sensitivity,
+            self.mouse_sensitivity_x,
+            self.mouse_sensitivity_y,
+        ) != (
+            other.mouse_sensitivity,
+            other.mouse_sensitivity_x,
+            other.mouse_sensitivity_y,
+        ) {
+            settings.push(SettingDiff::MouseSensitivity);
+        }
+        if self.motion_smoothing != other.motion_smoothing {
+            settings.push(SettingDiff::MotionSmoothing);
+        }
+        if self.last_device_wins != other.last_device_wins {
+            settings.push(SettingDiff::LastDeviceWins);
+        }
+        if self.profiles != other.profiles {
+            settings.push(SettingDiff::Profiles);
+        }
+        let mut changed_actions: Vec<(&String, &String)> = iter_nested(&self.action_settings)
+            .chain(iter_nested(&other.action_settings))
+            .filter(|(group_id, action_id, _)| {
+                self.action_settings(group_id, action_id)
+                    != other.action_settings(group_id, action_id)
+            })
+            .map(|(group_id, action_id, _)| (group_id, action_id))
+            .collect();
+        changed_actions.sort();
+        changed_actions.dedup();
+        settings.extend(changed_actions.into_iter().map(|(group_id, action_id)| {
+            SettingDiff::ActionSettings {
+                group_id: group_id.clone(),
+                action_id: action_id.clone(),
+            }
+        }));
+        ConfigDiff { actions, settings }
+    }
+
+    /// Returns the settings specific to the given action, if there are any.
+    #[must_use]
+    pub(crate) fn action_settings(
+        &self,
+        group_id: &str,
+        action_id: &str,
+    ) -> Option<&ActionSettings> {
+        self.action_settings
+            .get(group_id)
+            .and_then(|group| group.get(action_id))
+    }
+
+    /// Returns true iff chord blocking is enabled for the given action.
+    #[must_use]
+    pub(crate) fn chord_blocking(&self, group_id: &str, action_id: &str) -> bool {
+        self.action_settings(group_id, action_id)
+            .and_then(|settings| settings.chord_blocking)
+            .or(self.chord_blocking)
+            .unwrap_or(true)
+    }
+
+    /// Returns the factor by which the analog bindings of the given action are scaled.
+    #[must_use]
+    pub(crate) fn sensitivity(&self, group_id: &str, action_id: &str) -> f32 {
+        self.action_settings(group_id, action_id)
+            .and_then(|settings| settings.sensitivity)
+            .unwrap_or(1.)
+    }
+
+    /// Returns the minimum time between two pulses of the given action.
+    #[must_use]
+    pub(crate) fn cooldown(&self, group_id: &str, action_id: &str) -> Duration {
+        Duration::from_millis(
+            self.action_settings(group_id, action_id)
+                .and_then(|settings| settings.cooldown)
+                .unwrap_or_default(),
+        )
+    }
+
+    /// Returns true iff the post-acceptance delay should not apply to the given action.
+    #[must_use]
+    pub(crate) fn ignore_post_acceptance_delay(&self, group_id: &str, action_id: &str) -> bool {
+        self.action_settings(group_id, action_id)
+            .and_then(|settings| settings.ignore_post_acceptance_delay)
+            .unwrap_or(false)
+    }
+
+    /// Returns true iff the axis bindings of the given action should be inverted.
+    #[must_use]
+    pub(crate) fn invert(&self, group_id: &str, action_id: &str) -> bool {
+        self.action_settings(group_id, action_id)
+            .and_then(|settings| settings.invert)
+            .unwrap_or(false)
+    }
+}
+
+/// Iterates over a map that is keyed by group_id and then by action_id.
+fn iter_nested<T>(
+    map: &HashMap<String, HashMap<String, T>>,
+) -> impl Iterator<Item = (&String, &String, &T)> {
+    map.iter().flat_map(|(group_id, group)| {
+        group
+            .iter()
+            .map(move |(action_id, value)| (group_id, action_id, value))
+    })
 }
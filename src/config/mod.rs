@@ -1,7 +1,10 @@
+pub use action_settings::ActionSettings;
 pub use input_config::DurationInMillis;
 pub use input_config::InputConfig;
 
+mod action_settings;
 pub mod asset_loader_ron;
 pub mod builder;
+pub mod diff;
 mod input_config;
 pub mod simple_asset_loading;
@@ -18,6 +18,8 @@ pub struct CurrentlyLoading {
     /// Ordered collection of handles.
     /// The first handle is for the base config, others are to be merged into the base in order.
     pub handles: Vec<(MergeMode, Handle<InputConfig>)>,
+    /// Determines which settings win when the configs are merged.
+    pub settings_policy: SettingsMergePolicy,
 }
 
 /// This system runs every tick as long as the CurrentlyLoading resource exists.
@@ -43,18 +45,20 @@ pub(crate) fn manage_loading(
     // It also ensures that the assets are dropped from memory. (Unless the user keeps a strong handle for themself.)
     commands.remove_resource::<CurrentlyLoading>();
 
-    let merged_config = handles
+    let loaded = handles
         .handles
         .iter()
-        .filter_map(|(merge_mode, handle)| assets.get(handle).map(|asset| (merge_mode, asset)))
-        .fold(
-            InputConfig::default(),
-            |acc, (merge_mode, next)| match merge_mode {
-                MergeMode::Base => next.clone(),
-                MergeMode::Append => acc.merge_append(next),
-                MergeMode::Replace => acc.merge_replace(next),
-            },
-        );
+        .filter_map(|(merge_mode, handle)| assets.get(handle).map(|asset| (merge_mode, asset)));
+    // The base config doubles as the defaults that players can reset to.
+    if let Some((_, base)) = loaded
+        .clone()
+        .rfind(|(merge_mode, _)| **merge_mode == MergeMode::Base)
+    {
+        ineffable.set_default_config(base);
+    }
+    let merged_config = loaded.fold(InputConfig::default(), |acc, (merge_mode, next)| {
+        acc.merge_with(next, merge_mode, &handles.settings_policy)
+    });
     ineffable.set_config(&merged_config);
 }
 
@@ -74,3 +78,29 @@ pub enum MergeMode {
     #[default]
     Replace,
 }
+
+/// Determines how the settings of two `InputConfig`s are merged together, such as the double-click timing or the
+/// mouse sensitivity. This is separate from the `MergeMode`, which only applies to bindings.
+///
+/// Settings are always merged one by one: a setting that is only present in one of the configs ends up in the result.
+/// The policy only decides what happens when both configs define the same setting.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SettingsMergePolicy {
+    /// The settings in the base config win. Use this for configs that should only override bindings, such as a player
+    /// profile that must not clobber accessibility settings defined in the base config.
+    KeepBase,
+    /// The settings in the other config win. This is the behaviour of `InputConfig::merge_replace()` and
+    /// `InputConfig::merge_append()`.
+    #[default]
+    PreferOther,
+}
+
+impl SettingsMergePolicy {
+    /// Picks the winning value for a single setting.
+    pub(crate) fn pick<T>(&self, base: Option<T>, other: Option<T>) -> Option<T> {
+        match self {
+            SettingsMergePolicy::KeepBase => base.or(other),
+            SettingsMergePolicy::PreferOther => other.or(base),
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Contains an optional plugin that draws an on-screen panel listing every registered `InputAction` and its live
+//! value. Useful when a binding "doesn't work" and you're not sure whether the problem is in the config or the code.
+//!
+//! Only available with the `debug` feature enabled, so that release builds don't pull in bevy's UI and text rendering.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy::utils::HashMap;
+
+use crate::processed::bound_action::BoundAction;
+use crate::resources::meta_data::IneffableMetaData;
+use crate::resources::Ineffable;
+
+/// After an action pulses, it stays highlighted in the panel for this long. A single tick is too short to notice.
+const PULSE_FLASH_DURATION: Duration = Duration::from_millis(250);
+
+/// Draws a panel in the top left corner of the screen, listing every registered `InputAction` and its live value.
+///
+/// Requires the `IneffablePlugin`, and a camera to render the UI.
+///
+/// # Examples
+///
+/// ```no_run
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::debug::IneffableDebugPlugin;
+/// # use bevy_ineffable::prelude::*;
+/// App::new()
+///     .add_plugins((DefaultPlugins, IneffablePlugin, IneffableDebugPlugin))
+///     .run();
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IneffableDebugPlugin;
+
+impl Plugin for IneffableDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PulseFlashes>()
+            .add_systems(Startup, spawn_panel)
+            .add_systems(Update, update_panel);
+    }
+}
+
+/// Marks the text entity that the panel is drawn in.
+#[derive(Debug, Component)]
+struct DebugPanel;
+
+/// Keeps track of how long ago each pulse action last pulsed. Keyed by group_id, then by action_id.
+#[derive(Debug, Default, Resource)]
+struct PulseFlashes(HashMap<String, HashMap<String, Timer>>);
+
+fn spawn_panel(mut commands: Commands<'_, '_>) {
+    commands.spawn((
+        TextBundle::from_section(
+            "",
+            TextStyle {
+                font_size: 16.,
+                ..default()
+            },
+        )
+        .with_style(Style {
+            position_type: PositionType::Absolute,
+            top: Val::Px(8.),
+            left: Val::Px(8.),
+            ..default()
+        })
+        .with_background_color(Color::srgba(0., 0., 0., 0.75)),
+        DebugPanel,
+    ));
+}
+
+#[allow(clippy::needless_pass_by_value)] // That's just a bevy thing.
+fn update_panel(
+    ineffable: Res<'_, Ineffable>,
+    meta_data: Res<'_, IneffableMetaData>,
+    time: Res<'_, Time>,
+    mut flashes: ResMut<'_, PulseFlashes>,
+    mut panels: Query<'_, '_, &mut Text, With<DebugPanel>>,
+) {
+    let mut group_ids: Vec<&str> = meta_data.map.keys().map(String::as_str).collect();
+    group_ids.sort_unstable();
+    let mut out = String::new();
+    for group_id in group_ids {
+        let _ = writeln!(out, "{group_id}");
+        let bound_actions = ineffable.groups.get(group_id);
+        for meta in meta_data.actions(group_id) {
+            let value = match bound_actions.and_then(|group| group.get(meta.index)) {
+                None => "(unbound)".to_string(),
+                Some(BoundAction::DualAxis(binding)) => {
+                    format!("({:+.2}, {:+.2})", binding.value.x, binding.value.y)
+                }
+                Some(BoundAction::SingleAxis(binding)) => format!("{:+.2}", binding.value),
+                Some(BoundAction::Continuous(binding)) => {
+                    if binding.active { "ON" } else { "off" }.to_string()
+                }
+                Some(BoundAction::Pulse(binding)) => {
+                    let group = flashes.0.entry(group_id.to_string()).or_default();
+                    let timer = group.entry(meta.action_id.clone()).or_insert_with(|| {
+                        let mut timer = Timer::new(PULSE_FLASH_DURATION, TimerMode::Once);
+                        timer.tick(PULSE_FLASH_DURATION);
+                        timer
+                    });
+                    if binding.just_pulsed {
+                        timer.reset();
+                    } else {
+                        timer.tick(time.delta());
+                    }
+                    if timer.finished() { "-" } else { "PULSE" }.to_string()
+                }
+            };
+            let _ = writeln!(out, "  {}: {value}", meta.action_id);
+        }
+    }
+    for mut text in &mut panels {
+        if let Some(section) = text.sections.first_mut() {
+            section.value.clone_from(&out);
+        }
+    }
+}
@@ -0,0 +1,13 @@
+//! Contains the events that Ineffable sends.
+
+use bevy::prelude::{Event, Gamepad};
+
+/// Sent when a gamepad is connected or disconnected.
+///
+/// Listen to this event to pause the game when a controller is unplugged, or to switch between keyboard and gamepad
+/// button prompts. The gamepads that are currently connected can be found through `Ineffable::connected_gamepads()`.
+#[derive(Debug, Event, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GamepadConnectionChanged {
+    Connected(Gamepad),
+    Disconnected(Gamepad),
+}
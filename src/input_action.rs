@@ -8,6 +8,7 @@ use serde::{Deserialize, Serialize};
 use crate::bindings::{
     ContinuousBinding, DualAxisBinding, InputBinding, PulseBinding, SingleAxisBinding,
 };
+use crate::phantom::IAWrp;
 
 /// This trait represents an input-agnostic, abstract action that a player take.
 /// For example, `Jump`, `Move`, or `Sprint`.
@@ -34,6 +35,9 @@ use crate::bindings::{
 /// # }
 /// ```
 ///
+/// Optionally, the attribute can also give the variant a human-friendly `label` and a `category`, for use in a
+/// controls menu: `#[ineffable(pulse, label = "Fire Weapon", category = "Combat")]`.
+///
 /// # Examples
 /// ```
 /// # use bevy_ineffable_macros::InputAction;
@@ -72,6 +76,19 @@ pub trait InputAction {
     fn index(&self) -> usize;
     /// What `InputKind` is this action?
     fn kind(&self) -> InputKind;
+    /// A human-friendly name for this action, for example to show in a controls menu.
+    /// Set it with `#[ineffable(pulse, label = "Fire Weapon")]`. Defaults to the enum variant name.
+    fn label(&self) -> &'static str {
+        self.action_id()
+    }
+    /// The section of a controls menu that this action belongs in.
+    /// Set it with `#[ineffable(pulse, category = "Combat")]`. Defaults to the name of the enum.
+    fn category(&self) -> &'static str
+    where
+        Self: Sized,
+    {
+        Self::group_id()
+    }
     /// An iterator over all the enum variants.
     ///
     /// Used internally when processing `InputConfig`s.
@@ -80,6 +97,70 @@ pub trait InputAction {
         Self: Sized;
 }
 
+/// A plain identifier for an `InputAction`, consisting of its group_id and action_id. Unlike `IAWrp`, it does not
+/// carry the `InputKind` or the type of the enum, which makes it easy to store in save files or send over a network.
+///
+/// Obtain one from the `ineff!()` macro, and turn it back into an enum variant with `resolve()`.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy_ineffable::input_action::ActionId;
+/// # use bevy_ineffable::prelude::*;
+/// #[derive(InputAction, Debug, PartialEq)]
+/// pub enum PlayerInput {
+///     #[ineffable(pulse)]
+///     Fireball,
+/// }
+/// let id = ActionId::from(ineff!(PlayerInput::Fireball));
+/// let saved = ron::to_string(&id).unwrap();
+/// assert_eq!(saved, r#"(group:"PlayerInput",action:"Fireball")"#);
+///
+/// let loaded: ActionId = ron::from_str(&saved).unwrap();
+/// assert_eq!(loaded, id);
+/// assert_eq!(loaded.resolve::<PlayerInput>(), Some(PlayerInput::Fireball));
+/// assert_eq!(ActionId::new("PlayerInput", "Frostbolt").resolve::<PlayerInput>(), None);
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+pub struct ActionId {
+    /// The group_id of the action. This is the name of the enum.
+    pub group: String,
+    /// The action_id of the action. This is the name of the enum variant.
+    pub action: String,
+}
+
+impl ActionId {
+    #[must_use]
+    pub fn new(group: impl Into<String>, action: impl Into<String>) -> Self {
+        Self {
+            group: group.into(),
+            action: action.into(),
+        }
+    }
+
+    /// Finds the variant of the given enum that this identifier refers to.
+    /// Returns None if the identifier belongs to another group, or if the enum has no such variant.
+    #[must_use]
+    pub fn resolve<I: InputAction>(&self) -> Option<I> {
+        if self.group != I::group_id() {
+            return None;
+        }
+        I::iter().find(|action| action.action_id() == self.action)
+    }
+}
+
+impl<I: InputAction, Kind> From<IAWrp<I, Kind>> for ActionId {
+    fn from(action: IAWrp<I, Kind>) -> Self {
+        Self::from(&action)
+    }
+}
+
+impl<I: InputAction, Kind> From<&IAWrp<I, Kind>> for ActionId {
+    fn from(action: &IAWrp<I, Kind>) -> Self {
+        Self::new(I::group_id(), action.0.action_id())
+    }
+}
+
 /// All `InputAction`s are separated into four different `InputKind`s. Each represents a slightly different way for
 /// the player to interact with the game.
 #[derive(Debug, Serialize, Deserialize, Copy, Clone, PartialEq, Eq, Hash)]
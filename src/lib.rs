@@ -15,7 +15,11 @@ missing_debug_implementations,
 
 pub mod bindings;
 pub mod commands;
+pub mod components;
 pub mod config;
+#[cfg(feature = "debug")]
+pub mod debug;
+pub mod events;
 pub mod input_action;
 pub mod phantom;
 pub mod plugin;
@@ -36,9 +40,11 @@ pub mod prelude {
     pub use crate::bindings::PulseBinding;
     pub use crate::bindings::SingleAxisBinding;
     pub use crate::commands::IneffableCommands;
+    pub use crate::components::IneffableInput;
     pub use crate::config::InputConfig;
     pub use crate::input_action::InputAction;
     pub use crate::input_action::InputKind;
+    pub use crate::plugin::IneffableMinimalPlugin;
     pub use crate::plugin::IneffablePlugin;
     pub use crate::register::InputActionRegistrar;
     pub use crate::resources::Ineffable;
@@ -85,3 +85,9 @@ pub struct IAWrp<I: InputAction, Kind>(pub I, pub PhantomData<Kind>);
 /// ```
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct IBWrp<Kind>(pub InputBinding, pub PhantomData<Kind>);
+
+impl<Kind> From<IBWrp<Kind>> for InputBinding {
+    fn from(binding: IBWrp<Kind>) -> Self {
+        binding.0
+    }
+}
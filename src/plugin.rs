@@ -1,12 +1,15 @@
 //! Contains a bevy plugin to help set up all the resources etc. needed by Ineffable.
 
 use bevy::input::mouse::{MouseMotion, MouseWheel};
+use bevy::input::InputSystem;
 use bevy::prelude::*;
 
 use crate::bindings::*;
+use crate::components::IneffableInput;
 use crate::config::asset_loader_ron::InputConfigRonLoader;
 use crate::config::simple_asset_loading::{manage_loading, CurrentlyLoading};
 use crate::config::InputConfig;
+use crate::events::GamepadConnectionChanged;
 use crate::processed::stateful::axis_dual::StatefulDualAxisBinding;
 use crate::processed::stateful::axis_single::{
     StatefulSingleAxisBinding, StatefulSingleAxisBindingVariant,
@@ -18,6 +21,7 @@ use crate::processed::stateful::input_binary::StatefulBinaryInput;
 use crate::processed::stateful::pulse::{StatefulPulseBinding, StatefulPulseBindingVariant};
 use crate::processed::updating::update_input;
 use crate::resources::ineffable_settings::IneffableSettings;
+use crate::resources::key_labels::{learn_key_labels, KeyLabels};
 use crate::resources::meta_data::IneffableMetaData;
 use crate::resources::sources::IneffableEventSources;
 use crate::resources::Ineffable;
@@ -27,17 +31,79 @@ pub struct IneffablePlugin;
 
 impl Plugin for IneffablePlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Ineffable::default())
+        app.add_plugins(IneffableMinimalPlugin)
+            .init_asset::<InputConfig>()
+            .init_asset_loader::<InputConfigRonLoader>()
+            .add_systems(
+                PreUpdate,
+                manage_loading
+                    .run_if(resource_exists::<CurrentlyLoading>)
+                    .before(read_gamepad_events),
+            );
+    }
+}
+
+/// A lighter version of the `IneffablePlugin`, for headless apps such as integration tests and dedicated servers.
+/// It sets up everything that is needed to process input, but does not register the `InputConfig` asset or its
+/// loader. This means that configs can't be loaded with `IneffableCommands::load_configs()`; use
+/// `IneffableCommands::set_config()` instead.
+///
+/// Works with just the `MinimalPlugins`: any input resources that are missing are initialised, so that input can be
+/// simulated by writing to them directly.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::ecs::system::RunSystemOnce;
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::prelude::*;
+/// #[derive(InputAction)]
+/// pub enum PlayerInput {
+///     #[ineffable(pulse)]
+///     Jump,
+/// }
+/// let mut app = App::new();
+/// app.add_plugins((MinimalPlugins, IneffableMinimalPlugin))
+///     .register_input_action::<PlayerInput>();
+/// let config = InputConfig::builder()
+///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::Space))
+///     .build();
+/// app.world_mut().run_system_once(move |mut ineffable: IneffableCommands| {
+///     ineffable.set_config(&config);
+/// });
+/// app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Space);
+/// app.update();
+/// assert!(app.world().resource::<Ineffable>().just_pulsed(ineff!(PlayerInput::Jump)));
+/// ```
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IneffableMinimalPlugin;
+
+impl Plugin for IneffableMinimalPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<IneffableInput>()
+            .insert_resource(Ineffable::default())
             .insert_resource(IneffableSettings::default())
             .insert_resource(IneffableMetaData::default())
             .insert_resource(IneffableEventSources::default())
-            .init_asset::<InputConfig>()
-            .init_asset_loader::<InputConfigRonLoader>()
+            .add_event::<GamepadConnectionChanged>()
+            // Normally added by bevy's InputPlugin. These don't replace anything that already exists.
+            .init_resource::<Gamepads>()
+            .init_resource::<ButtonInput<KeyCode>>()
+            .init_resource::<ButtonInput<MouseButton>>()
+            .init_resource::<ButtonInput<GamepadButton>>()
+            .init_resource::<Axis<GamepadButton>>()
+            .init_resource::<Axis<GamepadAxis>>()
+            .add_event::<MouseMotion>()
+            .add_event::<MouseWheel>()
             .add_systems(
                 PreUpdate,
                 (
-                    manage_loading.run_if(resource_exists::<CurrentlyLoading>),
-                    (read_gamepad_events, read_mouse_events),
+                    (
+                        read_gamepad_events,
+                        read_mouse_events,
+                        read_touches.after(InputSystem),
+                        learn_key_labels.run_if(resource_exists::<KeyLabels>),
+                    ),
                     update_input,
                 )
                     .chain(),
@@ -61,14 +127,51 @@ impl Plugin for IneffablePlugin {
     }
 }
 
-pub(crate) fn read_gamepad_events() {
-    //todo
+pub(crate) fn read_gamepad_events(
+    gamepads: Res<'_, Gamepads>,
+    mut ineffable: ResMut<'_, Ineffable>,
+    mut players: Query<'_, '_, &mut IneffableInput>,
+    mut connection_events: EventWriter<'_, GamepadConnectionChanged>,
+) {
+    let known = &ineffable.connected_gamepads;
+    let disconnected: Vec<Gamepad> = known
+        .iter()
+        .filter(|gamepad| !gamepads.contains(**gamepad))
+        .copied()
+        .collect();
+    let connected: Vec<Gamepad> = gamepads
+        .iter()
+        .filter(|gamepad| !known.contains(gamepad))
+        .collect();
+    ineffable
+        .connected_gamepads
+        .retain(|gamepad| !disconnected.contains(gamepad));
+    ineffable.connected_gamepads.extend(&connected);
+    for mut player in &mut players {
+        // Also covers players that were spawned, or whose devices were reassigned, since the last change.
+        let assigned = ineffable
+            .connected_gamepads
+            .iter()
+            .filter(|gamepad| player.devices.uses_gamepad(**gamepad));
+        if !assigned.clone().eq(player.state.connected_gamepads.iter()) {
+            player.state.connected_gamepads = assigned.copied().collect();
+        }
+    }
+    connection_events.send_batch(
+        disconnected
+            .into_iter()
+            .map(GamepadConnectionChanged::Disconnected)
+            .chain(
+                connected
+                    .into_iter()
+                    .map(GamepadConnectionChanged::Connected),
+            ),
+    );
 }
 
 pub(crate) fn read_mouse_events(
     mut sources: ResMut<'_, IneffableEventSources>,
     mut mouse_motion_events: EventReader<'_, '_, MouseMotion>,
-    mut cursor_moved_events: EventReader<'_, '_, CursorMoved>,
     mut mouse_wheel_events: EventReader<'_, '_, MouseWheel>,
 ) {
     sources.clear();
@@ -80,5 +183,39 @@ pub(crate) fn read_mouse_events(
         sources.mouse_scroll.x += event.x;
         sources.mouse_scroll.y += event.y;
     }
-    for _event in cursor_moved_events.read() {}
+}
+
+/// Tracks the fingers on the touch screen. Bevy's `Touch::delta()` is only updated on ticks that receive touch
+/// events, so the positions from last tick are remembered here instead.
+pub(crate) fn read_touches(
+    mut sources: ResMut<'_, IneffableEventSources>,
+    touches: Option<Res<'_, Touches>>,
+    mut previous: Local<'_, Vec<(u64, Vec2)>>,
+) {
+    let mut current: Vec<(u64, Vec2)> = touches
+        .iter()
+        .flat_map(|touches| touches.iter())
+        .map(|touch| (touch.id(), touch.position()))
+        .collect();
+    current.sort_unstable_by_key(|(id, _)| *id);
+    let moved = |(id, position): &(u64, Vec2)| {
+        previous
+            .iter()
+            .find(|(previous_id, _)| previous_id == id)
+            .map(|(_, previous_position)| (*position, *previous_position))
+    };
+    // A finger that just landed has no previous position, so it counts as not having moved.
+    sources.touch_motion = current
+        .first()
+        .map(|first| moved(first).map_or(Vec2::ZERO, |(now, before)| now - before));
+    sources.touch_pinch = match (
+        current.first().and_then(moved),
+        current.get(1).and_then(moved),
+    ) {
+        (Some((first_now, first_before)), Some((second_now, second_before))) => {
+            first_now.distance(second_now) - first_before.distance(second_before)
+        }
+        _ => 0.,
+    };
+    *previous = current;
 }
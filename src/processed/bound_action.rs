@@ -1,16 +1,18 @@
 use bevy::prelude::Reflect;
+use serde::{Deserialize, Serialize};
 
 use crate::bindings::InputBinding;
 use crate::input_action::InputKind;
 use crate::processed::processor::Helper;
-use crate::processed::stateful::axis_dual::StatefulDualAxisBinding;
-use crate::processed::stateful::axis_single::StatefulSingleAxisBinding;
-use crate::processed::stateful::continuous::StatefulContinuousBinding;
-use crate::processed::stateful::pulse::StatefulPulseBinding;
-use crate::processed::updating::InputSources;
+use crate::processed::stateful::axis_dual::{DualAxisState, StatefulDualAxisBinding};
+use crate::processed::stateful::axis_single::{SingleAxisState, StatefulSingleAxisBinding};
+use crate::processed::stateful::continuous::{ContinuousState, StatefulContinuousBinding};
+use crate::processed::stateful::pulse::{PulseState, StatefulPulseBinding};
+use crate::processed::updating::{ConsumedInputs, InputSources};
 use crate::resources::meta_data::IneffableMetaItem;
+use crate::resources::{ActionDelta, ContinuousDelta};
 
-#[derive(Debug, Reflect, Clone)]
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) enum BoundAction {
     SingleAxis(StatefulSingleAxisBinding),
     DualAxis(StatefulDualAxisBinding),
@@ -18,6 +20,15 @@ pub(crate) enum BoundAction {
     Pulse(StatefulPulseBinding),
 }
 
+/// The transient state of a `BoundAction`, without any of its bindings.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
+pub(crate) enum ActionState {
+    SingleAxis(SingleAxisState),
+    DualAxis(DualAxisState),
+    Continuous(ContinuousState),
+    Pulse(PulseState),
+}
+
 impl BoundAction {
     pub(crate) fn new(
         meta: &IneffableMetaItem,
@@ -40,7 +51,73 @@ impl BoundAction {
         }
     }
 
-    pub(crate) fn update(&mut self, sources: &mut InputSources<'_>) {
+    /// Adds the inputs that this action is currently using to the consumed inputs.
+    pub(crate) fn consume(&self, sources: &InputSources<'_, '_>, consumed: &mut ConsumedInputs) {
+        match self {
+            BoundAction::SingleAxis(binding) => binding.consume(sources, consumed),
+            BoundAction::DualAxis(binding) => binding.consume(sources, consumed),
+            BoundAction::Continuous(binding) => binding.consume(sources, consumed),
+            BoundAction::Pulse(binding) => binding.consume(sources, consumed),
+        };
+    }
+
+    /// Clears all transient state, such as toggles, sequence progress and timers. The bindings stay the same.
+    pub(crate) fn reset(&mut self) {
+        match self {
+            BoundAction::SingleAxis(binding) => binding.reset(),
+            BoundAction::DualAxis(binding) => binding.reset(),
+            BoundAction::Continuous(binding) => binding.reset(),
+            BoundAction::Pulse(binding) => binding.reset(),
+        };
+    }
+
+    pub(crate) fn state(&self) -> ActionState {
+        match self {
+            BoundAction::SingleAxis(binding) => ActionState::SingleAxis(binding.state()),
+            BoundAction::DualAxis(binding) => ActionState::DualAxis(binding.state()),
+            BoundAction::Continuous(binding) => ActionState::Continuous(binding.state()),
+            BoundAction::Pulse(binding) => ActionState::Pulse(binding.state()),
+        }
+    }
+
+    /// Overwrites the transient state of this action, keeping its bindings.
+    /// Does nothing if the state belongs to a different `InputKind`.
+    pub(crate) fn restore(&mut self, state: &ActionState) {
+        match (self, state) {
+            (BoundAction::SingleAxis(binding), ActionState::SingleAxis(state)) => {
+                binding.restore(state);
+            }
+            (BoundAction::DualAxis(binding), ActionState::DualAxis(state)) => {
+                binding.restore(state);
+            }
+            (BoundAction::Continuous(binding), ActionState::Continuous(state)) => {
+                binding.restore(state);
+            }
+            (BoundAction::Pulse(binding), ActionState::Pulse(state)) => binding.restore(state),
+            _ => {}
+        }
+    }
+
+    /// Overrides the output state of this action for the current tick.
+    /// Returns false if the delta does not match the `InputKind` of this action.
+    pub(crate) fn apply_delta(&mut self, delta: ActionDelta) -> bool {
+        match (self, delta) {
+            (BoundAction::SingleAxis(binding), ActionDelta::SingleAxis(value)) => {
+                binding.value = value;
+            }
+            (BoundAction::DualAxis(binding), ActionDelta::DualAxis(value)) => {
+                binding.set_value(value);
+            }
+            (BoundAction::Continuous(binding), ActionDelta::Continuous(delta)) => {
+                binding.active = delta == ContinuousDelta::Start;
+            }
+            (BoundAction::Pulse(binding), ActionDelta::Pulse) => binding.pulse(),
+            _ => return false,
+        }
+        true
+    }
+
+    pub(crate) fn update(&mut self, sources: &InputSources<'_, '_>) {
         match self {
             BoundAction::SingleAxis(binding) => binding.update(sources),
             BoundAction::DualAxis(binding) => binding.update(sources),
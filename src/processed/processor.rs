@@ -1,13 +1,45 @@
-use crate::bindings::{Chord, ContinuousBinding, InputBinding, PulseBinding, SingleAxisBinding};
+use std::time::Duration;
+
+use bevy::utils::HashMap;
+
+use crate::bindings::{
+    Chord, ContinuousBinding, InputBinding, InputDeviceCategory, PulseBinding, SingleAxisBinding,
+};
 use crate::config::InputConfig;
 use crate::input_action::InputKind;
+use crate::processed::bound_action::BoundAction;
+use crate::processed::stateful::input_binary::is_blocked_by;
 use crate::processed::stateful::{axis_single, continuous, pulse};
 use crate::reporting::{ActionLocation, InputConfigProblem, InputConfigReport};
 use crate::resources::meta_data::{IneffableMetaData, IneffableMetaItem};
 
-#[derive(Debug, Default)]
+#[derive(Debug)]
 pub(crate) struct Helper<'a> {
     pub(crate) inputs: Vec<(&'a IneffableMetaItem, Chord)>,
+    /// Whether the bindings that are currently being processed can be blocked by more specific chords.
+    /// This differs per action, so it must be set before processing each action.
+    pub(crate) chord_blocking: bool,
+    /// Scales the analog bindings of the action that is currently being processed.
+    pub(crate) sensitivity: f32,
+    /// Whether to invert the axis bindings of the action that is currently being processed.
+    pub(crate) invert: bool,
+    /// The minimum time between two pulses of the action that is currently being processed.
+    pub(crate) cooldown: Duration,
+    /// Whether the action that is currently being processed ignores the post-acceptance delay.
+    pub(crate) ignore_pad: bool,
+}
+
+impl Default for Helper<'_> {
+    fn default() -> Self {
+        Self {
+            inputs: Vec::new(),
+            chord_blocking: true,
+            sensitivity: 1.,
+            invert: false,
+            cooldown: Duration::ZERO,
+            ignore_pad: false,
+        }
+    }
 }
 
 impl<'a> Helper<'a> {
@@ -37,34 +69,87 @@ pub(crate) fn collect_inputs<'a>(
                 .iter()
                 .map(move |input_binding| (meta, input_binding))
         })
-        .for_each(|(meta, binding)| match meta.kind {
-            InputKind::SingleAxis => {
-                if let InputBinding::SingleAxis(axis) = binding {
-                    axis_single::collect(&mut out, meta, axis);
-                };
-            }
-            InputKind::DualAxis => {
-                if let InputBinding::DualAxis { x, y } = binding {
-                    axis_single::collect(&mut out, meta, x);
-                    axis_single::collect(&mut out, meta, y);
-                };
-            }
-            InputKind::Continuous => {
-                if let InputBinding::Continuous(continuous) = binding {
-                    continuous::collect(&mut out, meta, continuous);
-                };
-            }
-            InputKind::Pulse => {
-                if let InputBinding::Pulse(pulse) = binding {
-                    pulse::collect(&mut out, meta, pulse);
-                };
-            }
-        });
+        .for_each(|(meta, binding)| collect_binding(&mut out, meta, binding));
     out
 }
 
+/// Collects all chords in the given binding, if it is of the same kind as the action.
+fn collect_binding<'a>(out: &mut Helper<'a>, meta: &'a IneffableMetaItem, binding: &InputBinding) {
+    match meta.kind {
+        InputKind::SingleAxis => {
+            if let InputBinding::SingleAxis(axis) = binding {
+                axis_single::collect(out, meta, axis);
+            };
+        }
+        InputKind::DualAxis => {
+            if let InputBinding::DualAxis { x, y } = binding {
+                axis_single::collect(out, meta, x);
+                axis_single::collect(out, meta, y);
+            };
+        }
+        InputKind::Continuous => {
+            if let InputBinding::Continuous(continuous) = binding {
+                continuous::collect(out, meta, continuous);
+            };
+        }
+        InputKind::Pulse => {
+            if let InputBinding::Pulse(pulse) = binding {
+                pulse::collect(out, meta, pulse);
+            };
+        }
+    }
+}
+
+/// Turns the bindings in the config into stateful bound actions that can be updated every tick.
+#[must_use]
+pub(crate) fn process_config(
+    meta_data: &IneffableMetaData,
+    config: &InputConfig,
+) -> HashMap<String, Vec<BoundAction>> {
+    let mut helper = collect_inputs(meta_data, config);
+    config
+        .bindings
+        .iter()
+        .filter(|(group_id, _)| meta_data.group_exists(group_id))
+        .map(|(group_id, group_data)| {
+            let mut bound_actions = Vec::new();
+            for meta in meta_data.actions(group_id) {
+                helper.chord_blocking = config.chord_blocking(group_id, &meta.action_id);
+                helper.sensitivity = config.sensitivity(group_id, &meta.action_id);
+                helper.invert = config.invert(group_id, &meta.action_id);
+                helper.cooldown = config.cooldown(group_id, &meta.action_id);
+                helper.ignore_pad = config.ignore_post_acceptance_delay(group_id, &meta.action_id);
+                if let Some(action) = group_data.get(&meta.action_id) {
+                    bound_actions.push(BoundAction::new(meta, action, &helper));
+                } else {
+                    bound_actions.push(BoundAction::new(meta, &[], &helper));
+                }
+            }
+            (group_id.clone(), bound_actions)
+        })
+        .collect()
+}
+
 #[must_use]
 pub(crate) fn validate(meta_data: &IneffableMetaData, config: &InputConfig) -> InputConfigReport {
+    let mut report = validate_ignoring_profiles(meta_data, config);
+    // Each profile is validated the way it is used: merged on top of the config. Problems that the config already has
+    // by itself are reported only once.
+    let mut names: Vec<&String> = config.profiles.keys().collect();
+    names.sort_unstable();
+    for name in names {
+        if let Some(merged) = config.with_profile(name) {
+            report.append_new(validate_ignoring_profiles(meta_data, &merged));
+        }
+    }
+    report
+}
+
+/// Validates the config itself, without looking at any of its profiles.
+pub(crate) fn validate_ignoring_profiles(
+    meta_data: &IneffableMetaData,
+    config: &InputConfig,
+) -> InputConfigReport {
     let mut report = InputConfigReport::default();
     for (group_id, groups) in &config.bindings {
         if !meta_data.group_exists(group_id) {
@@ -83,6 +168,7 @@ pub(crate) fn validate(meta_data: &IneffableMetaData, config: &InputConfig) -> I
                 });
                 continue;
             };
+            let mut has_wrong_kind = false;
             for (index, binding) in bindings.iter().enumerate() {
                 let loc = ActionLocation {
                     group_id: group_id.to_string(),
@@ -91,6 +177,7 @@ pub(crate) fn validate(meta_data: &IneffableMetaData, config: &InputConfig) -> I
                 };
                 let kind_from_config = binding.kind();
                 if kind_from_config != registered_item.kind {
+                    has_wrong_kind = true;
                     report.error(InputConfigProblem::ActionWrongKind {
                         loc: ActionLocation {
                             group_id: group_id.to_string(),
@@ -102,46 +189,328 @@ pub(crate) fn validate(meta_data: &IneffableMetaData, config: &InputConfig) -> I
                     });
                 }
 
-                match binding {
-                    InputBinding::SingleAxis(axis) => {
-                        if matches!(axis, SingleAxisBinding::Dummy) {
-                            report.warning(InputConfigProblem::RootBindingIsDummy {
-                                loc: loc.clone(),
-                            });
-                        }
-                        axis_single::check_for_problems(axis, &mut report, &loc);
-                    }
-                    InputBinding::DualAxis { x, y } => {
-                        if matches!(x, SingleAxisBinding::Dummy)
-                            && matches!(y, SingleAxisBinding::Dummy)
-                        {
-                            report.warning(InputConfigProblem::RootBindingIsDummy {
-                                loc: loc.clone(),
-                            });
-                        }
-                        axis_single::check_for_problems(x, &mut report, &loc);
-                        axis_single::check_for_problems(y, &mut report, &loc);
-                    }
-                    InputBinding::Continuous(continuous) => {
-                        if matches!(continuous, ContinuousBinding::Dummy) {
-                            report.warning(InputConfigProblem::RootBindingIsDummy {
-                                loc: loc.clone(),
-                            });
-                        }
-                        continuous::check_for_problems(continuous, &mut report, &loc);
-                    }
-                    InputBinding::Pulse(pulse) => {
-                        if matches!(pulse, PulseBinding::Dummy) {
-                            report.warning(InputConfigProblem::RootBindingIsDummy {
-                                loc: loc.clone(),
-                            });
-                        }
-                        pulse::check_for_problems(pulse, &mut report, &loc);
+                check_binding_for_problems(binding, &mut report, &loc);
+            }
+            // Bindings of the wrong kind were already reported one by one, which says more than mixed kinds would.
+            if !has_wrong_kind {
+                check_for_mixed_kinds(group_id, action_id, bindings, &mut report);
+            }
+        }
+    }
+    check_for_pulses_overlapping_sustained(meta_data, config, &mut report);
+    check_for_shadowed_bindings(meta_data, config, &mut report);
+    check_for_single_device_actions(config, &mut report);
+    // TODO: Warn conflicts.
+    report
+}
+
+/// Warns when one binding of an action uses a chord that is a strict subset of a chord in another binding of the same
+/// action. Chord blocking means the narrower binding can't activate while the broader one is held down.
+fn check_for_shadowed_bindings(
+    meta_data: &IneffableMetaData,
+    config: &InputConfig,
+    report: &mut InputConfigReport,
+) {
+    for (group_id, groups) in &config.bindings {
+        for (action_id, bindings) in groups {
+            let Some(meta) = meta_data.action(group_id, action_id) else {
+                continue;
+            };
+            if !config.chord_blocking(group_id, action_id) {
+                continue;
+            }
+            let chords: Vec<(usize, Chord)> = bindings
+                .iter()
+                .enumerate()
+                .flat_map(|(index, binding)| {
+                    let mut out = Helper::default();
+                    collect_binding(&mut out, meta, binding);
+                    out.inputs.into_iter().map(move |(_, chord)| (index, chord))
+                })
+                .collect();
+            for (narrower_index, narrower) in &chords {
+                for (broader_index, broader) in &chords {
+                    if narrower_index != broader_index && is_blocked_by(narrower, broader) {
+                        let loc = |index: usize| ActionLocation {
+                            group_id: group_id.to_string(),
+                            action_id: action_id.to_string(),
+                            index,
+                        };
+                        report.warning(InputConfigProblem::ChordShadowedInSameAction {
+                            narrower: loc(*narrower_index),
+                            broader: loc(*broader_index),
+                            narrower_chord: format!("{narrower:?}"),
+                            broader_chord: format!("{broader:?}"),
+                        });
                     }
                 }
             }
         }
     }
-    // TODO: Warn conflicts.
+}
+
+/// Warns when the same chord is bound to both a pulse action and a continuous or axis action. Pressing the chord
+/// fires the pulse while the other action stays active, which may or may not be intended.
+///
+/// Only actions in the same group are compared: different groups usually end up in different contexts, where the
+/// higher context consumes the chord. Toggles are left out, because they don't stay active while the chord is held.
+fn check_for_pulses_overlapping_sustained(
+    meta_data: &IneffableMetaData,
+    config: &InputConfig,
+    report: &mut InputConfigReport,
+) {
+    for (group_id, groups) in &config.bindings {
+        let mut pulses: Vec<(ActionLocation, Chord)> = Vec::new();
+        let mut sustained: Vec<(ActionLocation, Chord)> = Vec::new();
+        for (action_id, bindings) in groups {
+            let Some(meta) = meta_data.action(group_id, action_id) else {
+                continue;
+            };
+            for (index, binding) in bindings.iter().enumerate() {
+                if meta.kind != InputKind::Pulse && is_toggle(binding) {
+                    continue;
+                }
+                let mut out = Helper::default();
+                collect_binding(&mut out, meta, binding);
+                let loc = ActionLocation {
+                    group_id: group_id.to_string(),
+                    action_id: action_id.to_string(),
+                    index,
+                };
+                let target = if meta.kind == InputKind::Pulse {
+                    &mut pulses
+                } else {
+                    &mut sustained
+                };
+                target.extend(
+                    out.inputs
+                        .into_iter()
+                        .map(|(_, chord)| (loc.clone(), chord)),
+                );
+            }
+        }
+        for (pulse_loc, pulse_chord) in &pulses {
+            for (sustained_loc, sustained_chord) in &sustained {
+                let same_chord = !pulse_chord.is_empty()
+                    && pulse_chord.len() == sustained_chord.len()
+                    && pulse_chord
+                        .iter()
+                        .all(|input| sustained_chord.contains(input));
+                if same_chord {
+                    report.warning(InputConfigProblem::PulseOverlapsSustained {
+                        pulse: pulse_loc.clone(),
+                        sustained: sustained_loc.clone(),
+                        chord: format!("{pulse_chord:?}"),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Returns true if the binding flips its action on and off, instead of keeping it active while an input is held.
+fn is_toggle(binding: &InputBinding) -> bool {
+    let axis_is_toggle = |axis: &SingleAxisBinding| matches!(axis, SingleAxisBinding::Toggle(..));
+    match binding {
+        InputBinding::SingleAxis(axis) => axis_is_toggle(axis),
+        InputBinding::DualAxis { x, y } => axis_is_toggle(x) && axis_is_toggle(y),
+        InputBinding::Continuous(continuous) => matches!(continuous, ContinuousBinding::Toggle(_)),
+        InputBinding::Pulse(_) => false,
+    }
+}
+
+/// Only performs the checks that don't require knowing which `InputAction`s are registered. Unknown groups, unknown
+/// actions and bindings of the wrong kind are not reported.
+#[must_use]
+pub(crate) fn validate_bindings(config: &InputConfig) -> InputConfigReport {
+    let mut report = InputConfigReport::default();
+    for (group_id, groups) in &config.bindings {
+        for (action_id, bindings) in groups {
+            check_for_mixed_kinds(group_id, action_id, bindings, &mut report);
+            for (index, binding) in bindings.iter().enumerate() {
+                let loc = ActionLocation {
+                    group_id: group_id.to_string(),
+                    action_id: action_id.to_string(),
+                    index,
+                };
+                check_binding_for_problems(binding, &mut report, &loc);
+            }
+        }
+    }
+    check_for_single_device_actions(config, &mut report);
     report
 }
+
+/// Notes which actions can only be triggered with a single kind of device, for example only with a gamepad.
+/// Players who don't have that device can't use those actions. This is only reported if the config as a whole
+/// supports more than one kind of device, otherwise every single action would be reported.
+///
+/// The keyboard and mouse are used together, so they count as one kind of device here. Inputs that work with any
+/// device don't count towards the kinds of device used by the config, but an action bound to one is never reported.
+fn check_for_single_device_actions(config: &InputConfig, report: &mut InputConfigReport) {
+    let mut actions: Vec<(&String, &String, InputDeviceCategory)> = Vec::new();
+    let mut used_by_config: Vec<InputDeviceCategory> = Vec::new();
+    for (group_id, groups) in &config.bindings {
+        for (action_id, bindings) in groups {
+            let mut categories = Vec::new();
+            for binding in bindings {
+                binding_device_categories(binding, &mut categories);
+            }
+            let mut devices: Vec<InputDeviceCategory> = categories
+                .iter()
+                .map(|category| device_kind(*category))
+                .collect();
+            devices.dedup();
+            for device in &devices {
+                if *device != InputDeviceCategory::All && !used_by_config.contains(device) {
+                    used_by_config.push(*device);
+                }
+            }
+            if let [device] = devices[..] {
+                if device != InputDeviceCategory::All {
+                    // Report the exact category if there is one, otherwise both the keyboard and mouse are used.
+                    let device = if let [category] = categories[..] {
+                        category
+                    } else {
+                        device
+                    };
+                    actions.push((group_id, action_id, device));
+                }
+            }
+        }
+    }
+    if used_by_config.len() < 2 {
+        return;
+    }
+    for (group_id, action_id, device) in actions {
+        report.info(InputConfigProblem::ActionUsesSingleDevice {
+            group_id: group_id.to_string(),
+            action_id: action_id.to_string(),
+            device,
+        });
+    }
+}
+
+/// Maps the mouse onto the keyboard, since players use them together.
+fn device_kind(category: InputDeviceCategory) -> InputDeviceCategory {
+    match category {
+        InputDeviceCategory::Mouse => InputDeviceCategory::Keyboard,
+        other => other,
+    }
+}
+
+/// Collects the device category of every input in the binding, sorted and without duplicates.
+/// Inputs that can't be pinned down to a single category, like the touch screen, count as `InputDeviceCategory::All`.
+fn binding_device_categories(binding: &InputBinding, out: &mut Vec<InputDeviceCategory>) {
+    match binding {
+        InputBinding::SingleAxis(axis) => axis_device_categories(axis, out),
+        InputBinding::DualAxis { x, y } => {
+            axis_device_categories(x, out);
+            axis_device_categories(y, out);
+        }
+        InputBinding::Continuous(continuous) => match continuous {
+            ContinuousBinding::Dummy => {}
+            ContinuousBinding::Hold(chord, _) => chord_device_categories(chord, out),
+            ContinuousBinding::Toggle(pulse) => pulse_device_categories(pulse, out),
+        },
+        InputBinding::Pulse(pulse) => pulse_device_categories(pulse, out),
+    }
+    out.sort_unstable_by_key(|category| *category as u8);
+    out.dedup();
+}
+
+pub(crate) fn axis_device_categories(axis: &SingleAxisBinding, out: &mut Vec<InputDeviceCategory>) {
+    match axis {
+        SingleAxisBinding::Dummy => {}
+        SingleAxisBinding::Analog { input, .. } | SingleAxisBinding::Accumulate { input, .. } => {
+            out.push(input.device_category());
+        }
+        SingleAxisBinding::Hold(neg, pos, _) => {
+            chord_device_categories(neg, out);
+            chord_device_categories(pos, out);
+        }
+        SingleAxisBinding::Toggle(neg, pos, _) => {
+            pulse_device_categories(neg, out);
+            pulse_device_categories(pos, out);
+        }
+    }
+}
+
+fn pulse_device_categories(pulse: &PulseBinding, out: &mut Vec<InputDeviceCategory>) {
+    match pulse {
+        PulseBinding::Dummy => {}
+        PulseBinding::JustPressed(chord)
+        | PulseBinding::JustReleased(chord)
+        | PulseBinding::DoubleClick(chord) => chord_device_categories(chord, out),
+        PulseBinding::Sequence(_, chords, _) => {
+            for chord in chords {
+                chord_device_categories(chord, out);
+            }
+        }
+        PulseBinding::Notches(input, _) => out.push(input.device_category()),
+        // Swipes work with both the touch screen and the mouse.
+        PulseBinding::Swipe(_) => out.push(InputDeviceCategory::All),
+        PulseBinding::AnyInput(category) => out.push(*category),
+    }
+}
+
+fn chord_device_categories(chord: &Chord, out: &mut Vec<InputDeviceCategory>) {
+    out.extend(chord.iter().map(|input| input.device_category()));
+}
+
+/// Reports an error if the bindings of a single action are not all of the same kind.
+/// Unlike the wrong-kind check, this doesn't need to know which kind the action was registered as.
+fn check_for_mixed_kinds(
+    group_id: &str,
+    action_id: &str,
+    bindings: &[InputBinding],
+    report: &mut InputConfigReport,
+) {
+    let mut kinds: Vec<InputKind> = Vec::new();
+    for binding in bindings {
+        if !kinds.contains(&binding.kind()) {
+            kinds.push(binding.kind());
+        }
+    }
+    if kinds.len() > 1 {
+        report.error(InputConfigProblem::ActionHasMixedKinds {
+            group_id: group_id.to_string(),
+            action_id: action_id.to_string(),
+            kinds,
+        });
+    }
+}
+
+fn check_binding_for_problems(
+    binding: &InputBinding,
+    report: &mut InputConfigReport,
+    loc: &ActionLocation,
+) {
+    match binding {
+        InputBinding::SingleAxis(axis) => {
+            if matches!(axis, SingleAxisBinding::Dummy) {
+                report.warning(InputConfigProblem::RootBindingIsDummy { loc: loc.clone() });
+            }
+            axis_single::check_for_problems(axis, report, loc);
+        }
+        InputBinding::DualAxis { x, y } => {
+            if matches!(x, SingleAxisBinding::Dummy) && matches!(y, SingleAxisBinding::Dummy) {
+                report.warning(InputConfigProblem::RootBindingIsDummy { loc: loc.clone() });
+            }
+            axis_single::check_for_problems(x, report, loc);
+            axis_single::check_for_problems(y, report, loc);
+        }
+        InputBinding::Continuous(continuous) => {
+            if matches!(continuous, ContinuousBinding::Dummy) {
+                report.warning(InputConfigProblem::RootBindingIsDummy { loc: loc.clone() });
+            }
+            continuous::check_for_problems(continuous, report, loc);
+        }
+        InputBinding::Pulse(pulse) => {
+            if matches!(pulse, PulseBinding::Dummy) {
+                report.warning(InputConfigProblem::RootBindingIsDummy { loc: loc.clone() });
+            }
+            pulse::check_for_problems(pulse, report, loc);
+        }
+    }
+}
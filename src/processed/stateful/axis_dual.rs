@@ -1,26 +1,41 @@
 use bevy::log::error;
 use bevy::prelude::{Reflect, Vec2};
+use serde::{Deserialize, Serialize};
 
 use crate::bindings::InputBinding;
 use crate::input_action::InputAction;
 use crate::phantom::{DualAxis, IAWrp};
 use crate::processed::bound_action::BoundAction;
 use crate::processed::processor::Helper;
-use crate::processed::stateful::axis_single::StatefulSingleAxisBinding;
-use crate::processed::updating::InputSources;
-use crate::resources::Ineffable;
+use crate::processed::stateful::axis_single::{SingleAxisState, StatefulSingleAxisBinding};
+use crate::processed::updating::{ConsumedInputs, InputSources};
+use crate::resources::{AngleReference, Ineffable};
 
-#[derive(Debug, Reflect, Clone)]
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) struct StatefulDualAxisBinding {
     x: StatefulSingleAxisBinding,
     y: StatefulSingleAxisBinding,
     pub(crate) value: Vec2,
+    /// The most recent value that was not zero. Keeps the angle stable while the action is neutral.
+    last_direction: Vec2,
+}
+
+/// The transient state of a dual axis action, without any of its bindings. See `InputSnapshot`.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
+pub(crate) struct DualAxisState {
+    x: SingleAxisState,
+    y: SingleAxisState,
+    value: Vec2,
+    last_direction: Vec2,
 }
 
 pub(crate) fn bound_action<I: InputAction>(
     ineffable: &Ineffable,
     input_action: IAWrp<I, DualAxis>,
 ) -> Option<&StatefulDualAxisBinding> {
+    if ineffable.is_disabled(I::group_id(), input_action.0.action_id()) {
+        return None;
+    }
     ineffable
         .groups.get(I::group_id())?.get(input_action.0.index())
         .and_then(|bound_action| {
@@ -52,11 +67,58 @@ impl StatefulDualAxisBinding {
             x: StatefulSingleAxisBinding::new(&vec_x, helper),
             y: StatefulSingleAxisBinding::new(&vec_y, helper),
             value: Vec2::default(),
+            last_direction: Vec2::default(),
+        }
+    }
+    pub(crate) fn consume(&self, sources: &InputSources<'_, '_>, consumed: &mut ConsumedInputs) {
+        self.x.consume(sources, consumed);
+        self.y.consume(sources, consumed);
+    }
+    pub(crate) fn reset(&mut self) {
+        self.x.reset();
+        self.y.reset();
+        self.value = Vec2::ZERO;
+        self.last_direction = Vec2::ZERO;
+    }
+    pub(crate) fn state(&self) -> DualAxisState {
+        DualAxisState {
+            x: self.x.state(),
+            y: self.y.state(),
+            value: self.value,
+            last_direction: self.last_direction,
+        }
+    }
+    pub(crate) fn restore(&mut self, state: &DualAxisState) {
+        self.x.restore(&state.x);
+        self.y.restore(&state.y);
+        self.value = state.value;
+        self.last_direction = state.last_direction;
+    }
+    pub(crate) fn update(&mut self, sources: &InputSources<'_, '_>) {
+        let x = self.x.update_bindings(sources);
+        let y = self.y.update_bindings(sources);
+        // Both axes come from the same dual-axis bindings, so moving one axis deliberately switches both over.
+        let used = self
+            .x
+            .find_deliberately_used(&x, sources)
+            .or_else(|| self.y.find_deliberately_used(&y, sources));
+        self.x.resolve(&x, used, sources);
+        self.y.resolve(&y, used, sources);
+        self.set_value(Vec2::new(self.x.value, self.y.value));
+    }
+    pub(crate) fn set_value(&mut self, value: Vec2) {
+        self.value = value;
+        if value != Vec2::ZERO {
+            self.last_direction = value;
         }
     }
-    pub(crate) fn update(&mut self, sources: &mut InputSources<'_>) {
-        self.x.update(sources);
-        self.y.update(sources);
-        self.value = Vec2::new(self.x.value, self.y.value);
+    /// Returns the angle and magnitude of the value. See `Ineffable::direction_2d_polar()`.
+    pub(crate) fn polar(&self, reference: AngleReference) -> (f32, f32) {
+        let direction = self.last_direction;
+        let angle = match reference {
+            AngleReference::PositiveX => direction.y.atan2(direction.x),
+            AngleReference::PositiveY => direction.x.atan2(direction.y),
+        };
+        (angle, self.value.length())
     }
 }
@@ -2,40 +2,83 @@ use std::ops::Not;
 
 use bevy::log::error;
 use bevy::prelude::Reflect;
+use serde::{Deserialize, Serialize};
+use smallvec::SmallVec;
 
-use crate::bindings::{InputBinding, Inversion, PulseBinding, Sensitivity, SingleAxisBinding};
+use crate::bindings::{
+    Calibration, InputBinding, InputDeviceCategory, Inversion, PulseBinding, Sensitivity,
+    SingleAxisBinding,
+};
 use crate::input_action::InputAction;
 use crate::phantom::{IAWrp, SingleAxis};
 use crate::processed::bound_action::BoundAction;
-use crate::processed::processor::Helper;
+use crate::processed::processor::{axis_device_categories, Helper};
 use crate::processed::stateful::input_analog::StatefulAnalogInput;
 use crate::processed::stateful::input_binary::StatefulBinaryInput;
 use crate::processed::stateful::pulse::StatefulPulseBinding;
 use crate::processed::stateful::{input_binary, pulse};
-use crate::processed::updating::InputSources;
+use crate::processed::updating::{ConsumedInputs, InputSources};
 use crate::reporting::{ActionLocation, InputConfigProblem, InputConfigReport};
 use crate::resources::meta_data::IneffableMetaItem;
 use crate::resources::Ineffable;
 
-#[derive(Debug, Default, Reflect, Clone)]
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) struct StatefulSingleAxisBinding {
     bindings: Vec<StatefulSingleAxisBindingVariant>,
     pub(crate) value: f32,
     toggled_direction: Direction1D,
+    /// The device category of every binding. Bindings that mix inputs from several devices count as `All`.
+    devices: Vec<InputDeviceCategory>,
+    /// The device that was used last, if "last device wins" is enabled and any binding has been used yet.
+    /// While this is set, the bindings of all other devices are ignored, except for toggles.
+    last_used: Option<InputDeviceCategory>,
+    /// The value of every binding at the moment the last used binding took over. Analog inputs must move away from
+    /// this value before they count as deliberately used, so that a drifting stick can't take over.
+    resting: Vec<f32>,
 }
 
-#[derive(Debug, Reflect, Clone)]
+/// The transient state of a single axis action, without any of its bindings. See `InputSnapshot`.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
+pub(crate) struct SingleAxisState {
+    value: f32,
+    toggled_direction: Direction1D,
+}
+
+/// How far an analog input must move away from where it was resting, before it counts as deliberately used.
+const DELIBERATE_MOVEMENT: f32 = 0.25;
+
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) enum StatefulSingleAxisBindingVariant {
     Dummy,
     /// Support for analog input devices is coming.
-    Analog(StatefulAnalogInput, Inversion, Sensitivity),
+    Analog(
+        StatefulAnalogInput,
+        Inversion,
+        Sensitivity,
+        Option<Calibration>,
+        /// Unipolar: clamp the value to the range 0.0 to 1.0.
+        bool,
+    ),
     Held {
         negative: StatefulBinaryInput,
         positive: StatefulBinaryInput,
+        inversion: Inversion,
     },
     Toggle {
         negative: StatefulPulseBinding,
         positive: StatefulPulseBinding,
+        inversion: Inversion,
+    },
+    Accumulate {
+        input: StatefulAnalogInput,
+        /// How much each unit of movement adds to the value. Negative if the action is inverted.
+        multiplier: f32,
+        min: f32,
+        max: f32,
+        /// Units per second.
+        decay: f32,
+        /// The sum of all movement so far, minus the decay.
+        accumulated: f32,
     },
 }
 
@@ -43,6 +86,9 @@ pub(crate) fn bound_action<I: InputAction>(
     ineffable: &Ineffable,
     input_action: IAWrp<I, SingleAxis>,
 ) -> Option<&StatefulSingleAxisBinding> {
+    if ineffable.is_disabled(I::group_id(), input_action.0.action_id()) {
+        return None;
+    }
     ineffable
         .groups.get(I::group_id())?.get(input_action.0.index())
         .and_then(|bound_action| {
@@ -62,14 +108,14 @@ pub(crate) fn collect<'a>(
 ) {
     match binding {
         SingleAxisBinding::Dummy => {}
-        SingleAxisBinding::Analog { .. } => {
+        SingleAxisBinding::Analog { .. } | SingleAxisBinding::Accumulate { .. } => {
             //todo
         }
-        SingleAxisBinding::Hold(neg, pos) => {
+        SingleAxisBinding::Hold(neg, pos, _) => {
             out.push(meta, neg.clone());
             out.push(meta, pos.clone());
         }
-        SingleAxisBinding::Toggle(neg, pos) => {
+        SingleAxisBinding::Toggle(neg, pos, _) => {
             pulse::collect(out, meta, neg);
             pulse::collect(out, meta, pos);
         }
@@ -83,10 +129,15 @@ pub(crate) fn check_for_problems(
 ) {
     match axis {
         SingleAxisBinding::Dummy => (),
-        SingleAxisBinding::Analog { .. } => {
-            //todo
+        SingleAxisBinding::Analog { calibration, .. } => {
+            if calibration
+                .as_ref()
+                .is_some_and(Calibration::has_zero_bound)
+            {
+                report.error(InputConfigProblem::CalibrationBoundIsZero { loc: loc.clone() });
+            }
         }
-        SingleAxisBinding::Hold(neg, pos) => {
+        SingleAxisBinding::Hold(neg, pos, _) => {
             if neg.is_empty() && pos.is_empty() {
                 report.warning(InputConfigProblem::ConvolutedDummy {
                     loc: loc.clone(),
@@ -95,8 +146,10 @@ pub(crate) fn check_for_problems(
             }
             input_binary::check_for_problems(neg, report, loc);
             input_binary::check_for_problems(pos, report, loc);
+            input_binary::check_for_sustained_problems(neg, report, loc);
+            input_binary::check_for_sustained_problems(pos, report, loc);
         }
-        SingleAxisBinding::Toggle(neg, pos) => {
+        SingleAxisBinding::Toggle(neg, pos, _) => {
             if matches!(neg, PulseBinding::Dummy) && matches!(pos, PulseBinding::Dummy) {
                 report.warning(InputConfigProblem::ConvolutedDummy {
                     loc: loc.clone(),
@@ -106,12 +159,17 @@ pub(crate) fn check_for_problems(
             pulse::check_for_problems(neg, report, loc);
             pulse::check_for_problems(pos, report, loc);
         }
+        SingleAxisBinding::Accumulate { clamp, .. } => {
+            if clamp.0 == clamp.1 || clamp.0.is_nan() || clamp.1.is_nan() {
+                report.error(InputConfigProblem::AccumulateClampIsEmpty { loc: loc.clone() });
+            }
+        }
     }
 }
 
 impl StatefulSingleAxisBinding {
     pub(crate) fn new(data: &[InputBinding], helper: &Helper<'_>) -> StatefulSingleAxisBinding {
-        let stateful_bindings = data
+        let axes: Vec<&SingleAxisBinding> = data
             .iter()
             .filter_map(|binding| {
                 if let InputBinding::SingleAxis(axis) = binding {
@@ -120,111 +178,369 @@ impl StatefulSingleAxisBinding {
                     None
                 }
             })
+            .collect();
+        let devices = axes
+            .iter()
+            .map(|axis| {
+                let mut categories = Vec::new();
+                axis_device_categories(axis, &mut categories);
+                categories.dedup();
+                match categories[..] {
+                    [category] => category,
+                    _ => InputDeviceCategory::All,
+                }
+            })
+            .collect();
+        // The inversion from the action settings comes on top of the inversion of each binding.
+        let invert = |inversion: &Inversion| {
+            if helper.invert {
+                inversion.flipped()
+            } else {
+                inversion.clone()
+            }
+        };
+        let stateful_bindings = axes
+            .into_iter()
             .map(|axis| match axis {
                 SingleAxisBinding::Dummy => StatefulSingleAxisBindingVariant::Dummy,
                 SingleAxisBinding::Analog {
                     input,
                     inversion,
                     sensitivity,
+                    calibration,
+                    unipolar,
                 } => StatefulSingleAxisBindingVariant::Analog(
-                    StatefulAnalogInput::new(input),
-                    inversion.clone(),
-                    sensitivity.clone(),
+                    StatefulAnalogInput::new(input, helper.ignore_pad),
+                    invert(inversion),
+                    sensitivity.scaled(helper.sensitivity),
+                    calibration.clone(),
+                    *unipolar,
                 ),
-                SingleAxisBinding::Hold(negative, positive) => {
+                SingleAxisBinding::Hold(negative, positive, inversion) => {
                     StatefulSingleAxisBindingVariant::Held {
                         negative: StatefulBinaryInput::new(negative, helper),
                         positive: StatefulBinaryInput::new(positive, helper),
+                        inversion: invert(inversion),
                     }
                 }
-                SingleAxisBinding::Toggle(negative, positive) => {
+                SingleAxisBinding::Toggle(negative, positive, inversion) => {
                     StatefulSingleAxisBindingVariant::Toggle {
                         negative: StatefulPulseBinding::new_from_single(negative, helper),
                         positive: StatefulPulseBinding::new_from_single(positive, helper),
+                        inversion: invert(inversion),
                     }
                 }
+                SingleAxisBinding::Accumulate {
+                    input,
+                    sensitivity,
+                    clamp,
+                    decay,
+                } => StatefulSingleAxisBindingVariant::Accumulate {
+                    input: StatefulAnalogInput::new(input, helper.ignore_pad),
+                    multiplier: sensitivity.scaled(helper.sensitivity).multiplier()
+                        * if helper.invert { -1. } else { 1. },
+                    // The bounds may be given in either order.
+                    min: clamp.0.min(clamp.1),
+                    max: clamp.0.max(clamp.1),
+                    decay: decay.max(0.),
+                    accumulated: 0.,
+                },
             })
             .collect();
         StatefulSingleAxisBinding {
             bindings: stateful_bindings,
             value: 0.,
             toggled_direction: Direction1D::Neutral,
+            devices,
+            last_used: None,
+            resting: Vec::new(),
+        }
+    }
+    pub(crate) fn consume(&self, sources: &InputSources<'_, '_>, consumed: &mut ConsumedInputs) {
+        for binding in &self.bindings {
+            match binding {
+                StatefulSingleAxisBindingVariant::Dummy => {}
+                StatefulSingleAxisBindingVariant::Analog(input, ..)
+                | StatefulSingleAxisBindingVariant::Accumulate { input, .. } => {
+                    input.consume(consumed);
+                }
+                StatefulSingleAxisBindingVariant::Held {
+                    negative, positive, ..
+                } => {
+                    negative.consume(sources, consumed);
+                    positive.consume(sources, consumed);
+                }
+                StatefulSingleAxisBindingVariant::Toggle {
+                    negative, positive, ..
+                } => {
+                    negative.consume(sources, consumed);
+                    positive.consume(sources, consumed);
+                }
+            }
         }
     }
-    pub(crate) fn update(&mut self, sources: &mut InputSources<'_>) {
-        let (min, max, toggle_neg, toggle_pos, newly_held) = self.bindings.iter_mut().fold(
-            (0., 0., false, false, false),
-            |(min, max, toggle_neg, toggle_pos, newly_held), binding| match binding {
-                StatefulSingleAxisBindingVariant::Dummy => {
-                    (min, max, toggle_neg, toggle_pos, newly_held)
+    pub(crate) fn reset(&mut self) {
+        for binding in &mut self.bindings {
+            match binding {
+                StatefulSingleAxisBindingVariant::Toggle {
+                    negative, positive, ..
+                } => {
+                    negative.reset();
+                    positive.reset();
+                }
+                StatefulSingleAxisBindingVariant::Accumulate { accumulated, .. } => {
+                    *accumulated = 0.;
                 }
-                StatefulSingleAxisBindingVariant::Analog(input, inversion, sensitivity) => {
+                _ => {}
+            }
+        }
+        self.value = 0.;
+        self.toggled_direction = Direction1D::Neutral;
+        self.last_used = None;
+        self.resting.clear();
+    }
+    pub(crate) fn state(&self) -> SingleAxisState {
+        SingleAxisState {
+            value: self.value,
+            toggled_direction: self.toggled_direction,
+        }
+    }
+    pub(crate) fn restore(&mut self, state: &SingleAxisState) {
+        self.value = state.value;
+        self.toggled_direction = state.toggled_direction;
+    }
+    pub(crate) fn update(&mut self, sources: &InputSources<'_, '_>) {
+        let outputs = self.update_bindings(sources);
+        let used = self.find_deliberately_used(&outputs, sources);
+        self.resolve(&outputs, used, sources);
+    }
+    /// Updates every binding, and returns what each of them contributes this tick.
+    pub(crate) fn update_bindings(
+        &mut self,
+        sources: &InputSources<'_, '_>,
+    ) -> SmallVec<[BindingOutput; 4]> {
+        let resting = &self.resting;
+        self.bindings
+            .iter_mut()
+            .enumerate()
+            .map(|(index, binding)| match binding {
+                StatefulSingleAxisBindingVariant::Dummy => BindingOutput::Nothing,
+                StatefulSingleAxisBindingVariant::Analog(
+                    input,
+                    inversion,
+                    sensitivity,
+                    calibration,
+                    unipolar,
+                ) => {
                     input.update(sources);
-                    let value =
-                        input.value_current * inversion.multiplier() * sensitivity.multiplier();
-                    (
-                        value.min(min),
-                        value.max(max),
-                        toggle_neg,
-                        toggle_pos,
-                        newly_held || input.just_activated(),
-                    )
+                    let raw = calibration
+                        .as_ref()
+                        .map_or(input.value_current, |calibration| {
+                            calibration.apply(input.value_current)
+                        });
+                    let value = raw
+                        * inversion.multiplier()
+                        * sensitivity.multiplier()
+                        * sources.settings.global_sensitivity;
+                    let value = if *unipolar {
+                        value.clamp(0., 1.)
+                    } else {
+                        value
+                    };
+                    let rest = resting.get(index).copied().unwrap_or_default();
+                    BindingOutput::Value {
+                        value,
+                        newly_held: input.just_activated(),
+                        deliberate: (value - rest).abs() > DELIBERATE_MOVEMENT,
+                    }
                 }
-                StatefulSingleAxisBindingVariant::Held { negative, positive } => {
+                StatefulSingleAxisBindingVariant::Held {
+                    negative,
+                    positive,
+                    inversion,
+                } => {
                     negative.update(sources);
                     positive.update(sources);
-                    (
-                        if negative.is_active() {
-                            min.min(-1.)
-                        } else {
-                            min
+                    // If both are held, they cancel each other out.
+                    let value = (f32::from(u8::from(positive.is_active()))
+                        - f32::from(u8::from(negative.is_active())))
+                        * inversion.multiplier();
+                    let newly_held = negative.just_pressed() || positive.just_pressed();
+                    BindingOutput::Value {
+                        value,
+                        newly_held,
+                        deliberate: newly_held,
+                    }
+                }
+                StatefulSingleAxisBindingVariant::Toggle {
+                    negative,
+                    positive,
+                    inversion,
+                } => {
+                    negative.update(sources);
+                    positive.update(sources);
+                    match inversion {
+                        Inversion::NotInverted => BindingOutput::Toggle {
+                            negative: negative.just_pulsed,
+                            positive: positive.just_pulsed,
                         },
-                        if positive.is_active() {
-                            max.max(1.)
-                        } else {
-                            max
+                        Inversion::Inverted => BindingOutput::Toggle {
+                            negative: positive.just_pulsed,
+                            positive: negative.just_pulsed,
                         },
+                    }
+                }
+                StatefulSingleAxisBindingVariant::Accumulate {
+                    input,
+                    multiplier,
+                    min,
+                    max,
+                    decay,
+                    accumulated,
+                } => {
+                    input.update(sources);
+                    // Decay towards zero first, without overshooting it.
+                    let decayed = *decay * sources.time.delta_seconds();
+                    *accumulated -= accumulated.signum() * decayed.min(accumulated.abs());
+                    let movement =
+                        input.value_current * *multiplier * sources.settings.global_sensitivity;
+                    // Unlike `clamp()`, this doesn't panic if a bound is NaN.
+                    *accumulated = (*accumulated + movement).max(*min).min(*max);
+                    let moved = input.value_current.abs() > f32::EPSILON;
+                    BindingOutput::Value {
+                        value: *accumulated,
+                        newly_held: input.just_activated(),
+                        deliberate: moved,
+                    }
+                }
+            })
+            .collect()
+    }
+    /// If "last device wins" is enabled, returns the device of a binding that was deliberately used this tick, if
+    /// that isn't the device that was used last. Keys count as soon as they are pressed, but analog inputs must move away from
+    /// where they were resting when another binding took over.
+    #[must_use]
+    pub(crate) fn find_deliberately_used(
+        &self,
+        outputs: &[BindingOutput],
+        sources: &InputSources<'_, '_>,
+    ) -> Option<InputDeviceCategory> {
+        if !sources.settings.last_device_wins {
+            return None;
+        }
+        outputs
+            .iter()
+            .zip(&self.devices)
+            .find(|(output, device)| {
+                Some(**device) != self.last_used
+                    && matches!(
+                        output,
+                        BindingOutput::Value {
+                            deliberate: true,
+                            ..
+                        }
+                    )
+            })
+            .map(|(_, device)| *device)
+    }
+    /// Combines the outputs of all bindings into a single value.
+    /// If `used` is given, that device becomes the last used one, and the others are ignored from now on.
+    #[allow(clippy::unnecessary_map_or)] // `Option::is_none_or()` needs Rust 1.82.
+    pub(crate) fn resolve(
+        &mut self,
+        outputs: &[BindingOutput],
+        used: Option<InputDeviceCategory>,
+        sources: &InputSources<'_, '_>,
+    ) {
+        if !sources.settings.last_device_wins {
+            self.last_used = None;
+        } else if used.is_some() {
+            self.last_used = used;
+            self.resting = outputs
+                .iter()
+                .map(|output| match output {
+                    BindingOutput::Value { value, .. } => *value,
+                    _ => 0.,
+                })
+                .collect();
+        }
+        let (min, max, toggle_neg, toggle_pos, newly_held) = outputs
+            .iter()
+            .enumerate()
+            .filter(|(index, output)| {
+                // Toggles are never drowned out by noise, so they always count.
+                matches!(output, BindingOutput::Toggle { .. })
+                    || self
+                        .last_used
+                        .map_or(true, |last_used| self.devices[*index] == last_used)
+            })
+            .fold(
+                (0., 0., false, false, false),
+                |(min, max, toggle_neg, toggle_pos, newly_held), (_, output)| match output {
+                    BindingOutput::Nothing => (min, max, toggle_neg, toggle_pos, newly_held),
+                    BindingOutput::Value {
+                        value,
+                        newly_held: newly,
+                        ..
+                    } => (
+                        value.min(min),
+                        value.max(max),
                         toggle_neg,
                         toggle_pos,
-                        newly_held || negative.just_pressed() || positive.just_pressed(),
-                    )
-                }
-                StatefulSingleAxisBindingVariant::Toggle { negative, positive } => {
-                    negative.update(sources);
-                    positive.update(sources);
-                    (
+                        newly_held || *newly,
+                    ),
+                    BindingOutput::Toggle { negative, positive } => (
                         min,
                         max,
-                        toggle_neg || negative.just_pulsed,
-                        toggle_pos || positive.just_pulsed,
+                        toggle_neg || *negative,
+                        toggle_pos || *positive,
                         newly_held,
-                    )
-                }
-            },
-        );
+                    ),
+                },
+            );
         let toggle = Direction1D::from_input(toggle_neg, toggle_pos);
         if !matches!(toggle, Direction1D::Neutral) {
             self.toggled_direction = self.toggled_direction.toggle(toggle);
         } else if newly_held {
             self.toggled_direction = Direction1D::Neutral;
         }
+        // Of all bindings, the one with the greatest magnitude wins, keeping its sign. This lets players switch
+        // between devices freely: holding a key is full deflection, even while a stick is slightly pushed the other
+        // way. If opposite directions are equally strong (like holding both keys), they cancel each other out.
         self.value = if !matches!(self.toggled_direction, Direction1D::Neutral) {
             self.toggled_direction.signum()
-        } else if min < 0. && max > 0. {
-            0.
-        } else if min < 0. {
+        } else if -min > max {
             min
-        } else {
+        } else if max > -min {
             max
+        } else {
+            0.
         };
     }
 }
 
+/// What a single binding contributes to the value of its axis during one tick.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BindingOutput {
+    Nothing,
+    Value {
+        value: f32,
+        /// True iff the binding started being held this tick.
+        newly_held: bool,
+        /// True iff the player deliberately used the binding this tick, as opposed to it merely being noisy.
+        deliberate: bool,
+    },
+    Toggle {
+        negative: bool,
+        positive: bool,
+    },
+}
+
 // =====================================================================================================================
 // ===== Direction helper enum:
 // =====================================================================================================================
 
-#[derive(Debug, Default, Reflect, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, Copy, PartialEq, Eq)]
 enum Direction1D {
     Negative,
     Positive,
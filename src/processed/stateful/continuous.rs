@@ -4,8 +4,9 @@ use bevy::log::error;
 use bevy::prelude::Reflect;
 use bevy::time::Stopwatch;
 use bevy::utils::default;
+use serde::{Deserialize, Serialize};
 
-use crate::bindings::{ContinuousBinding, InputBinding, PulseBinding};
+use crate::bindings::{Chord, ContinuousBinding, HoldOptions, InputBinding, PulseBinding};
 use crate::input_action::InputAction;
 use crate::phantom::{Continuous, IAWrp};
 use crate::processed::bound_action::BoundAction;
@@ -13,7 +14,7 @@ use crate::processed::processor::Helper;
 use crate::processed::stateful::input_binary::StatefulBinaryInput;
 use crate::processed::stateful::pulse::StatefulPulseBinding;
 use crate::processed::stateful::{input_binary, pulse};
-use crate::processed::updating::InputSources;
+use crate::processed::updating::{ConsumedInputs, InputSources};
 use crate::reporting::{ActionLocation, InputConfigProblem, InputConfigReport};
 use crate::resources::meta_data::IneffableMetaItem;
 use crate::resources::Ineffable;
@@ -23,26 +24,101 @@ use crate::resources::Ineffable;
 // /// Setting this as a floor prevents strange glitches caused by mistaken assumptions about the charge length.
 // pub const MINIMUM_CHARGE_DURATION_MILLIS: u64 = 50;
 
-#[derive(Debug, Reflect, Default, Clone)]
+#[derive(Debug, Serialize, Deserialize, Reflect, Default, Clone)]
 pub(crate) struct StatefulContinuousBinding {
     bindings: Vec<StatefulContinuousBindingVariant>,
     toggled_on: bool,
     pub(crate) active: bool,
     pub(crate) active_previous_tick: bool,
     time_active: Stopwatch,
+    /// How long the action has been inactive. Used to decide whether to retain the charge when it activates again.
+    time_inactive: Stopwatch,
 }
 
-#[derive(Debug, Reflect, Clone)]
+/// The transient state of a continuous action, without any of its bindings. See `InputSnapshot`.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
+pub(crate) struct ContinuousState {
+    toggled_on: bool,
+    active: bool,
+    active_previous_tick: bool,
+    time_active: Stopwatch,
+    time_inactive: Stopwatch,
+}
+
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) enum StatefulContinuousBindingVariant {
     Dummy,
-    Held(StatefulBinaryInput),
+    Held(StatefulHeldBinding),
     Toggle(StatefulPulseBinding),
 }
 
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
+pub(crate) struct StatefulHeldBinding {
+    input: StatefulBinaryInput,
+    /// The input must be held down for this long before it counts as held.
+    activation_delay: Duration,
+    /// How long the input has been held down for.
+    held_for: Stopwatch,
+    /// The binding stays held for this long after the input is released.
+    release_grace: Duration,
+    /// Counts down after the input is released. The binding is held until this reaches zero.
+    grace_left: Duration,
+    held: bool,
+    held_previous_tick: bool,
+}
+
+impl StatefulHeldBinding {
+    fn new(input: &Chord, options: &HoldOptions, helper: &Helper<'_>) -> Self {
+        Self {
+            input: StatefulBinaryInput::new(input, helper),
+            activation_delay: Duration::from_millis(options.activation_delay.unwrap_or_default()),
+            held_for: Stopwatch::default(),
+            release_grace: Duration::from_millis(options.release_grace.unwrap_or_default()),
+            grace_left: Duration::ZERO,
+            held: false,
+            held_previous_tick: false,
+        }
+    }
+    fn update(&mut self, sources: &InputSources<'_, '_>) {
+        self.input.update(sources);
+        self.held_previous_tick = self.held;
+        if self.input.is_active() {
+            self.held_for.tick(sources.time.delta());
+        } else {
+            self.held_for.reset();
+        }
+        if self.is_pressed() {
+            // Pressing the input again during the grace period picks up where it left off,
+            // without waiting for the activation delay a second time.
+            self.held = true;
+            self.grace_left = self.release_grace;
+        } else if self.held && !self.grace_left.is_zero() {
+            self.grace_left = self.grace_left.saturating_sub(sources.time.delta());
+            self.held = !self.grace_left.is_zero();
+        } else {
+            self.held = false;
+        }
+    }
+    fn is_active(&self) -> bool {
+        self.held
+    }
+    /// True while the input is actually held down, as opposed to being kept alive by the release grace period.
+    fn is_pressed(&self) -> bool {
+        self.input.is_active() && (self.held || self.held_for.elapsed() >= self.activation_delay)
+    }
+    /// True on the tick the activation delay elapses, rather than on the tick the input was pressed.
+    fn just_pressed(&self) -> bool {
+        self.held && !self.held_previous_tick
+    }
+}
+
 pub(crate) fn bound_action<I: InputAction>(
     ineffable: &Ineffable,
     input_action: IAWrp<I, Continuous>,
 ) -> Option<&StatefulContinuousBinding> {
+    if ineffable.is_disabled(I::group_id(), input_action.0.action_id()) {
+        return None;
+    }
     ineffable
         .groups.get(I::group_id())?.get(input_action.0.index())
         .and_then(|bound_action| {
@@ -62,7 +138,7 @@ pub(crate) fn collect<'a>(
 ) {
     match binding {
         ContinuousBinding::Dummy => {}
-        ContinuousBinding::Hold(input) => {
+        ContinuousBinding::Hold(input, _) => {
             out.push(meta, input.clone());
         }
         ContinuousBinding::Toggle(pulse) => {
@@ -78,7 +154,7 @@ pub(crate) fn check_for_problems(
 ) {
     match continuous {
         ContinuousBinding::Dummy => {}
-        ContinuousBinding::Hold(input) => {
+        ContinuousBinding::Hold(input, _) => {
             if input.is_empty() {
                 report.warning(InputConfigProblem::ConvolutedDummy {
                     loc: loc.clone(),
@@ -86,6 +162,7 @@ pub(crate) fn check_for_problems(
                 });
             }
             input_binary::check_for_problems(input, report, loc);
+            input_binary::check_for_sustained_problems(input, report, loc);
         }
         ContinuousBinding::Toggle(pulse) => {
             if matches!(pulse, PulseBinding::Dummy) {
@@ -112,9 +189,13 @@ impl StatefulContinuousBinding {
             })
             .map(|continuous| match continuous {
                 ContinuousBinding::Dummy => StatefulContinuousBindingVariant::Dummy,
-                ContinuousBinding::Hold(binary_input) => StatefulContinuousBindingVariant::Held(
-                    StatefulBinaryInput::new(binary_input, helper),
-                ),
+                ContinuousBinding::Hold(binary_input, options) => {
+                    StatefulContinuousBindingVariant::Held(StatefulHeldBinding::new(
+                        binary_input,
+                        options,
+                        helper,
+                    ))
+                }
                 ContinuousBinding::Toggle(pulse) => StatefulContinuousBindingVariant::Toggle(
                     StatefulPulseBinding::new_from_single(pulse, helper),
                 ),
@@ -125,23 +206,86 @@ impl StatefulContinuousBinding {
             ..default()
         }
     }
-    pub(crate) fn update(&mut self, sources: &mut InputSources<'_>) {
+    pub(crate) fn consume(&self, sources: &InputSources<'_, '_>, consumed: &mut ConsumedInputs) {
+        for binding in &self.bindings {
+            match binding {
+                StatefulContinuousBindingVariant::Dummy => {}
+                StatefulContinuousBindingVariant::Held(held) => {
+                    held.input.consume(sources, consumed);
+                }
+                StatefulContinuousBindingVariant::Toggle(toggle) => {
+                    toggle.consume(sources, consumed);
+                }
+            }
+        }
+    }
+    pub(crate) fn reset(&mut self) {
+        let mut held_down = false;
+        for binding in &mut self.bindings {
+            match binding {
+                StatefulContinuousBindingVariant::Dummy => {}
+                StatefulContinuousBindingVariant::Held(held) => {
+                    // Inputs that are still held down stay held, so that they don't activate the action again.
+                    held.held_for.reset();
+                    held.grace_left = Duration::ZERO;
+                    held.held = held.held && held.input.is_active();
+                    held.held_previous_tick = held.held;
+                    held_down |= held.held;
+                }
+                StatefulContinuousBindingVariant::Toggle(toggle) => toggle.reset(),
+            }
+        }
+        self.toggled_on = false;
+        self.active = held_down;
+        self.active_previous_tick = held_down;
+        self.time_active.reset();
+        self.time_inactive.reset();
+    }
+    pub(crate) fn state(&self) -> ContinuousState {
+        ContinuousState {
+            toggled_on: self.toggled_on,
+            active: self.active,
+            active_previous_tick: self.active_previous_tick,
+            time_active: self.time_active.clone(),
+            time_inactive: self.time_inactive.clone(),
+        }
+    }
+    pub(crate) fn restore(&mut self, state: &ContinuousState) {
+        self.toggled_on = state.toggled_on;
+        self.active = state.active;
+        self.active_previous_tick = state.active_previous_tick;
+        self.time_active.clone_from(&state.time_active);
+        self.time_inactive.clone_from(&state.time_inactive);
+    }
+    pub(crate) fn update(&mut self, sources: &InputSources<'_, '_>) {
         self.active_previous_tick = self.active;
-        let (held, just_pressed, toggle) = self.bindings.iter_mut().fold(
-            (false, false, false),
-            |(held, just_pressed, toggle), binding| match binding {
-                StatefulContinuousBindingVariant::Dummy => (held, just_pressed, toggle),
+        let hold_to_toggle = sources.settings.hold_to_toggle;
+        let (held, pressed, just_pressed, toggle) = self.bindings.iter_mut().fold(
+            (false, false, false, false),
+            |(held, pressed, just_pressed, toggle), binding| match binding {
+                StatefulContinuousBindingVariant::Dummy => (held, pressed, just_pressed, toggle),
+                StatefulContinuousBindingVariant::Held(input) if hold_to_toggle => {
+                    // Accessibility mode: pressing a hold binding toggles the action instead.
+                    input.update(sources);
+                    (held, pressed, just_pressed, toggle || input.just_pressed())
+                }
                 StatefulContinuousBindingVariant::Held(input) => {
                     input.update(sources);
                     (
                         held || input.is_active(),
+                        pressed || input.is_pressed(),
                         just_pressed || input.just_pressed(),
                         toggle,
                     )
                 }
                 StatefulContinuousBindingVariant::Toggle(toggle_control) => {
                     toggle_control.update(sources);
-                    (held, just_pressed, toggle || toggle_control.just_pulsed)
+                    (
+                        held,
+                        pressed,
+                        just_pressed,
+                        toggle || toggle_control.just_pulsed,
+                    )
                 }
             },
         );
@@ -154,14 +298,23 @@ impl StatefulContinuousBinding {
         }
         self.active = held || self.toggled_on;
         if self.active {
-            self.time_active.tick(sources.time.delta());
+            if !self.active_previous_tick
+                && self.time_inactive.elapsed() > sources.settings.charge_retain_window
+            {
+                self.time_active.reset();
+            }
+            self.time_inactive.reset();
+            // The charge doesn't grow during a release grace period: the player already let go.
+            if pressed || self.toggled_on {
+                self.time_active.tick(sources.time.delta());
+            }
         } else {
-            self.time_active.reset();
+            self.time_inactive.tick(sources.time.delta());
         }
     }
 
     pub fn charging_duration(&self) -> Option<Duration> {
-        if self.time_active.elapsed().is_zero() {
+        if !(self.active || self.active_previous_tick) || self.time_active.elapsed().is_zero() {
             None
         } else {
             Some(self.time_active.elapsed())
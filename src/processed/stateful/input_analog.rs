@@ -1,21 +1,28 @@
 use bevy::prelude::{GamepadAxis, GamepadButton, GamepadButtonType, Reflect};
+use serde::{Deserialize, Serialize};
 
 use crate::bindings::AnalogInput;
-use crate::processed::updating::InputSources;
+use crate::processed::updating::{ConsumedInputs, InputSources};
 
-#[derive(Debug, Reflect, Clone)]
+/// The frame rate at which the motion smoothing factor is applied as is. See `IneffableSettings::motion_smoothing`.
+const SMOOTHING_REFERENCE_FRAME_RATE: f32 = 60.;
+
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) struct StatefulAnalogInput {
     analog_input: AnalogInput,
     pub(crate) value_current: f32,
     pub(crate) value_previous: f32,
+    /// If true, the post-acceptance delay does not block this input.
+    ignore_pad: bool,
 }
 
 impl StatefulAnalogInput {
-    pub fn new(input: &AnalogInput) -> Self {
+    pub fn new(input: &AnalogInput, ignore_pad: bool) -> Self {
         Self {
             analog_input: input.clone(),
             value_current: 0.,
             value_previous: 0.,
+            ignore_pad,
         }
     }
 
@@ -23,27 +30,68 @@ impl StatefulAnalogInput {
         self.value_current.abs() > f32::EPSILON && self.value_previous.abs() < f32::EPSILON
     }
 
-    pub(crate) fn update(&mut self, sources: &mut InputSources<'_>) {
+    pub(crate) fn update(&mut self, sources: &InputSources<'_, '_>) {
         self.value_previous = self.value_current;
 
         // If the post-acceptance-delay is active, then do nothing. We should ignore all user input.
-        if sources.settings.input_blocked_by_pad() {
+        if !self.ignore_pad && sources.settings.input_blocked_by_pad() {
             return;
         }
-        self.value_current = Self::calc_value(&self.analog_input, sources);
+        let value = Self::available_value(&self.analog_input, sources);
+        let smoothing = sources.settings.motion_smoothing;
+        self.value_current = if smoothing > 0. && self.is_mouse_motion() {
+            // The smoothing factor applies to a single reference frame. Scale it to the length of this tick, so
+            // that the mouse feels the same at any frame rate.
+            let weight =
+                smoothing.powf(sources.time.delta_seconds() * SMOOTHING_REFERENCE_FRAME_RATE);
+            weight * self.value_previous + (1. - weight) * value
+        } else {
+            value
+        };
+    }
+    /// While the input is moving, adds it to the consumed inputs.
+    pub(crate) fn consume(&self, consumed: &mut ConsumedInputs) {
+        if self.value_current.abs() > f32::EPSILON {
+            consumed.analog.push(self.analog_input.clone());
+        }
+    }
+    /// The current value of the input, or zero if it is consumed by a context with a higher priority.
+    pub(crate) fn available_value(input: &AnalogInput, sources: &InputSources<'_, '_>) -> f32 {
+        if sources.consumed.analog.contains(input) {
+            0.
+        } else {
+            Self::calc_value(input, sources)
+        }
     }
-    pub(crate) fn calc_value(input: &AnalogInput, sources: &InputSources<'_>) -> f32 {
+    fn is_mouse_motion(&self) -> bool {
+        matches!(
+            self.analog_input,
+            AnalogInput::MouseMotionX | AnalogInput::MouseMotionY
+        )
+    }
+    pub(crate) fn calc_value(input: &AnalogInput, sources: &InputSources<'_, '_>) -> f32 {
         match input {
+            AnalogInput::ScrollWheelX
+            | AnalogInput::ScrollWheelY
+            | AnalogInput::MouseMotionX
+            | AnalogInput::MouseMotionY
+                if !sources.uses_keyboard_mouse() =>
+            {
+                0.
+            }
             AnalogInput::ScrollWheelX => sources.from_events.mouse_scroll.x,
             AnalogInput::ScrollWheelY => sources.from_events.mouse_scroll.y,
-            AnalogInput::MouseMotionX => sources.from_events.mouse_motion.x,
-            AnalogInput::MouseMotionY => sources.from_events.mouse_motion.y,
+            AnalogInput::MouseMotionX => {
+                sources.from_events.mouse_motion.x * sources.settings.mouse_sensitivity.x
+            }
+            AnalogInput::MouseMotionY => {
+                sources.from_events.mouse_motion.y * sources.settings.mouse_sensitivity.y
+            }
+            AnalogInput::TouchPinch => sources.from_events.touch_pinch,
             AnalogInput::GamePad(axis_type) => {
-                // For now, we don't support local multiplayer. (Will change in the future)
-                // We'll check if the button is active on *any* connected gamepad.
+                // We'll check if the axis is active on *any* of the assigned gamepads.
                 sources
-                    .gamepads
-                    .iter()
+                    .assigned_gamepads()
                     .filter_map(|gamepad| {
                         sources
                             .axis_gamepad_axis
@@ -61,12 +109,10 @@ impl StatefulAnalogInput {
         }
     }
 
-    fn gamepad_value(btn: GamepadButtonType, sources: &InputSources<'_>) -> f32 {
-        // For now, we don't support local multiplayer. (Will change in the future)
-        // We'll check if the button is active on *any* connected gamepad.
+    fn gamepad_value(btn: GamepadButtonType, sources: &InputSources<'_, '_>) -> f32 {
+        // We'll check if the button is active on *any* of the assigned gamepads.
         sources
-            .gamepads
-            .iter()
+            .assigned_gamepads()
             .filter_map(|gamepad| {
                 sources
                     .axis_gamepad_btn
@@ -1,28 +1,34 @@
 use bevy::prelude::{GamepadButton, Reflect};
+use serde::{Deserialize, Serialize};
 
 use crate::bindings::{BinaryInput, Chord};
 use crate::processed::processor::Helper;
 use crate::processed::stateful::input_analog::StatefulAnalogInput;
-use crate::processed::updating::InputSources;
+use crate::processed::updating::{ConsumedInputs, InputSources};
 use crate::reporting::{ActionLocation, InputConfigProblem, InputConfigReport};
 
-#[derive(Debug, Reflect, Clone)]
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) struct StatefulBinaryInput {
     binary_input: ProcessedChord,
     active: bool,
     active_previous_tick: bool,
     blocked: bool,
+    /// True while the input is consumed by a context with a higher priority. Once consumed, the input stays consumed
+    /// until it is released, even if the context is removed in the meantime.
+    consumed: bool,
     /// These are the inputs that conflict with this one and take precedence over it.
     /// If any of these binary inputs is active, this one cannot be active.
     blockers: Vec<Chord>,
+    /// If true, the post-acceptance delay neither blocks this input, nor is started by it.
+    ignore_pad: bool,
 }
 
 /// This enum prevents us requiring a Vec for every single input.
-#[derive(Debug, Reflect, Clone)]
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) enum ProcessedChord {
     Dummy,
     Single(BinaryInput),
-    Chord(Vec<BinaryInput>),
+    Chord(Chord),
 }
 
 impl ProcessedChord {
@@ -51,91 +57,173 @@ pub(crate) fn check_for_problems(
     }
 }
 
+/// Checks a chord that is expected to be held down for a while, like in a hold binding.
+/// Delta-based analog inputs only reach their threshold on the tick they move, so they can't be held.
+pub(crate) fn check_for_sustained_problems(
+    input: &Chord,
+    report: &mut InputConfigReport,
+    loc: &ActionLocation,
+) {
+    for child in input {
+        if let BinaryInput::Axis(analog, _) = child {
+            if analog.is_delta_based() {
+                report.warning(InputConfigProblem::DeltaInputInHoldBinding {
+                    loc: loc.clone(),
+                    input: format!("{analog:?}"),
+                });
+            }
+        }
+    }
+}
+
 impl StatefulBinaryInput {
     pub(crate) fn new(value: &Chord, helper: &Helper<'_>) -> StatefulBinaryInput {
-        let blockers = helper
-            .inputs
-            .iter()
-            .filter_map(|(_, other)| {
-                if is_blocked_by(value, other) {
-                    Some(other.clone())
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let blockers = if helper.chord_blocking {
+            helper
+                .inputs
+                .iter()
+                .filter_map(|(_, other)| {
+                    if is_blocked_by(value, other) {
+                        Some(other.clone())
+                    } else {
+                        None
+                    }
+                })
+                .collect()
+        } else {
+            // Chord blocking was disabled for this action, so nothing can block it.
+            Vec::new()
+        };
         StatefulBinaryInput {
             binary_input: ProcessedChord::new(value.clone()),
             active: false,
             active_previous_tick: false,
             blocked: false,
+            consumed: false,
             blockers,
+            ignore_pad: helper.ignore_pad,
         }
     }
     pub(crate) fn is_active(&self) -> bool {
         !self.blocked && self.active
     }
+    /// True iff the input became active this tick. For a multi-input chord, this means that all of its inputs are
+    /// held down now, and they were not all held down last tick; ie: at least one of them was newly pressed.
     pub(crate) fn just_pressed(&self) -> bool {
         !self.blocked && self.active && !self.active_previous_tick
     }
     pub(crate) fn just_released(&self) -> bool {
         !self.blocked && !self.active && self.active_previous_tick
     }
-    pub(crate) fn update(&mut self, sources: &mut InputSources<'_>) {
+    /// If this input is active, adds everything it consists of to the consumed inputs.
+    pub(crate) fn consume(&self, sources: &InputSources<'_, '_>, consumed: &mut ConsumedInputs) {
+        if !self.is_active() {
+            return;
+        }
+        let inputs: &[BinaryInput] = match &self.binary_input {
+            ProcessedChord::Dummy => &[],
+            ProcessedChord::Single(input) => std::slice::from_ref(input),
+            ProcessedChord::Chord(inputs) => inputs,
+        };
+        for input in inputs {
+            match input {
+                // Only the keys of the group that are actually held down are in use.
+                BinaryInput::KeyGroup(group) => consumed.binary.extend(
+                    group
+                        .iter()
+                        .filter(|key_code| sources.input_keycodes.pressed(**key_code))
+                        .map(|key_code| BinaryInput::Key(*key_code)),
+                ),
+                input => consumed.binary.push(input.clone()),
+            }
+        }
+    }
+    pub(crate) fn update(&mut self, sources: &InputSources<'_, '_>) {
         self.active_previous_tick = self.active;
+        let pressed = Self::is_chord_pressed(&self.binary_input, sources, false);
+        let available = Self::is_chord_pressed(&self.binary_input, sources, true);
 
-        // If the post-acceptance-delay is active, then do nothing. We should ignore all user input.
-        if sources.settings.input_blocked_by_pad() {
+        // If the post-acceptance-delay is active, then ignore all new user input.
+        // Releases still come through, otherwise held actions could get stuck in the active state.
+        if !self.ignore_pad && sources.settings.input_blocked_by_pad() {
+            self.active = self.active && available;
             return;
         }
 
-        self.active = Self::is_chord_pressed(&self.binary_input, sources);
-        self.blocked = self
-            .blockers
-            .iter()
-            .any(|blocker| blocker.iter().all(|child| Self::is_pressed(child, sources)));
+        self.active = pressed;
+        // An input that is consumed by a context with a higher priority is blocked until it is released. That way,
+        // removing the context while the input is still held down doesn't count as pressing it.
+        self.consumed = pressed && (self.consumed || !available);
+        self.blocked = self.consumed
+            || self
+                .blockers
+                .iter()
+                .any(|blocker| blocker.iter().all(|child| Self::is_pressed(child, sources)));
 
         // If the user just activated this input, then report this to the post-acceptance-delay.
-        if self.just_pressed() {
-            if let Some(pad) = &mut sources.settings.post_acceptance_delay {
-                pad.input_detected();
-            }
+        if self.just_pressed() && !self.ignore_pad {
+            sources.input_detected();
         }
     }
-    fn is_chord_pressed(chord: &ProcessedChord, sources: &InputSources<'_>) -> bool {
+    fn is_chord_pressed(
+        chord: &ProcessedChord,
+        sources: &InputSources<'_, '_>,
+        skip_consumed: bool,
+    ) -> bool {
         match chord {
             ProcessedChord::Dummy => false,
-            ProcessedChord::Single(input) => Self::is_pressed(input, sources),
-            ProcessedChord::Chord(inputs) => {
-                inputs.iter().all(|child| Self::is_pressed(child, sources))
-            }
+            ProcessedChord::Single(input) => Self::is_held(input, sources, skip_consumed),
+            ProcessedChord::Chord(inputs) => inputs
+                .iter()
+                .all(|child| Self::is_held(child, sources, skip_consumed)),
         }
     }
-    fn is_pressed(input: &BinaryInput, sources: &InputSources<'_>) -> bool {
+    /// Returns true iff the input is pressed, and not consumed by a context with a higher priority.
+    pub(crate) fn is_pressed(input: &BinaryInput, sources: &InputSources<'_, '_>) -> bool {
+        Self::is_held(input, sources, true)
+    }
+    fn is_held(input: &BinaryInput, sources: &InputSources<'_, '_>, skip_consumed: bool) -> bool {
+        let consumed =
+            |input: &BinaryInput| skip_consumed && sources.consumed.binary.contains(input);
+        if consumed(input) {
+            // A context with a higher priority is using this input.
+            return false;
+        }
         match input {
-            BinaryInput::Key(key_code) => sources.input_keycodes.pressed(*key_code),
-            BinaryInput::KeyGroup(group) => group
-                .iter()
-                .any(|key_code| sources.input_keycodes.pressed(*key_code)),
-            BinaryInput::MouseButton(mouse_btn) => sources.input_mouse_btn.pressed(*mouse_btn),
+            BinaryInput::Key(key_code) => {
+                sources.uses_keyboard_mouse() && sources.input_keycodes.pressed(*key_code)
+            }
+            BinaryInput::KeyGroup(group) => {
+                sources.uses_keyboard_mouse()
+                    && group.iter().any(|key_code| {
+                        !consumed(&BinaryInput::Key(*key_code))
+                            && sources.input_keycodes.pressed(*key_code)
+                    })
+            }
+            BinaryInput::MouseButton(mouse_btn) => {
+                sources.uses_keyboard_mouse() && sources.input_mouse_btn.pressed(*mouse_btn)
+            }
             BinaryInput::Gamepad(btn) => {
-                // For now, we don't support local multiplayer. (Will change in the future)
-                // We'll check if the button is active on *any* connected gamepad.
-                sources.gamepads.iter().any(|gamepad| {
+                // We'll check if the button is active on *any* of the assigned gamepads.
+                sources.assigned_gamepads().any(|gamepad| {
                     sources
                         .input_gamepad_btn
                         .pressed(GamepadButton::new(gamepad, *btn))
                 })
             }
             BinaryInput::Axis(input, threshold) => {
-                let value = StatefulAnalogInput::calc_value(input, sources);
+                let value = if skip_consumed {
+                    StatefulAnalogInput::available_value(input, sources)
+                } else {
+                    StatefulAnalogInput::calc_value(input, sources)
+                };
                 threshold.is_reached(value)
             }
         }
     }
 }
 
-fn is_blocked_by(this: &Chord, other: &Chord) -> bool {
+pub(crate) fn is_blocked_by(this: &Chord, other: &Chord) -> bool {
     if this.is_empty() || this.len() >= other.len() {
         // If this is empty, blockers don't matter because it will never activate anyways.
         // Also, other must be longer in order to block this,
@@ -1,3 +1,5 @@
+pub(crate) use input_analog::StatefulAnalogInput;
+
 pub mod axis_dual;
 pub mod axis_single;
 pub mod continuous;
@@ -1,26 +1,49 @@
+use std::time::Duration;
+
 use bevy::log::error;
-use bevy::prelude::Reflect;
+use bevy::prelude::{Reflect, Vec2};
 use bevy::time::Stopwatch;
+use serde::{Deserialize, Serialize};
 
-use crate::bindings::{InputBinding, PulseBinding};
+use crate::bindings::{
+    AnalogInput, Chord, InputBinding, InputDeviceCategory, PulseBinding, SequenceMode,
+    SequenceResetPolicy, SwipeOptions,
+};
 use crate::input_action::InputAction;
 use crate::phantom::{IAWrp, Pulse};
 use crate::processed::bound_action::BoundAction;
 use crate::processed::processor::Helper;
+use crate::processed::stateful::input_analog::StatefulAnalogInput;
 use crate::processed::stateful::input_binary;
 use crate::processed::stateful::input_binary::StatefulBinaryInput;
-use crate::processed::updating::InputSources;
+use crate::processed::updating::{ConsumedInputs, InputSources};
 use crate::reporting::{ActionLocation, InputConfigProblem, InputConfigReport};
 use crate::resources::meta_data::IneffableMetaItem;
 use crate::resources::Ineffable;
 
-#[derive(Debug, Reflect, Clone)]
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) struct StatefulPulseBinding {
     bindings: Vec<StatefulPulseBindingVariant>,
     pub(crate) just_pulsed: bool,
+    /// How many times the binding pulsed this tick. Only `Notches` bindings can pulse more than once per tick.
+    pub(crate) pulse_count: u32,
+    /// Time elapsed since the binding last pulsed. None if it never pulsed.
+    since_last_pulse: Option<Stopwatch>,
+    /// The minimum time between two pulses. Pulses that come sooner are suppressed.
+    cooldown: Duration,
+    /// If true, the post-acceptance delay neither blocks this binding, nor is started by it.
+    ignore_pad: bool,
+}
+
+/// The transient state of a pulse action, without any of its bindings. See `InputSnapshot`.
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
+pub(crate) struct PulseState {
+    just_pulsed: bool,
+    pulse_count: u32,
+    since_last_pulse: Option<Stopwatch>,
 }
 
-#[derive(Debug, Reflect, Clone)]
+#[derive(Debug, Serialize, Deserialize, Reflect, Clone)]
 pub(crate) enum StatefulPulseBindingVariant {
     Dummy,
     JustPressed(StatefulBinaryInput),
@@ -32,16 +55,46 @@ pub(crate) enum StatefulPulseBindingVariant {
     },
     Sequence {
         inputs: Vec<StatefulBinaryInput>,
-        timeout: u128,
+        /// The maximum delay before each step, indexed by the step.
+        /// The first element is never used, because there is no delay before the first step.
+        timeouts: Vec<u128>,
         timer: Stopwatch,
+        /// The minimum hold duration of each step, indexed by the step. Zero means the step is a plain tap.
+        holds: Vec<u128>,
+        /// How long the input of the current step has been held down.
+        held_for: Stopwatch,
         index: usize,
+        /// The step to continue from after the sequence fires.
+        restart_index: usize,
+        mode: SequenceMode,
+    },
+    Notches {
+        input: AnalogInput,
+        /// The signed amount of movement that makes up a single notch. Guaranteed not to be zero.
+        notch: f32,
+        /// Movement in the direction of the notch that did not yet add up to a whole notch. Always between zero and
+        /// one notch.
+        accumulated: f32,
+    },
+    Swipe {
+        options: SwipeOptions,
+        /// How far the pointer travelled since the swipe started.
+        travelled: Vec2,
+        /// How long the current swipe has lasted.
+        timer: Stopwatch,
+        /// True while the finger or mouse button is down.
+        swiping: bool,
     },
+    AnyInput(InputDeviceCategory),
 }
 
 pub(crate) fn bound_action<I: InputAction>(
     ineffable: &Ineffable,
     input_action: IAWrp<I, Pulse>,
 ) -> Option<&StatefulPulseBinding> {
+    if ineffable.is_disabled(I::group_id(), input_action.0.action_id()) {
+        return None;
+    }
     ineffable
         .groups.get(I::group_id())?.get(input_action.0.index())
         .and_then(|bound_action| {
@@ -66,11 +119,12 @@ pub(crate) fn collect<'a>(
         | PulseBinding::DoubleClick(input) => {
             out.push(meta, input.clone());
         }
-        PulseBinding::Sequence(_, inputs) => {
+        PulseBinding::Sequence(_, inputs, _) => {
             for input in inputs {
                 out.push(meta, input.clone());
             }
         }
+        PulseBinding::Notches(_, _) | PulseBinding::Swipe(_) | PulseBinding::AnyInput(_) => (),
     }
 }
 
@@ -92,13 +146,22 @@ pub(crate) fn check_for_problems(
             }
             input_binary::check_for_problems(input, report, loc);
         }
-        PulseBinding::Sequence(millis, inputs) => {
+        PulseBinding::Sequence(millis, inputs, options) => {
             if *millis <= 25 {
                 report.error(InputConfigProblem::SequenceUnrealisticTiming {
                     loc: loc.clone(),
                     actual_millis: *millis as usize,
                 });
             }
+            for (step, millis) in options.step_timeouts.iter().enumerate() {
+                if *millis <= 25 {
+                    report.error(InputConfigProblem::SequenceStepUnrealisticTiming {
+                        loc: loc.clone(),
+                        step,
+                        actual_millis: *millis as usize,
+                    });
+                }
+            }
             if inputs.is_empty() {
                 report.warning(InputConfigProblem::SequenceEmpty { loc: loc.clone() });
             }
@@ -107,19 +170,58 @@ pub(crate) fn check_for_problems(
                     loc: loc.clone(),
                 });
             }
-            if inputs.iter().any(Vec::is_empty) {
-                report.error(InputConfigProblem::ConvolutedDummy {
+            for (step, _) in inputs
+                .iter()
+                .enumerate()
+                .filter(|(_, chord)| chord.is_empty())
+            {
+                report.error(InputConfigProblem::SequenceStepEmpty {
                     loc: loc.clone(),
-                    is_now: format!("{pulse:?}"),
+                    step,
+                });
+            }
+            if inputs.len() > 1
+                && !inputs[0].is_empty()
+                && inputs.iter().all(|chord| *chord == inputs[0])
+            {
+                report.warning(InputConfigProblem::SequenceRepeatsSameChord {
+                    loc: loc.clone(),
+                    repeats: inputs.len(),
                 });
             }
             for child in inputs {
                 input_binary::check_for_problems(child, report, loc);
             }
         }
+        PulseBinding::Notches(_, notch) => {
+            if notch.0.abs() < f32::EPSILON {
+                report.error(InputConfigProblem::NotchSizeIsZero { loc: loc.clone() });
+            }
+        }
+        PulseBinding::Swipe(options) => {
+            if options.min_distance.is_nan() || options.min_distance <= 0. {
+                report.error(InputConfigProblem::SwipeDistanceNotPositive { loc: loc.clone() });
+            }
+            if options.max_duration <= 25 {
+                report.error(InputConfigProblem::SwipeUnrealisticTiming {
+                    loc: loc.clone(),
+                    actual_millis: options.max_duration as usize,
+                });
+            }
+        }
+        PulseBinding::AnyInput(_) => (),
     }
 }
 
+/// Returns the length of the longest part at the end of the sequence, that is also the start of the sequence.
+/// The whole sequence itself doesn't count.
+fn overlap(chords: &[Chord]) -> usize {
+    (1..chords.len())
+        .rev()
+        .find(|len| chords[..*len] == chords[chords.len() - len..])
+        .unwrap_or(0)
+}
+
 impl StatefulPulseBinding {
     pub(crate) fn new_from_vec(data: &[InputBinding], helper: &Helper<'_>) -> StatefulPulseBinding {
         let stateful_bindings = data
@@ -136,6 +238,10 @@ impl StatefulPulseBinding {
         StatefulPulseBinding {
             bindings: stateful_bindings,
             just_pulsed: false,
+            pulse_count: 0,
+            since_last_pulse: None,
+            cooldown: helper.cooldown,
+            ignore_pad: helper.ignore_pad,
         }
     }
     pub(crate) fn new_from_single(
@@ -145,6 +251,10 @@ impl StatefulPulseBinding {
         Self {
             bindings: vec![Self::process(value, helper)],
             just_pulsed: false,
+            pulse_count: 0,
+            since_last_pulse: None,
+            cooldown: helper.cooldown,
+            ignore_pad: helper.ignore_pad,
         }
     }
     fn process(binding: &PulseBinding, helper: &Helper<'_>) -> StatefulPulseBindingVariant {
@@ -161,81 +271,374 @@ impl StatefulPulseBinding {
                 timer: Stopwatch::default(),
                 index: 0,
             },
-            PulseBinding::Sequence(timeout, inputs) => StatefulPulseBindingVariant::Sequence {
-                inputs: inputs
-                    .iter()
-                    .map(|input| StatefulBinaryInput::new(input, helper))
-                    .collect(),
-                timeout: u128::from(*timeout),
+            PulseBinding::Sequence(timeout, inputs, options) => {
+                StatefulPulseBindingVariant::Sequence {
+                    inputs: inputs
+                        .iter()
+                        .map(|input| StatefulBinaryInput::new(input, helper))
+                        .collect(),
+                    timeouts: (0..inputs.len())
+                        .map(|step| {
+                            let millis = step
+                                .checked_sub(1)
+                                .and_then(|previous| options.step_timeouts.get(previous))
+                                .unwrap_or(timeout);
+                            u128::from(*millis)
+                        })
+                        .collect(),
+                    timer: Stopwatch::default(),
+                    holds: (0..inputs.len())
+                        .map(|step| u128::from(options.step_holds.get(step).copied().unwrap_or(0)))
+                        .collect(),
+                    held_for: Stopwatch::default(),
+                    index: 0,
+                    restart_index: match options.reset_policy {
+                        SequenceResetPolicy::FullReset => 0,
+                        SequenceResetPolicy::SlidingWindow => overlap(inputs),
+                    },
+                    mode: options.mode,
+                }
+            }
+            // A notch of zero size would pulse infinitely often, so such a binding never pulses instead.
+            PulseBinding::Notches(_, notch) if notch.0.abs() < f32::EPSILON => {
+                StatefulPulseBindingVariant::Dummy
+            }
+            PulseBinding::Notches(input, notch) => StatefulPulseBindingVariant::Notches {
+                input: input.clone(),
+                notch: notch.0,
+                accumulated: 0.,
+            },
+            PulseBinding::AnyInput(category) => StatefulPulseBindingVariant::AnyInput(*category),
+            PulseBinding::Swipe(options) => StatefulPulseBindingVariant::Swipe {
+                options: options.clone(),
+                travelled: Vec2::ZERO,
                 timer: Stopwatch::default(),
-                index: 0,
+                swiping: false,
             },
         }
     }
 
-    pub(crate) fn update(&mut self, sources: &mut InputSources<'_>) {
-        self.just_pulsed =
-            self.bindings
-                .iter_mut()
-                .fold(false, |activated, binding| match binding {
-                    StatefulPulseBindingVariant::Dummy => false,
-                    StatefulPulseBindingVariant::JustPressed(input) => {
-                        input.update(sources);
-                        activated || input.just_pressed()
-                    }
-                    StatefulPulseBindingVariant::JustReleased(input) => {
-                        input.update(sources);
-                        activated || input.just_released()
-                    }
-                    StatefulPulseBindingVariant::DoubleClick {
-                        input,
-                        timer,
-                        index,
-                    } => {
-                        input.update(sources);
-                        let advance = input.just_pressed();
-                        let timed_out = timer.elapsed() > sources.settings.double_click_timing;
-                        if timed_out {
-                            timer.reset();
-                            *index = 0;
-                        } else if advance && 2 <= *index + 1 {
-                            timer.reset();
-                            *index = 0;
-                            return true;
-                        } else if advance {
-                            timer.reset();
-                            *index += 1;
-                        } else if *index > 0 {
-                            timer.tick(sources.time.delta());
-                        }
-                        activated
+    /// Returns the progress through the sequence-like binding that is furthest along, as (current step, total steps).
+    /// Returns None if there are no sequence-like bindings.
+    pub(crate) fn sequence_progress(&self) -> Option<(usize, usize)> {
+        self.bindings
+            .iter()
+            .filter_map(|binding| match binding {
+                StatefulPulseBindingVariant::DoubleClick { index, .. } => Some((*index, 2)),
+                StatefulPulseBindingVariant::Sequence { inputs, index, .. } => {
+                    Some((*index, inputs.len()))
+                }
+                _ => None,
+            })
+            .reduce(|furthest, next| {
+                // Compare the fractions without dividing: a/b < c/d <=> a*d < c*b
+                if furthest.0 * next.1 < next.0 * furthest.1 {
+                    next
+                } else {
+                    furthest
+                }
+            })
+    }
+
+    /// Returns the time since the binding last pulsed, or None if it never pulsed.
+    /// This is zero on the tick that the binding pulses.
+    pub(crate) fn time_since_pulse(&self) -> Option<Duration> {
+        self.since_last_pulse.as_ref().map(Stopwatch::elapsed)
+    }
+
+    /// Swipe bindings follow the pointer rather than any individual input, so they don't consume anything.
+    pub(crate) fn consume(&self, sources: &InputSources<'_, '_>, consumed: &mut ConsumedInputs) {
+        for binding in &self.bindings {
+            match binding {
+                StatefulPulseBindingVariant::Dummy
+                | StatefulPulseBindingVariant::Swipe { .. }
+                | StatefulPulseBindingVariant::AnyInput(_) => {}
+                StatefulPulseBindingVariant::JustPressed(input)
+                | StatefulPulseBindingVariant::JustReleased(input)
+                | StatefulPulseBindingVariant::DoubleClick { input, .. } => {
+                    input.consume(sources, consumed);
+                }
+                StatefulPulseBindingVariant::Sequence { inputs, .. } => {
+                    inputs
+                        .iter()
+                        .for_each(|input| input.consume(sources, consumed));
+                }
+                StatefulPulseBindingVariant::Notches { input, .. } => {
+                    if StatefulAnalogInput::available_value(input, sources).abs() > f32::EPSILON {
+                        consumed.analog.push(input.clone());
                     }
-                    StatefulPulseBindingVariant::Sequence {
-                        inputs,
-                        timeout,
-                        timer,
-                        index,
-                    } => {
-                        inputs.iter_mut().for_each(|input| input.update(sources));
-                        let advance = inputs
-                            .get(*index)
-                            .is_some_and(StatefulBinaryInput::just_pressed);
-                        let timed_out = timer.elapsed().as_millis() > *timeout;
-                        if timed_out {
-                            timer.reset();
-                            *index = 0;
-                        } else if advance && inputs.len() <= *index + 1 {
-                            timer.reset();
-                            *index = 0;
-                            return true;
-                        } else if advance {
-                            timer.reset();
-                            *index += 1;
-                        } else if *index > 0 {
-                            timer.tick(sources.time.delta());
-                        }
-                        activated
+                }
+            }
+        }
+    }
+
+    pub(crate) fn reset(&mut self) {
+        for binding in &mut self.bindings {
+            binding.reset();
+        }
+        self.just_pulsed = false;
+        self.pulse_count = 0;
+        self.since_last_pulse = None;
+    }
+    pub(crate) fn state(&self) -> PulseState {
+        PulseState {
+            just_pulsed: self.just_pulsed,
+            pulse_count: self.pulse_count,
+            since_last_pulse: self.since_last_pulse.clone(),
+        }
+    }
+    pub(crate) fn restore(&mut self, state: &PulseState) {
+        self.just_pulsed = state.just_pulsed;
+        self.pulse_count = state.pulse_count;
+        self.since_last_pulse.clone_from(&state.since_last_pulse);
+    }
+
+    /// Pulses once more this tick, regardless of the bindings and the cooldown.
+    pub(crate) fn pulse(&mut self) {
+        self.pulse_count += 1;
+        self.just_pulsed = true;
+        self.since_last_pulse = Some(Stopwatch::default());
+    }
+
+    pub(crate) fn update(&mut self, sources: &InputSources<'_, '_>) {
+        let mut notches = 0;
+        let mut pulsed = false;
+        for binding in &mut self.bindings {
+            let count = binding.update(sources, self.ignore_pad);
+            if matches!(binding, StatefulPulseBindingVariant::Notches { .. }) {
+                notches += count;
+            } else {
+                pulsed |= count > 0;
+            }
+        }
+        // Bindings combine with OR semantics: when several of them fire on the same tick, such as a single press and
+        // a sequence that ends with that same press, that only counts as one pulse. Notches are the exception.
+        self.pulse_count = notches + u32::from(pulsed);
+        if let Some(stopwatch) = &mut self.since_last_pulse {
+            stopwatch.tick(sources.time.delta());
+        }
+        if !self.cooldown.is_zero() {
+            let cooling_down = self
+                .since_last_pulse
+                .as_ref()
+                .is_some_and(|stopwatch| stopwatch.elapsed() < self.cooldown);
+            // Unlike auto-fire, a cooldown never pulses by itself, and never more than once at a time.
+            self.pulse_count = if cooling_down {
+                0
+            } else {
+                self.pulse_count.min(1)
+            };
+        }
+        self.just_pulsed = self.pulse_count > 0;
+        if self.just_pulsed {
+            self.since_last_pulse = Some(Stopwatch::default());
+        }
+    }
+}
+
+impl StatefulPulseBindingVariant {
+    fn reset(&mut self) {
+        match self {
+            StatefulPulseBindingVariant::Dummy
+            | StatefulPulseBindingVariant::JustPressed(_)
+            | StatefulPulseBindingVariant::JustReleased(_)
+            | StatefulPulseBindingVariant::AnyInput(_) => {}
+            StatefulPulseBindingVariant::DoubleClick { timer, index, .. } => {
+                timer.reset();
+                *index = 0;
+            }
+            StatefulPulseBindingVariant::Sequence {
+                timer,
+                held_for,
+                index,
+                ..
+            } => {
+                timer.reset();
+                held_for.reset();
+                *index = 0;
+            }
+            StatefulPulseBindingVariant::Notches { accumulated, .. } => *accumulated = 0.,
+            StatefulPulseBindingVariant::Swipe {
+                travelled,
+                timer,
+                swiping,
+                ..
+            } => {
+                *travelled = Vec2::ZERO;
+                timer.reset();
+                *swiping = false;
+            }
+        }
+    }
+
+    /// Updates the binding, and returns how many times it pulsed this tick.
+    fn update(&mut self, sources: &InputSources<'_, '_>, ignore_pad: bool) -> u32 {
+        let blocked_by_pad = !ignore_pad && sources.settings.input_blocked_by_pad();
+        match self {
+            StatefulPulseBindingVariant::Dummy => 0,
+            StatefulPulseBindingVariant::AnyInput(category) => {
+                // If the post-acceptance-delay is active, then do nothing. We should ignore all user input.
+                if blocked_by_pad || !sources.any_just_pressed(*category) {
+                    return 0;
+                }
+                if !ignore_pad {
+                    sources.input_detected();
+                }
+                1
+            }
+            StatefulPulseBindingVariant::JustPressed(input) => {
+                input.update(sources);
+                u32::from(input.just_pressed())
+            }
+            StatefulPulseBindingVariant::JustReleased(input) => {
+                input.update(sources);
+                u32::from(input.just_released())
+            }
+            StatefulPulseBindingVariant::DoubleClick {
+                input,
+                timer,
+                index,
+            } => {
+                input.update(sources);
+                let advance = input.just_pressed();
+                let timed_out = timer.elapsed() > sources.settings.double_click_timing;
+                if timed_out {
+                    timer.reset();
+                    *index = 0;
+                } else if advance && 2 <= *index + 1 {
+                    timer.reset();
+                    *index = 0;
+                    return 1;
+                } else if advance {
+                    timer.reset();
+                    *index += 1;
+                } else if *index > 0 {
+                    timer.tick(sources.time.delta());
+                }
+                0
+            }
+            StatefulPulseBindingVariant::Sequence {
+                inputs,
+                timeouts,
+                timer,
+                holds,
+                held_for,
+                index,
+                restart_index,
+                mode,
+            } => {
+                inputs.iter_mut().for_each(|input| input.update(sources));
+                let current = inputs.get(*index);
+                let hold = holds.get(*index).copied().unwrap_or(0);
+                // A step that must be held advances on the tick that it has been held long enough.
+                let holding = hold > 0 && current.is_some_and(StatefulBinaryInput::is_active);
+                if holding {
+                    held_for.tick(sources.time.delta());
+                } else {
+                    held_for.reset();
+                }
+                let advance = if hold > 0 {
+                    holding && held_for.elapsed().as_millis() >= hold
+                } else {
+                    current.is_some_and(StatefulBinaryInput::just_pressed)
+                };
+                let wrong_input = !advance
+                    && *mode == SequenceMode::Strict
+                    && !current.is_some_and(StatefulBinaryInput::just_pressed)
+                    && inputs.iter().any(StatefulBinaryInput::just_pressed);
+                let timed_out = timeouts
+                    .get(*index)
+                    .is_some_and(|timeout| timer.elapsed().as_millis() > *timeout);
+                if timed_out {
+                    timer.reset();
+                    held_for.reset();
+                    *index = 0;
+                } else if wrong_input {
+                    // In strict mode, a wrong input resets progress.
+                    // It may however be the first step of a new attempt.
+                    timer.reset();
+                    held_for.reset();
+                    *index = usize::from(
+                        holds.first().is_some_and(|hold| *hold == 0)
+                            && inputs
+                                .first()
+                                .is_some_and(StatefulBinaryInput::just_pressed),
+                    );
+                } else if advance && inputs.len() <= *index + 1 {
+                    timer.reset();
+                    held_for.reset();
+                    *index = *restart_index;
+                    return 1;
+                } else if advance {
+                    timer.reset();
+                    held_for.reset();
+                    *index += 1;
+                } else if *index > 0 && !holding {
+                    // The maximum delay applies to the start of a hold, not its completion.
+                    timer.tick(sources.time.delta());
+                }
+                0
+            }
+            StatefulPulseBindingVariant::Notches {
+                input,
+                notch,
+                accumulated,
+            } => {
+                // If the post-acceptance-delay is active, then do nothing. We should ignore all user input.
+                if blocked_by_pad {
+                    return 0;
+                }
+                let value = StatefulAnalogInput::available_value(input, sources);
+                if value * *accumulated < 0. {
+                    // Changing direction discards any partial notch.
+                    *accumulated = 0.;
+                }
+                // Measured in notches, movement against the direction of the notch is discarded, and whatever
+                // remains after the whole notches is kept. This way, rounding errors can't push the accumulator
+                // past either end and accidentally discard a partial notch on the next tick.
+                let progress = ((*accumulated + value) / *notch).max(0.);
+                let notches = progress.floor();
+                *accumulated = (progress - notches) * *notch;
+                notches as u32
+            }
+            StatefulPulseBindingVariant::Swipe {
+                options,
+                travelled,
+                timer,
+                swiping,
+            } => {
+                // If the post-acceptance-delay is active, then do nothing. We should ignore all user input.
+                if blocked_by_pad {
+                    *swiping = false;
+                    return 0;
+                }
+                if let Some(movement) = sources.pointer_movement() {
+                    if !*swiping {
+                        *swiping = true;
+                        *travelled = Vec2::ZERO;
+                        timer.reset();
+                    } else {
+                        timer.tick(sources.time.delta());
                     }
-                });
+                    *travelled += movement;
+                    return 0;
+                }
+                if !*swiping {
+                    return 0;
+                }
+                // The finger or mouse button was released, so the swipe is complete.
+                *swiping = false;
+                let direction = options.direction.as_vec2();
+                let along = travelled.dot(direction);
+                let sideways = travelled.perp_dot(direction).abs();
+                let swiped = along >= options.min_distance
+                    && along > sideways
+                    && timer.elapsed().as_millis() <= u128::from(options.max_duration);
+                if swiped && !ignore_pad {
+                    sources.input_detected();
+                }
+                u32::from(swiped)
+            }
+        }
     }
 }
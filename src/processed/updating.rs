@@ -1,14 +1,45 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
 use bevy::ecs::system::SystemParam;
 use bevy::log::{error, info, warn};
 use bevy::prelude::*;
+use bevy::tasks::{ComputeTaskPool, ParallelSliceMut, TaskPool};
+use bevy::utils::{HashMap, HashSet};
 
+use crate::bindings::{AnalogInput, BinaryInput, InputDeviceCategory};
+use crate::components::{IneffableInput, InputDevices};
+use crate::processed::bound_action::BoundAction;
+use crate::processed::processor::process_config;
+use crate::processed::stateful::input_binary::StatefulBinaryInput;
+use crate::processed::stateful::StatefulAnalogInput;
 use crate::resources::ineffable_settings::IneffableSettings;
+use crate::resources::meta_data::IneffableMetaData;
 use crate::resources::sources::IneffableEventSources;
 use crate::resources::Ineffable;
 
+/// All the input state that ineffable reads from when updating `InputAction`s.
+///
+/// You can use this as a system parameter to evaluate inputs against the current input state, using the same
+/// matching logic that ineffable uses internally. This is useful for custom tooling, like a live debugger that shows
+/// which inputs are pressed.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::bindings::BinaryInput;
+/// # use bevy_ineffable::processed::updating::InputSources;
+/// fn debug_save_chord(sources: InputSources) {
+///     let save = [BinaryInput::Key(KeyCode::ControlLeft), BinaryInput::Key(KeyCode::KeyS)];
+///     if sources.is_chord_pressed(&save) {
+///         info!("Ctrl-S is held down");
+///     }
+/// }
+/// ```
+#[allow(missing_debug_implementations)]
 #[derive(SystemParam)]
-pub(crate) struct InputSources<'w> {
-    pub(crate) settings: ResMut<'w, IneffableSettings>,
+pub struct InputSources<'w, 's> {
+    pub(crate) settings: Res<'w, IneffableSettings>,
     pub(crate) time: Res<'w, Time>,
     pub(crate) from_events: Res<'w, IneffableEventSources>,
     pub(crate) gamepads: Res<'w, Gamepads>,
@@ -17,24 +48,293 @@ pub(crate) struct InputSources<'w> {
     pub(crate) input_gamepad_btn: Res<'w, ButtonInput<GamepadButton>>,
     pub(crate) axis_gamepad_btn: Res<'w, Axis<GamepadButton>>,
     pub(crate) axis_gamepad_axis: Res<'w, Axis<GamepadAxis>>,
+    /// The input devices that are currently being listened to. Differs per `IneffableInput` component.
+    pub(crate) devices: Local<'s, InputDevices>,
+    /// Inputs that are used by a context with a higher priority. They count as not pressed.
+    pub(crate) consumed: Local<'s, ConsumedInputs>,
+    /// Set when any action accepts new user input. Actions may be updated in parallel, so this is collected here and
+    /// passed on to the post-acceptance-delay once all actions are updated.
+    pub(crate) detected: Local<'s, AtomicBool>,
+}
+
+/// The inputs that the contexts on the stack are using, and that the contexts below them can't use.
+///
+/// Keys from a `KeyGroup` are resolved to the keys that are actually held down, so binding a group in one context and
+/// one of its keys in another works as expected.
+#[derive(Debug, Default)]
+pub(crate) struct ConsumedInputs {
+    pub(crate) binary: Vec<BinaryInput>,
+    /// Analog inputs count as consumed while they are moving. Consumed analog inputs have a value of zero.
+    pub(crate) analog: Vec<AnalogInput>,
+}
+
+impl ConsumedInputs {
+    fn clear(&mut self) {
+        self.binary.clear();
+        self.analog.clear();
+    }
+    fn append(&mut self, other: &mut ConsumedInputs) {
+        self.binary.append(&mut other.binary);
+        self.analog.append(&mut other.analog);
+    }
+}
+
+impl InputSources<'_, '_> {
+    /// Returns true iff the given input is currently pressed.
+    ///
+    /// Gamepad buttons are checked on all connected gamepads. Analog inputs with a threshold are pressed when their
+    /// current value reaches the threshold.
+    #[must_use]
+    pub fn is_pressed(&self, input: &BinaryInput) -> bool {
+        StatefulBinaryInput::is_pressed(input, self)
+    }
+
+    /// Returns true iff all inputs in the given chord are currently pressed. An empty chord is never pressed.
+    ///
+    /// Note that this does not take chord blocking into account: this is only about the raw input state.
+    #[must_use]
+    pub fn is_chord_pressed(&self, chord: &[BinaryInput]) -> bool {
+        !chord.is_empty() && chord.iter().all(|input| self.is_pressed(input))
+    }
+
+    /// Returns the current value of the given analog input, before the settings of any binding are applied, like
+    /// calibration, sensitivity or inversion. The input doesn't need to be bound to any action.
+    ///
+    /// This is useful for calibration screens that need to know the extents of the hardware. The mouse sensitivity
+    /// of the config isn't applied either. Note that bevy itself may still apply dead zones to gamepad axes, as
+    /// configured in its `GamepadSettings` resource.
+    ///
+    /// For gamepads, the value of the first connected gamepad that has the axis is returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::processed::updating::InputSources;
+    /// fn calibrate(sources: InputSources) {
+    ///     let trigger = sources.analog_value(&AnalogInput::GamePadLeftTrigger2);
+    ///     info!("The left trigger is at {trigger}");
+    /// }
+    /// ```
+    #[must_use]
+    pub fn analog_value(&self, input: &AnalogInput) -> f32 {
+        match input {
+            AnalogInput::MouseMotionX if self.uses_keyboard_mouse() => {
+                self.from_events.mouse_motion.x
+            }
+            AnalogInput::MouseMotionY if self.uses_keyboard_mouse() => {
+                self.from_events.mouse_motion.y
+            }
+            _ => StatefulAnalogInput::calc_value(input, self),
+        }
+    }
+
+    /// Returns the current value of the given axis on one specific gamepad, before any of ineffable's processing.
+    /// Unlike `analog_value()`, this doesn't fall back to other gamepads, so it can show the sticks of every connected
+    /// gamepad separately, for example in a diagnostics screen or while players pick their controllers.
+    ///
+    /// Returns zero if the gamepad is not connected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::processed::updating::InputSources;
+    /// fn show_sticks(gamepads: Res<Gamepads>, sources: InputSources) {
+    ///     for gamepad in gamepads.iter() {
+    ///         let x = sources.gamepad_axis(gamepad, GamepadAxisType::LeftStickX);
+    ///         info!("Gamepad {}: {x}", gamepad.id);
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn gamepad_axis(&self, gamepad: Gamepad, axis: GamepadAxisType) -> f32 {
+        if !self.gamepads.contains(gamepad) {
+            return 0.;
+        }
+        self.axis_gamepad_axis
+            .get(GamepadAxis::new(gamepad, axis))
+            .unwrap_or_default()
+    }
+
+    /// Returns how far the pointer moved this tick, while a finger is on the touch screen or the left mouse button
+    /// is held down. Returns None if neither is the case.
+    pub(crate) fn pointer_movement(&self) -> Option<Vec2> {
+        if self.from_events.touch_motion.is_some() {
+            return self.from_events.touch_motion;
+        }
+        (self.uses_keyboard_mouse() && self.input_mouse_btn.pressed(MouseButton::Left))
+            .then_some(self.from_events.mouse_motion)
+    }
+
+    /// Announces that user input was detected, which starts the post-acceptance-delay.
+    pub(crate) fn input_detected(&self) {
+        self.detected.store(true, Ordering::Relaxed);
+    }
+
+    /// True iff the keyboard and mouse are currently being listened to.
+    pub(crate) fn uses_keyboard_mouse(&self) -> bool {
+        self.devices.keyboard_mouse
+    }
+
+    /// True iff any button of the given category was newly pressed this tick, on a device that is being listened to.
+    /// Buttons that are consumed by a context higher on the stack don't count.
+    pub(crate) fn any_just_pressed(&self, category: InputDeviceCategory) -> bool {
+        let available = |input: BinaryInput| !self.consumed.binary.contains(&input);
+        let keyboard = || {
+            self.uses_keyboard_mouse()
+                && self
+                    .input_keycodes
+                    .get_just_pressed()
+                    .any(|key| available(BinaryInput::Key(*key)))
+        };
+        let mouse = || {
+            self.uses_keyboard_mouse()
+                && self
+                    .input_mouse_btn
+                    .get_just_pressed()
+                    .any(|button| available(BinaryInput::MouseButton(*button)))
+        };
+        let gamepad = || {
+            self.input_gamepad_btn.get_just_pressed().any(|button| {
+                self.devices.uses_gamepad(button.gamepad)
+                    && available(BinaryInput::Gamepad(button.button_type))
+            })
+        };
+        match category {
+            InputDeviceCategory::All => keyboard() || mouse() || gamepad(),
+            InputDeviceCategory::Keyboard => keyboard(),
+            InputDeviceCategory::Mouse => mouse(),
+            InputDeviceCategory::Gamepad => gamepad(),
+        }
+    }
+
+    /// Iterates over all connected gamepads that are currently being listened to.
+    pub(crate) fn assigned_gamepads(&self) -> impl Iterator<Item = Gamepad> + '_ {
+        self.gamepads
+            .iter()
+            .filter(|gamepad| self.devices.uses_gamepad(*gamepad))
+    }
 }
 
 #[allow(clippy::needless_pass_by_value)] // That's just a bevy thing.
-pub(crate) fn update_input(mut bindings: ResMut<'_, Ineffable>, mut sources: InputSources<'_>) {
-    bindings
-        .groups
+pub(crate) fn update_input(
+    mut bindings: ResMut<'_, Ineffable>,
+    mut players: Query<'_, '_, &mut IneffableInput>,
+    meta_data: Res<'_, IneffableMetaData>,
+    time: Res<'_, Time>,
+    // `InputSources` only reads the settings, so the post-acceptance-delay is ticked separately, afterwards.
+    mut params: ParamSet<'_, '_, (InputSources<'_, '_>, ResMut<'_, IneffableSettings>)>,
+) {
+    let mut sources = params.p0();
+    *sources.devices = InputDevices::default();
+    update_layers(&mut bindings, &mut sources);
+    for mut player in &mut players {
+        if !player.initialised {
+            // This component was added after the config was set, so it has no bindings yet.
+            if let Some(config) = &bindings.current_config {
+                player.state.groups = process_config(&meta_data, config);
+                player.state.action_indices = meta_data.action_indices();
+            }
+            player.initialised = true;
+        }
+        *sources.devices = player.devices.clone();
+        update_layers(&mut player.state, &mut sources);
+    }
+    let detected = sources.detected.swap(false, Ordering::Relaxed);
+    if let Some(pad) = &mut params.p1().post_acceptance_delay {
+        if detected {
+            pad.input_detected();
+        }
+        pad.tick(time);
+    }
+}
+
+/// Updates all groups, starting with the context on top of the stack. Inputs that are used by a context are consumed,
+/// so the contexts below it see them as not pressed. Groups without a context are updated last.
+fn update_layers(state: &mut Ineffable, sources: &mut InputSources<'_, '_>) {
+    sources.consumed.clear();
+    let mut updated: Vec<&str> = Vec::new();
+    for context in state.contexts.iter().rev() {
+        let layer: Vec<&str> = context
+            .groups
+            .iter()
+            .map(String::as_str)
+            .filter(|group_id| !updated.contains(group_id))
+            .collect();
+        updated.extend(&layer);
+        let mut bound_actions = enabled_actions(
+            &mut state.groups,
+            &state.action_indices,
+            &state.disabled_actions,
+            |group_id| layer.contains(&group_id),
+        );
+        update_all(&mut bound_actions, sources);
+        let mut consumed = ConsumedInputs::default();
+        for bound_action in &bound_actions {
+            bound_action.consume(sources, &mut consumed);
+        }
+        sources.consumed.append(&mut consumed);
+    }
+    let mut bound_actions = enabled_actions(
+        &mut state.groups,
+        &state.action_indices,
+        &state.disabled_actions,
+        |group_id| !updated.contains(&group_id),
+    );
+    update_all(&mut bound_actions, sources);
+}
+
+/// Collects the actions in the groups that match the filter. Disabled actions are left out: they are not updated, so
+/// they don't consume inputs or start the post-acceptance-delay.
+fn enabled_actions<'a>(
+    groups: &'a mut HashMap<String, Vec<BoundAction>>,
+    action_indices: &HashMap<String, HashMap<String, usize>>,
+    disabled_actions: &HashMap<String, HashSet<String>>,
+    filter: impl Fn(&str) -> bool,
+) -> Vec<&'a mut BoundAction> {
+    groups
         .iter_mut()
-        .flat_map(|(_, group)| group.iter_mut())
-        .for_each(|bound_action| {
-            bound_action.update(&mut sources);
-        });
-    if let Some(pad) = &mut sources.settings.post_acceptance_delay {
-        pad.tick(sources.time);
+        .filter(|(group_id, _)| filter(group_id))
+        .flat_map(|(group_id, group)| {
+            let disabled: Vec<usize> = disabled_actions
+                .get(group_id)
+                .into_iter()
+                .flatten()
+                .filter_map(|action_id| action_indices.get(group_id)?.get(action_id).copied())
+                .collect();
+            group
+                .iter_mut()
+                .enumerate()
+                .filter(move |(index, _)| !disabled.contains(index))
+                .map(|(_, bound_action)| bound_action)
+        })
+        .collect()
+}
+
+/// Updates the given actions. Each action only reads from the shared input sources and writes to its own state, so
+/// if there are enough of them to be worth the overhead, they are spread out over the `ComputeTaskPool`.
+fn update_all(mut bound_actions: &mut [&mut BoundAction], sources: &InputSources<'_, '_>) {
+    if bound_actions.len() < sources.settings.parallel_threshold {
+        for bound_action in bound_actions {
+            bound_action.update(sources);
+        }
+        return;
     }
+    bound_actions.par_splat_map_mut(
+        ComputeTaskPool::get_or_init(TaskPool::default),
+        None,
+        |_, chunk| {
+            for bound_action in chunk {
+                bound_action.update(sources);
+            }
+        },
+    );
 }
 
 // TODO: Remove.
-pub(crate) fn _peek_at_input(sources: InputSources<'_>) {
+pub(crate) fn _peek_at_input(sources: InputSources<'_, '_>) {
     for btn in sources.input_mouse_btn.get_just_pressed() {
         warn!("JustPressed: {:?}", btn);
     }
@@ -1,13 +1,28 @@
 //! Handles registering `InputAction`s. This should be done once per `InputAction` enum at the start of the game.
 
-use bevy::app::App;
+use bevy::app::{App, PreUpdate};
 use bevy::log::{error, warn};
+use bevy::prelude::{IntoSystemConfigs, Res};
 
-use crate::input_action::InputAction;
+use crate::input_action::{InputAction, InputKind};
+use crate::phantom::{IAWrp, Pulse};
+use crate::processed::updating::update_input;
 use crate::resources::meta_data::{IneffableMetaData, IneffableMetaItem};
+use crate::resources::Ineffable;
 
 pub trait InputActionRegistrar {
     fn register_input_action<I: InputAction>(&mut self) -> &mut Self;
+    fn register_dynamic_action(
+        &mut self,
+        group_id: impl Into<String>,
+        action_id: impl Into<String>,
+        kind: InputKind,
+    ) -> &mut Self;
+    fn on_pulse<I: InputAction, M>(
+        &mut self,
+        action: IAWrp<I, Pulse>,
+        system: impl IntoSystemConfigs<M>,
+    ) -> &mut Self;
 }
 
 impl InputActionRegistrar for App {
@@ -16,6 +31,9 @@ impl InputActionRegistrar for App {
     /// Ineffable needs this to perform various compile-time and runtime checks.
     /// It is not possible to use any InputAction that was not registered through this function first.
     ///
+    /// Every enum must have a unique name, even if they live in different modules. Registering two different enums
+    /// with the same name logs an error that lists the actions of both.
+    ///
     /// # Examples
     /// ```
     /// # use bevy::prelude::App;
@@ -33,29 +51,158 @@ impl InputActionRegistrar for App {
             .world_mut()
             .get_resource_mut::<IneffableMetaData>()
             .expect("Missing resource IneffableMetaData. Try adding the IneffablePlugin first.");
+        let type_name = std::any::type_name::<I>();
         if let Some(previously_registered_group) = resource.group(I::group_id()) {
-            let conflicting_group = construct_variants_meta_data::<I>();
-            if &conflicting_group == previously_registered_group {
+            match resource.type_name(I::group_id()) {
+                Some(previous_type_name) if previous_type_name == type_name => {
+                    warn!(
+                        "Tried to register an InputAction more than once. \
+                    You can safely remove the redundant call to `app.register_input_action::<{}>()`",
+                        I::group_id()
+                    );
+                }
+                previous_type_name => {
+                    error!(
+                        "Tried to register two different InputActions with the same name: `{}`.\n\
+                    \tEach InputAction enum must have a unique name.\n\
+                    \tThis is almost certainly a bug, and may result in input not being read properly.\n\
+                    \tPreviously registered by {}: {}\n\
+                    \tConflicting enum {type_name}: {}",
+                        I::group_id(),
+                        previous_type_name.unwrap_or("dynamic actions"),
+                        describe_actions(previously_registered_group),
+                        describe_actions(&construct_variants_meta_data::<I>()),
+                    );
+                }
+            }
+            return self;
+        }
+        resource
+            .type_names
+            .insert(I::group_id().to_string(), type_name);
+        resource.map.insert(
+            I::group_id().to_string(),
+            construct_variants_meta_data::<I>(),
+        );
+        self
+    }
+
+    /// Registers a single action that is not known at compile time, such as an ability that was defined by a mod.
+    ///
+    /// Dynamic actions are bound and queried by their group_id and action_id, rather than through the `ineff!()`
+    /// macro. There is no compile-time guarantee that they exist or that they are of the right `InputKind`, so
+    /// prefer deriving `InputAction` whenever the actions are known in advance.
+    ///
+    /// Multiple dynamic actions can share the same group_id. The group_id of an `InputAction` enum can't be used:
+    /// trying to do so logs an error, and the action is not registered.
+    ///
+    /// The `IneffablePlugin` must be added first, otherwise this panics.
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// let mut app = App::new();
+    /// # app.add_plugins((MinimalPlugins, AssetPlugin::default()));
+    /// app.add_plugins(IneffablePlugin)
+    ///     .register_dynamic_action("ModAbilities", "Fireball", InputKind::Pulse);
+    /// let config = InputConfig::builder()
+    ///     .bind_dynamic("ModAbilities", "Fireball", PulseBinding::just_pressed(KeyCode::KeyF))
+    ///     .build();
+    ///
+    /// fn cast_spells(ineffable: Res<Ineffable>) {
+    ///     if ineffable.just_pulsed_by_id("ModAbilities", "Fireball") == Some(true) {
+    ///         // Cast the fireball.
+    ///     }
+    /// }
+    /// ```
+    fn register_dynamic_action(
+        &mut self,
+        group_id: impl Into<String>,
+        action_id: impl Into<String>,
+        kind: InputKind,
+    ) -> &mut Self {
+        let mut resource = self
+            .world_mut()
+            .get_resource_mut::<IneffableMetaData>()
+            .expect("Missing resource IneffableMetaData. Try adding the IneffablePlugin first.");
+        let group_id = group_id.into();
+        let action_id = action_id.into();
+        if let Some(type_name) = resource.type_name(&group_id) {
+            error!(
+                "Tried to register the dynamic action `{group_id}::{action_id}`, \
+            but the group `{group_id}` belongs to the InputAction enum {type_name}.\n\
+            \tDynamic actions can't be added to an enum. Use a different group_id for them."
+            );
+            return self;
+        }
+        if let Some(previously_registered) = resource.action(&group_id, &action_id) {
+            if previously_registered.kind == kind {
                 warn!(
-                    "Tried to register an InputAction more than once. \
-                You can safely remove the redundant call to `app.register_input_action::<{}>()`",
-                    I::group_id()
+                    "Tried to register the dynamic action `{group_id}::{action_id}` more than once. \
+                You can safely remove the redundant call to `app.register_dynamic_action()`"
                 );
             } else {
                 error!(
-                    "Tried to register two different InputActions with the same name: `{}`.\n\
-                \tEach InputAction enum must have a unique name.\n\
-                \tThis is almost certainly a bug, and may result in input not being read properly.",
-                    I::group_id()
+                    "Tried to register the dynamic action `{group_id}::{action_id}` as a {kind:?} action, \
+                but it was already registered as a {:?} action.\n\
+                \tEach action must have a unique name within its group.",
+                    previously_registered.kind
                 );
             }
             return self;
         }
-        resource
-            .map
-            .insert(I::group_id(), construct_variants_meta_data::<I>());
+        let group = resource.map.entry(group_id.clone()).or_default();
+        group.push(IneffableMetaItem {
+            label: action_id.clone(),
+            category: group_id.clone(),
+            group_id,
+            action_id,
+            kind,
+            index: group.len(),
+        });
         self
     }
+
+    /// Runs the given system on every tick that the pulse action pulses. This is a shortcut for wiring an action to
+    /// a system, without having to check `Ineffable::just_pulsed()` in the system itself.
+    ///
+    /// The system runs in the `PreUpdate` schedule, after ineffable has updated its input state for this tick.
+    /// Only the global `Ineffable` resource is checked, not the `IneffableInput` components of individual players.
+    ///
+    /// # Examples
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum MenuInput {
+    ///     #[ineffable(pulse)]
+    ///     Open,
+    /// }
+    /// fn open_menu() {
+    ///     // Show the menu.
+    /// }
+    /// App::new()
+    ///     .register_input_action::<MenuInput>()
+    ///     .on_pulse(ineff!(MenuInput::Open), open_menu);
+    /// ```
+    fn on_pulse<I: InputAction, M>(
+        &mut self,
+        action: IAWrp<I, Pulse>,
+        system: impl IntoSystemConfigs<M>,
+    ) -> &mut Self {
+        let action_id = action.0.action_id();
+        self.add_systems(
+            PreUpdate,
+            system
+                .after(update_input)
+                .run_if(move |ineffable: Res<'_, Ineffable>| {
+                    ineffable
+                        .just_pulsed_by_id(I::group_id(), action_id)
+                        .unwrap_or(false)
+                }),
+        )
+    }
 }
 
 fn construct_variants_meta_data<I: InputAction>() -> Vec<IneffableMetaItem> {
@@ -65,6 +212,17 @@ fn construct_variants_meta_data<I: InputAction>() -> Vec<IneffableMetaItem> {
             action_id: action.action_id().to_string(),
             kind: action.kind(),
             index: action.index(),
+            label: action.label().to_string(),
+            category: action.category().to_string(),
         })
         .collect()
 }
+
+/// Lists the actions in a group in a human-readable way, for use in error messages.
+fn describe_actions(actions: &[IneffableMetaItem]) -> String {
+    actions
+        .iter()
+        .map(|action| format!("{} ({:?})", action.action_id, action.kind))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
@@ -6,6 +6,7 @@ use std::fmt::{Display, Formatter};
 use bevy::log::{error, info, warn};
 use serde::{Deserialize, Serialize};
 
+use crate::bindings::InputDeviceCategory;
 use crate::input_action::InputKind;
 
 /// Generated by scanning an `InputConfig`, contains problems that were found with it.
@@ -37,6 +38,18 @@ impl InputConfigReport {
     pub fn is_empty(&self) -> bool {
         self.problems.is_empty()
     }
+    /// All problems in this report, for example to show them to the player in a GUI.
+    #[must_use]
+    pub fn problems(&self) -> &[InputConfigReportItem] {
+        &self.problems
+    }
+    /// True iff this report contains at least one problem with `Severity::Error`.
+    #[must_use]
+    pub fn has_errors(&self) -> bool {
+        self.problems
+            .iter()
+            .any(|problem| problem.severity == Severity::Error)
+    }
     /// Write the report to the log.
     pub fn dump_to_log(&self) {
         for problem in &self.problems {
@@ -53,7 +66,15 @@ impl InputConfigReport {
             }
         }
     }
-    #[allow(unused)]
+    /// Adds all problems from the other report to this one.
+    /// Appends only the problems from the other report that are not in this report yet.
+    pub(crate) fn append_new(&mut self, other: InputConfigReport) {
+        for item in other.problems {
+            if !self.problems.contains(&item) {
+                self.problems.push(item);
+            }
+        }
+    }
     pub(crate) fn info(&mut self, problem: InputConfigProblem) {
         self.problems.push(InputConfigReportItem {
             severity: Severity::Info,
@@ -128,6 +149,14 @@ pub enum InputConfigProblem {
         action_id: String,
         options: Vec<String>,
     },
+    /// The bindings of a single action are not all of the same kind, perhaps because two configs that disagree about
+    /// the kind of the action were merged.
+    /// If the action is registered, the bindings of the wrong kind are reported as `ActionWrongKind` instead.
+    ActionHasMixedKinds {
+        group_id: String,
+        action_id: String,
+        kinds: Vec<InputKind>,
+    },
     ActionWrongKind {
         loc: ActionLocation,
         wrong_kind: InputKind,
@@ -149,11 +178,129 @@ pub enum InputConfigProblem {
     SequenceOnlyContainsOneElement {
         loc: ActionLocation,
     },
+    /// A step of a sequence has no inputs, so the sequence can never get past it.
+    SequenceStepEmpty {
+        loc: ActionLocation,
+        step: usize,
+    },
+    /// Every step of a sequence is the same chord, which is better expressed as a double-click.
+    SequenceRepeatsSameChord {
+        loc: ActionLocation,
+        repeats: usize,
+    },
     /// Perhaps the player thought the duration was in seconds, instead of milliseconds?
     SequenceUnrealisticTiming {
         loc: ActionLocation,
         actual_millis: usize,
     },
+    /// Same as `SequenceUnrealisticTiming`, but for the delay of a specific step.
+    SequenceStepUnrealisticTiming {
+        loc: ActionLocation,
+        step: usize,
+        actual_millis: usize,
+    },
+    /// An analog input was calibrated with a bound of zero, perhaps because a field was left out.
+    CalibrationBoundIsZero {
+        loc: ActionLocation,
+    },
+    /// A delta-based analog input, like the scroll wheel, is used in a binding that expects it to be held down.
+    DeltaInputInHoldBinding {
+        loc: ActionLocation,
+        input: String,
+    },
+    /// A notches binding has a notch size of zero, so it would pulse infinitely often.
+    NotchSizeIsZero {
+        loc: ActionLocation,
+    },
+    /// The bounds of an accumulate binding are equal or not a number, so the value can't go anywhere.
+    AccumulateClampIsEmpty {
+        loc: ActionLocation,
+    },
+    /// A swipe binding has a minimum distance of zero or less, so any click or tap counts as a swipe.
+    SwipeDistanceNotPositive {
+        loc: ActionLocation,
+    },
+    /// A swipe binding has an extremely short maximum duration, so it is unlikely to ever complete.
+    SwipeUnrealisticTiming {
+        loc: ActionLocation,
+        actual_millis: usize,
+    },
+    /// All bindings of an action use the same kind of device, while other actions in the config use other devices.
+    /// Players who don't have that device can't use the action. This is purely informational.
+    ///
+    /// The keyboard and mouse count as one kind of device. If an action is bound to both, `device` is
+    /// `InputDeviceCategory::Keyboard`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::InputDeviceCategory;
+    /// # use bevy_ineffable::prelude::*;
+    /// # use bevy_ineffable::reporting::{InputConfigProblem, Severity};
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(pulse)]
+    ///     Jump,
+    ///     #[ineffable(pulse)]
+    ///     Taunt,
+    /// }
+    /// let (_config, report) = InputConfig::builder()
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(KeyCode::Space))
+    ///     .bind(ineff!(PlayerInput::Jump), PulseBinding::just_pressed(GamepadButtonType::South))
+    ///     .bind(ineff!(PlayerInput::Taunt), PulseBinding::just_pressed(GamepadButtonType::North))
+    ///     .build_validated();
+    /// assert!(!report.has_errors());
+    /// let item = &report.problems()[0];
+    /// assert_eq!(item.severity, Severity::Info);
+    /// assert_eq!(
+    ///     item.problem,
+    ///     InputConfigProblem::ActionUsesSingleDevice {
+    ///         group_id: "PlayerInput".to_string(),
+    ///         action_id: "Taunt".to_string(),
+    ///         device: InputDeviceCategory::Gamepad,
+    ///     }
+    /// );
+    /// ```
+    ActionUsesSingleDevice {
+        group_id: String,
+        action_id: String,
+        device: InputDeviceCategory,
+    },
+    /// The same chord is bound to a pulse action and to a continuous or axis action in the same group. This might be
+    /// intentional. Toggles are not reported, since they don't stay active while the chord is held.
+    PulseOverlapsSustained {
+        pulse: ActionLocation,
+        sustained: ActionLocation,
+        chord: String,
+    },
+    /// Within a single action, the chord of one binding is a strict subset of the chord of another binding.
+    /// Due to chord blocking, the narrower binding can't activate while the broader one is held down.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum EditorInput {
+    ///     #[ineffable(pulse)]
+    ///     Save,
+    /// }
+    /// let builder = InputConfig::builder()
+    ///     .bind(ineff!(EditorInput::Save), PulseBinding::just_pressed(KeyCode::KeyS))
+    ///     .bind(ineff!(EditorInput::Save), PulseBinding::just_pressed((KeyCode::ControlLeft, KeyCode::KeyS)));
+    /// // Holding Ctrl-S blocks the plain S binding, so this is reported:
+    /// let shadowed = builder.build();
+    /// // Unless chord blocking is turned off for the action:
+    /// let unblocked = builder.chord_blocking_for(ineff!(EditorInput::Save), false).build();
+    /// ```
+    ChordShadowedInSameAction {
+        narrower: ActionLocation,
+        broader: ActionLocation,
+        narrower_chord: String,
+        broader_chord: String,
+    },
     // TODO: sequences that contain Dummy and something else.
 }
 
@@ -175,6 +322,15 @@ impl InputConfigProblem {
             } => {
                 format!("Unknown action '{group_id}::{action_id}'. Must be one of: {known:?}")
             }
+            InputConfigProblem::ActionHasMixedKinds {
+                group_id,
+                action_id,
+                kinds,
+            } => {
+                format!("Action '{group_id}::{action_id}' has bindings of multiple kinds: {kinds:?}.\n\
+                \tAll bindings of an action must be of the same kind, otherwise some of them are ignored.\n\
+                \tThis often happens when merging configs that disagree about the kind of the action, for example when one of them was edited by hand.")
+            }
             InputConfigProblem::ActionWrongKind {
                 loc,
                 wrong_kind,
@@ -214,12 +370,96 @@ impl InputConfigProblem {
                 \tA sequence is a series of inputs that must be triggered one after another, with a maximum delay between individual inputs. For example: entering a cheat code.\n\
                 \tA sequence with only one element may be replaced with `WhenPressed(_)` for greater readability.", )
             }
+            InputConfigProblem::SequenceStepEmpty { loc, step } => {
+                format!("Binding {loc} contains a sequence in which step {step} is empty.\n\
+                \tA sequence is a series of inputs that must be triggered one after another, with a maximum delay between individual inputs. For example: entering a cheat code.\n\
+                \tAn empty step can never be triggered, so the sequence will never activate. Remove the step, or replace the whole binding with `Dummy`.")
+            }
+            InputConfigProblem::SequenceRepeatsSameChord { loc, repeats } => {
+                format!("Binding {loc} contains a sequence that repeats the same input {repeats} times.\n\
+                \tA sequence is a series of inputs that must be triggered one after another, with a maximum delay between individual inputs. For example: entering a cheat code.\n\
+                \tTo react to the same input pressed twice, use `DoubleClick(_)` instead. It is more readable, and respects the player's double-click timing setting.")
+            }
             InputConfigProblem::SequenceUnrealisticTiming { loc, actual_millis } => {
                 format!("Binding {loc} contains a sequence with a maximum delay of {actual_millis} milliseconds.\n\
                 \tA sequence is a series of inputs that must be triggered one after another, with a maximum delay between individual inputs. For example: entering a cheat code.\n\
                 \tThe maximum delay (currently {actual_millis}ms) is the maximum amount of time between any two inputs in the sequence.\n\
                 \tThis seems unrealistically low and may never activate. Did you perhaps mean {actual_millis} seconds? If so, change to `{actual_millis}000`.")
             }
+            InputConfigProblem::SequenceStepUnrealisticTiming {
+                loc,
+                step,
+                actual_millis,
+            } => {
+                format!("Binding {loc} contains a sequence with a maximum delay of {actual_millis} milliseconds between steps {step} and {}.\n\
+                \tA sequence is a series of inputs that must be triggered one after another, with a maximum delay between individual inputs. For example: entering a cheat code.\n\
+                \tThe step timeouts override the maximum delay between specific steps of the sequence.\n\
+                \tThis seems unrealistically low and may never activate. Did you perhaps mean {actual_millis} seconds? If so, change to `{actual_millis}000`.", step + 1)
+            }
+            InputConfigProblem::CalibrationBoundIsZero { loc } => {
+                format!("Binding {loc} contains an analog calibration with a bound of zero.\n\
+                \tThe calibration bounds are the furthest the analog input can reach in the negative and positive directions. For example: `0.9` for a worn stick.\n\
+                \tA bound of zero is meaningless and is ignored, so the input is not calibrated in that direction.")
+            }
+            InputConfigProblem::DeltaInputInHoldBinding { loc, input } => {
+                format!("Binding {loc} expects `{input}` to be held down, but it only reports movement since the last tick.\n\
+                \tDelta-based inputs like the scroll wheel and mouse motion only reach their threshold on the tick they move, and are released right after.\n\
+                \tThis binding will flicker instead of staying active. Consider using a pulse binding, or an analog axis binding instead.")
+            }
+            InputConfigProblem::NotchSizeIsZero { loc } => {
+                format!("Binding {loc} pulses once per notch, but its notch size is zero.\n\
+                \tThe threshold of a notches binding is the amount of movement that makes up one notch. For example: `1.0` for one line on the scroll wheel.\n\
+                \tA notch size of zero would pulse infinitely often, so this binding never pulses instead.")
+            }
+            InputConfigProblem::AccumulateClampIsEmpty { loc } => {
+                format!("Binding {loc} accumulates its input, but the bounds of its clamp leave no room to move.\n\
+                \tFor example: `(0.0, 1.0)` for a throttle that only goes forward, or `(-1.0, 1.0)` for one that can also go in reverse.")
+            }
+            InputConfigProblem::SwipeDistanceNotPositive { loc } => {
+                format!("Binding {loc} contains a swipe with a minimum distance of zero or less.\n\
+                \tThe minimum distance is how far the pointer must travel in the direction of the swipe, in logical pixels. For example: `100.0`.\n\
+                \tAs it is, any click or tap counts as a swipe.")
+            }
+            InputConfigProblem::SwipeUnrealisticTiming { loc, actual_millis } => {
+                format!("Binding {loc} contains a swipe with a maximum duration of {actual_millis} milliseconds.\n\
+                \tThe maximum duration is the time between touching the screen (or pressing the mouse button) and letting go.\n\
+                \tThis seems unrealistically low and may never activate. Did you perhaps mean {actual_millis} seconds? If so, change to `{actual_millis}000`.")
+            }
+            InputConfigProblem::ActionUsesSingleDevice {
+                group_id,
+                action_id,
+                device,
+            } => {
+                let device = match device {
+                    InputDeviceCategory::Keyboard | InputDeviceCategory::Mouse => {
+                        "keyboard and mouse"
+                    }
+                    InputDeviceCategory::Gamepad => "gamepad",
+                    InputDeviceCategory::All => "single kind of device",
+                };
+                format!("Action '{group_id}::{action_id}' can only be triggered with a {device}.\n\
+                \tPlayers who don't use that kind of device can't trigger this action.\n\
+                \tIf that is intended, you can ignore this message. Otherwise, consider adding a binding for other devices.")
+            }
+            InputConfigProblem::PulseOverlapsSustained {
+                pulse,
+                sustained,
+                chord,
+            } => {
+                format!("Bindings {pulse} and {sustained} both use `{chord}`.\n\
+                \tPressing it fires the pulse action once, while the other action stays active for as long as it is held.\n\
+                \tFor example, Shift might both start sprinting and trigger a dodge. If that is intended, you can ignore this warning.")
+            }
+            InputConfigProblem::ChordShadowedInSameAction {
+                narrower,
+                broader,
+                narrower_chord,
+                broader_chord,
+            } => {
+                format!("Binding {narrower} uses `{narrower_chord}`, which is part of `{broader_chord}` in binding {broader}.\n\
+                \tBecause of chord blocking, {narrower} can't activate while {broader} is held down.\n\
+                \tIf that is intended, you can ignore this warning. Otherwise, remove one of the bindings or disable chord blocking for this action.")
+            }
         }
     }
 }
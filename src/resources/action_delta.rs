@@ -0,0 +1,24 @@
+use bevy::prelude::Vec2;
+
+/// A change to the state of a single `InputAction`, applied directly rather than through its bindings.
+/// Used with `IneffableCommands::apply_delta()` to script input, for example in cutscenes or tests.
+///
+/// The variant must match the `InputKind` of the action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActionDelta {
+    /// Sets the direction of a dual-axis action.
+    DualAxis(Vec2),
+    /// Sets the direction of a single-axis action.
+    SingleAxis(f32),
+    /// Activates or deactivates a continuous action.
+    Continuous(ContinuousDelta),
+    /// Makes a pulse action pulse once.
+    Pulse,
+}
+
+/// Whether an `ActionDelta` activates or deactivates a continuous action.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ContinuousDelta {
+    Start,
+    End,
+}
@@ -0,0 +1,73 @@
+use bevy::prelude::Vec2;
+
+use crate::input_action::InputKind;
+use crate::processed::bound_action::BoundAction;
+
+/// A read-only description of a single bound `InputAction` and its current value.
+/// Obtained from `Ineffable::iter_actions()`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ActionDescriptor<'a> {
+    pub group_id: &'a str,
+    pub action_id: &'a str,
+    pub kind: InputKind,
+    pub value: ActionValue,
+}
+
+/// The current value of an `InputAction`. The variant matches the `InputKind` of the action.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ActionValue {
+    /// The direction of a dual-axis action, as returned by `Ineffable::direction_2d()`.
+    DualAxis(Vec2),
+    /// The direction of a single-axis action, as returned by `Ineffable::direction_1d()`.
+    SingleAxis(f32),
+    /// Whether a continuous action is active, as returned by `Ineffable::is_active()`.
+    Continuous(bool),
+    /// Whether a pulse action pulsed this tick, as returned by `Ineffable::just_pulsed()`.
+    Pulse(bool),
+}
+
+impl ActionValue {
+    /// The value of the same kind that an action reports while nothing is happening.
+    fn neutral(self) -> Self {
+        match self {
+            ActionValue::DualAxis(_) => ActionValue::DualAxis(Vec2::ZERO),
+            ActionValue::SingleAxis(_) => ActionValue::SingleAxis(0.),
+            ActionValue::Continuous(_) => ActionValue::Continuous(false),
+            ActionValue::Pulse(_) => ActionValue::Pulse(false),
+        }
+    }
+}
+
+impl<'a> ActionDescriptor<'a> {
+    /// Disabled actions report their neutral value, just like they do in `Ineffable`.
+    #[must_use]
+    pub(crate) fn new(
+        group_id: &'a str,
+        action_id: &'a str,
+        bound_action: &BoundAction,
+        enabled: bool,
+    ) -> Self {
+        let (kind, value) = match bound_action {
+            BoundAction::DualAxis(binding) => {
+                (InputKind::DualAxis, ActionValue::DualAxis(binding.value))
+            }
+            BoundAction::SingleAxis(binding) => (
+                InputKind::SingleAxis,
+                ActionValue::SingleAxis(binding.value),
+            ),
+            BoundAction::Continuous(binding) => (
+                InputKind::Continuous,
+                ActionValue::Continuous(binding.active),
+            ),
+            BoundAction::Pulse(binding) => {
+                (InputKind::Pulse, ActionValue::Pulse(binding.just_pulsed))
+            }
+        };
+        Self {
+            group_id,
+            action_id,
+            kind,
+            value: if enabled { value } else { value.neutral() },
+        }
+    }
+}
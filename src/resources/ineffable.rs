@@ -1,12 +1,14 @@
 use std::time::Duration;
 
-use bevy::prelude::{Reflect, Resource, Vec2};
-use bevy::utils::HashMap;
+use bevy::prelude::{Gamepad, Reflect, Resource, Vec2};
+use bevy::utils::{HashMap, HashSet};
 
+use crate::config::InputConfig;
 use crate::input_action::InputAction;
 use crate::phantom::{Continuous, DualAxis, IAWrp, Pulse, SingleAxis};
 use crate::processed::bound_action::BoundAction;
 use crate::processed::stateful::{axis_dual, axis_single, continuous, pulse};
+use crate::resources::{ActionDelta, ActionDescriptor, InputContext, InputSnapshot};
 
 /// Main entry point for querying the state of `InputAction`s.
 ///
@@ -63,16 +65,285 @@ use crate::processed::stateful::{axis_dual, axis_single, continuous, pulse};
 /// ```
 #[derive(Debug, Default, Resource, Reflect, Clone)]
 pub struct Ineffable {
-    pub(crate) _contexts: HashMap<String, InputContext>,
+    /// The context stack. The last context has the highest priority.
+    pub(crate) contexts: Vec<InputContext>,
     pub(crate) groups: HashMap<String, ProcessedBindingGroup>,
+    /// The config that is in use, with the active profile merged in.
+    pub(crate) current_config: Option<InputConfig>,
+    /// The config that was installed most recently, without any profile. Profiles are activated on top of this one.
+    pub(crate) base_config: Option<InputConfig>,
+    /// The name of the profile in the current config that is active, if any.
+    pub(crate) active_profile: Option<String>,
+    /// The defaults that `IneffableCommands::reset_to_defaults()` goes back to.
+    pub(crate) default_config: Option<InputConfig>,
+    /// The gamepads that are currently connected, in the order they were connected.
+    pub(crate) connected_gamepads: Vec<Gamepad>,
+    /// The index of every registered action within its group, keyed by group_id and then by action_id.
+    /// Used to look up actions by their string ids.
+    pub(crate) action_indices: HashMap<String, HashMap<String, usize>>,
+    /// The action_ids of the actions that were disabled through `IneffableCommands::set_action_enabled()`,
+    /// keyed by group_id.
+    pub(crate) disabled_actions: HashMap<String, HashSet<String>>,
 }
 
 /// All bindings within a certain group. The Vec index is the order in which they appear in the enum.
 type ProcessedBindingGroup = Vec<BoundAction>;
-/// A context. Consists of a number of group names.
-type InputContext = Vec<String>;
+
+/// The direction from which `Ineffable::direction_2d_polar()` measures angles.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AngleReference {
+    /// Angles are measured counter-clockwise from the positive x-axis, as is usual in mathematics.
+    /// Pointing right is 0, pointing up is π/2.
+    #[default]
+    PositiveX,
+    /// Angles are measured clockwise from the positive y-axis, like a compass bearing.
+    /// Pointing up is 0, pointing right is π/2.
+    PositiveY,
+}
 
 impl Ineffable {
+    /// Returns the `InputConfig` that is currently in use, if one was set. If a profile is active, it is already merged
+    /// into the returned config.
+    #[must_use]
+    pub fn current_config(&self) -> Option<&InputConfig> {
+        self.current_config.as_ref()
+    }
+
+    /// Returns the name of the active profile, or None if the current config is used without any profile.
+    /// See `IneffableCommands::activate_profile()`.
+    #[must_use]
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_deref()
+    }
+
+    /// Returns the default `InputConfig`, if one is known.
+    ///
+    /// This is the base config when loading configs through `IneffableCommands::load_configs()`, or whatever config
+    /// was passed to `IneffableCommands::set_default_config()`.
+    #[must_use]
+    pub fn default_config(&self) -> Option<&InputConfig> {
+        self.default_config.as_ref()
+    }
+
+    /// Returns the gamepads that are currently connected, in the order they were connected.
+    /// For an `IneffableInput` component, this only includes the gamepads that are assigned to it.
+    ///
+    /// A `GamepadConnectionChanged` event is sent whenever a gamepad is connected or disconnected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::events::GamepadConnectionChanged;
+    /// # use bevy_ineffable::prelude::*;
+    /// fn show_controllers(ineffable: Res<Ineffable>, mut changes: EventReader<GamepadConnectionChanged>) {
+    ///     if changes.read().next().is_some() {
+    ///         info!("Connected gamepads: {:?}", ineffable.connected_gamepads());
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn connected_gamepads(&self) -> &[Gamepad] {
+        &self.connected_gamepads
+    }
+
+    /// Pushes a context onto the top of the context stack, giving it priority over all other contexts.
+    /// If a context with the same id is already on the stack, it is moved to the top.
+    ///
+    /// While an action in this context uses an input, that input counts as not pressed for the contexts below it,
+    /// and for all groups that are not part of any context. This makes it possible for a menu to use the same keys
+    /// as the gameplay below it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// fn open_menu(mut ineffable: ResMut<Ineffable>) {
+    ///     // While the menu is open, its actions take priority over the gameplay actions below it.
+    ///     ineffable.push_context("Menu", ["MenuInput"]);
+    /// }
+    /// ```
+    pub fn push_context<S: Into<String>>(
+        &mut self,
+        id: impl Into<String>,
+        groups: impl IntoIterator<Item = S>,
+    ) {
+        let id = id.into();
+        self.remove_context(&id);
+        self.contexts.push(InputContext {
+            id,
+            groups: groups.into_iter().map(Into::into).collect(),
+        });
+    }
+
+    /// Removes the context with the given id from the context stack. Returns false if there was no such context.
+    pub fn remove_context(&mut self, id: &str) -> bool {
+        let len = self.contexts.len();
+        self.contexts.retain(|context| context.id != id);
+        self.contexts.len() != len
+    }
+
+    /// Returns the context stack. The last context has the highest priority.
+    #[must_use]
+    pub fn contexts(&self) -> &[InputContext] {
+        &self.contexts
+    }
+
+    /// Captures the transient input state of all `InputAction`s, such as toggle states, charge times and the current
+    /// values of all actions. Restore it later with `restore()`. See `InputSnapshot` for what is included.
+    ///
+    /// The post-acceptance-delay is shared by all players, so it is not part of this snapshot. Use
+    /// `IneffableCommands::snapshot()` to capture the global state, including the post-acceptance-delay. This function
+    /// is useful for the `IneffableInput` components of individual players.
+    #[must_use]
+    pub fn snapshot(&self) -> InputSnapshot {
+        let actions = self
+            .action_indices
+            .iter()
+            .filter_map(|(group_id, indices)| {
+                let group = self.groups.get(group_id)?;
+                let states = indices
+                    .iter()
+                    .filter_map(|(action_id, index)| {
+                        Some((action_id.clone(), group.get(*index)?.state()))
+                    })
+                    .collect();
+                Some((group_id.clone(), states))
+            })
+            .collect();
+        InputSnapshot {
+            actions,
+            post_acceptance_delay: None,
+        }
+    }
+
+    /// Restores the input state from a snapshot that was taken earlier with `snapshot()`.
+    ///
+    /// The state is applied to the bindings that are currently installed, so rebinding keys or setting a new
+    /// `InputConfig` in the meantime is fine. Actions that no longer exist, or whose `InputKind` changed, are ignored.
+    pub fn restore(&mut self, snapshot: &InputSnapshot) {
+        for (group_id, states) in &snapshot.actions {
+            let (Some(indices), Some(group)) = (
+                self.action_indices.get(group_id),
+                self.groups.get_mut(group_id),
+            ) else {
+                continue;
+            };
+            for (action_id, state) in states {
+                if let Some(bound_action) = indices
+                    .get(action_id)
+                    .and_then(|index| group.get_mut(*index))
+                {
+                    bound_action.restore(state);
+                }
+            }
+        }
+    }
+
+    /// Iterates over all bound actions, yielding their ids, kinds and current values. This is useful for building a
+    /// controls overview, an editor or diagnostics. Groups are sorted by their id, and actions within a group are in
+    /// the order in which they were registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// fn log_actions(ineffable: Res<Ineffable>) {
+    ///     for action in ineffable.iter_actions() {
+    ///         info!("{}::{} = {:?}", action.group_id, action.action_id, action.value);
+    ///     }
+    /// }
+    /// ```
+    pub fn iter_actions(&self) -> impl Iterator<Item = ActionDescriptor<'_>> {
+        let mut actions: Vec<(&String, usize, &String)> = self
+            .action_indices
+            .iter()
+            .flat_map(|(group_id, indices)| {
+                indices
+                    .iter()
+                    .map(move |(action_id, index)| (group_id, *index, action_id))
+            })
+            .collect();
+        actions.sort_unstable();
+        actions
+            .into_iter()
+            .filter_map(|(group_id, index, action_id)| {
+                let bound_action = self.groups.get(group_id)?.get(index)?;
+                let enabled = !self.is_disabled(group_id, action_id);
+                Some(ActionDescriptor::new(
+                    group_id,
+                    action_id,
+                    bound_action,
+                    enabled,
+                ))
+            })
+    }
+
+    /// Clears the transient state of all actions. See `IneffableCommands::reset_state()`.
+    pub(crate) fn reset_state(&mut self) {
+        self.groups
+            .values_mut()
+            .flatten()
+            .for_each(BoundAction::reset);
+    }
+
+    /// Returns true iff the given action is currently enabled. Actions are enabled unless they were disabled through
+    /// `IneffableCommands::set_action_enabled()`. Disabled actions always report their neutral state.
+    pub fn is_action_enabled<I: InputAction, K>(&self, action: IAWrp<I, K>) -> bool {
+        !self.is_disabled(I::group_id(), action.0.action_id())
+    }
+
+    pub(crate) fn is_disabled(&self, group_id: &str, action_id: &str) -> bool {
+        self.disabled_actions
+            .get(group_id)
+            .is_some_and(|actions| actions.contains(action_id))
+    }
+
+    pub(crate) fn set_action_enabled(&mut self, group_id: &str, action_id: &str, enabled: bool) {
+        if enabled {
+            if let Some(actions) = self.disabled_actions.get_mut(group_id) {
+                actions.remove(action_id);
+            }
+        } else {
+            self.disabled_actions
+                .entry(group_id.to_string())
+                .or_default()
+                .insert(action_id.to_string());
+        }
+    }
+
+    /// Looks up an action by its string ids. Returns None if the action was not registered or is not bound.
+    /// Disabled actions are treated as if they were not bound.
+    fn bound_action_by_id(&self, group_id: &str, action_id: &str) -> Option<&BoundAction> {
+        if self.is_disabled(group_id, action_id) {
+            return None;
+        }
+        let index = *self.action_indices.get(group_id)?.get(action_id)?;
+        self.groups.get(group_id)?.get(index)
+    }
+
+    /// Overrides the state of a single action for this tick. See `IneffableCommands::apply_delta()`.
+    pub(crate) fn apply_delta(
+        &mut self,
+        group_id: &str,
+        action_id: &str,
+        delta: ActionDelta,
+    ) -> bool {
+        let Some(&index) = self
+            .action_indices
+            .get(group_id)
+            .and_then(|g| g.get(action_id))
+        else {
+            return false;
+        };
+        self.groups
+            .get_mut(group_id)
+            .and_then(|group| group.get_mut(index))
+            .is_some_and(|bound_action| bound_action.apply_delta(delta))
+    }
+
     // =================================================================================================================
     // ===== Dual Axis
     // =================================================================================================================
@@ -86,12 +357,89 @@ impl Ineffable {
             .unwrap_or_default()
     }
 
+    /// Returns the direction of the given dual-axis `InputAction` in polar coordinates, as (angle, magnitude).
+    /// The angle is in radians, in the range [-π, π], and measured from the given reference direction.
+    ///
+    /// When the action is neutral, the magnitude is zero and the angle stays at the last direction the action pointed
+    /// in, rather than jumping back to zero. This is useful for twin-stick aiming, where letting go of the stick should
+    /// not change the aim.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// # use bevy_ineffable::resources::AngleReference;
+    /// #[derive(InputAction)]
+    /// pub enum ShipInput {
+    ///     #[ineffable(dual_axis)]
+    ///     Aim,
+    /// }
+    /// fn aim_turret(ineffable: Res<Ineffable>) {
+    ///     let (angle, magnitude) = ineffable.direction_2d_polar(ineff!(ShipInput::Aim), AngleReference::PositiveY);
+    /// }
+    /// ```
+    pub fn direction_2d_polar<I: InputAction>(
+        &self,
+        action: IAWrp<I, DualAxis>,
+        reference: AngleReference,
+    ) -> (f32, f32) {
+        axis_dual::bound_action(self, action)
+            .map(|bound| bound.polar(reference))
+            .unwrap_or_default()
+    }
+
+    /// Same as `direction_2d()`, but for actions that are identified by their string ids, such as dynamic actions.
+    ///
+    /// Returns None if the action does not exist or is not a dual-axis action.
+    #[must_use]
+    pub fn direction_2d_by_id(&self, group_id: &str, action_id: &str) -> Option<Vec2> {
+        match self.bound_action_by_id(group_id, action_id)? {
+            BoundAction::DualAxis(binding) => Some(binding.value),
+            _ => None,
+        }
+    }
+
     // =================================================================================================================
     // ===== Single Axis
     // =================================================================================================================
 
     /// Returns a `f32` representing the 1-dimensional direction of the given single-axis `InputAction`.
     ///
+    /// If the action has multiple bindings, the one with the greatest magnitude wins and its sign is kept. This means
+    /// that players can switch between devices at any time. If two bindings point in opposite directions with equal
+    /// magnitude, such as when both the negative and the positive keys are held, they cancel each other out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::bindings::AnalogInput;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(single_axis)]
+    ///     Walk,
+    /// }
+    /// // The player can walk with either the keyboard or the left stick.
+    /// let config = InputConfig::builder()
+    ///     .bind(
+    ///         ineff!(PlayerInput::Walk),
+    ///         SingleAxisBinding::hold()
+    ///             .set_negative(KeyCode::KeyA)
+    ///             .set_positive(KeyCode::KeyD)
+    ///             .build(),
+    ///     )
+    ///     .bind(
+    ///         ineff!(PlayerInput::Walk),
+    ///         SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::LeftStickX)).build(),
+    ///     )
+    ///     .build();
+    /// fn walk(ineffable: Res<Ineffable>) {
+    ///     let direction = ineffable.direction_1d(ineff!(PlayerInput::Walk));
+    /// }
+    /// ```
+    ///
     /// Call like this: `ineffable.direction_1d(ineff!(ExampleInput::ExampleVariant))`
     pub fn direction_1d<I: InputAction>(&self, action: IAWrp<I, SingleAxis>) -> f32 {
         axis_single::bound_action(self, action)
@@ -99,6 +447,47 @@ impl Ineffable {
             .unwrap_or_default()
     }
 
+    /// Same as `direction_1d()`, but linearly remaps the value from the range -1.0 to 1.0 onto the range `min` to
+    /// `max`. Values outside the normal range, for example due to a high sensitivity, are clamped first, so the
+    /// result never leaves the target range.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum ShipInput {
+    ///     #[ineffable(single_axis)]
+    ///     Throttle,
+    /// }
+    /// fn update_throttle_gauge(ineffable: Res<Ineffable>) {
+    ///     // Throttle in percent: idle is halfway.
+    ///     let percent = ineffable.direction_1d_mapped(ineff!(ShipInput::Throttle), 0., 100.);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn direction_1d_mapped<I: InputAction>(
+        &self,
+        action: IAWrp<I, SingleAxis>,
+        min: f32,
+        max: f32,
+    ) -> f32 {
+        let normalized = (self.direction_1d(action).clamp(-1., 1.) + 1.) / 2.;
+        min + normalized * (max - min)
+    }
+
+    /// Same as `direction_1d()`, but for actions that are identified by their string ids, such as dynamic actions.
+    ///
+    /// Returns None if the action does not exist or is not a single-axis action.
+    #[must_use]
+    pub fn direction_1d_by_id(&self, group_id: &str, action_id: &str) -> Option<f32> {
+        match self.bound_action_by_id(group_id, action_id)? {
+            BoundAction::SingleAxis(binding) => Some(binding.value),
+            _ => None,
+        }
+    }
+
     // =================================================================================================================
     // ===== Continuous
     // =================================================================================================================
@@ -110,6 +499,17 @@ impl Ineffable {
         continuous::bound_action(self, action).is_some_and(|binding| binding.active)
     }
 
+    /// Same as `is_active()`, but for actions that are identified by their string ids, such as dynamic actions.
+    ///
+    /// Returns None if the action does not exist or is not a continuous action.
+    #[must_use]
+    pub fn is_active_by_id(&self, group_id: &str, action_id: &str) -> Option<bool> {
+        match self.bound_action_by_id(group_id, action_id)? {
+            BoundAction::Continuous(binding) => Some(binding.active),
+            _ => None,
+        }
+    }
+
     /// Returns true iff the given continuous action is active now, but was not active last tick.
     ///
     /// Call like this: `ineffable.just_activated(ineff!(ExampleInput::ExampleVariant))`
@@ -130,9 +530,14 @@ impl Ineffable {
     ///
     /// Returns:
     /// - `Some(duration)` if this action is currently active.
-    /// - `Some(duration)` if the action is not active, but was active last game tick.
+    /// - `Some(duration)` if the action is not active, but was active last game tick. This is the full charge, so a
+    ///   charged attack can read it on the tick that the player lets go.
     /// - `None` otherwise.
     ///
+    /// If the config sets a `charge_retain_window` and the action activates again within that window, the charge
+    /// continues where it left off, instead of starting over. The time in between, while the action was inactive,
+    /// doesn't count towards the charge.
+    ///
     /// Call like this: `ineffable.charge_time(ineff!(ExampleInput::ExampleVariant))`
     pub fn charge_time<I: InputAction>(&self, action: IAWrp<I, Continuous>) -> Option<Duration> {
         continuous::bound_action(self, action).and_then(|binding| binding.charging_duration())
@@ -148,4 +553,91 @@ impl Ineffable {
     pub fn just_pulsed<I: InputAction>(&self, action: IAWrp<I, Pulse>) -> bool {
         pulse::bound_action(self, action).is_some_and(|binding| binding.just_pulsed)
     }
+
+    /// Same as `just_pulsed()`, but for actions that are identified by their string ids, such as dynamic actions.
+    ///
+    /// Returns None if the action does not exist or is not a pulse action.
+    #[must_use]
+    pub fn just_pulsed_by_id(&self, group_id: &str, action_id: &str) -> Option<bool> {
+        match self.bound_action_by_id(group_id, action_id)? {
+            BoundAction::Pulse(binding) => Some(binding.just_pulsed),
+            _ => None,
+        }
+    }
+
+    /// Returns how many times the given pulse action pulsed this tick. This is zero or one for most bindings, but
+    /// `PulseBinding::Notches` pulses once for every notch, so a fast scroll can pulse several times in one tick.
+    ///
+    /// Call like this: `ineffable.pulse_count(ineff!(ExampleInput::Example))`
+    pub fn pulse_count<I: InputAction>(&self, action: IAWrp<I, Pulse>) -> u32 {
+        pulse::bound_action(self, action)
+            .map(|binding| binding.pulse_count)
+            .unwrap_or_default()
+    }
+
+    /// Returns how much time has passed since the given pulse action last pulsed. This is zero on the tick that it
+    /// pulses. Useful for cooldown indicators, or for showing something like "last shot 0.8s ago".
+    ///
+    /// Returns None if the action has never pulsed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::time::Duration;
+    /// # use bevy::ecs::system::RunSystemOnce;
+    /// # use bevy::input::InputPlugin;
+    /// # use bevy::prelude::*;
+    /// # use bevy::time::TimeUpdateStrategy;
+    /// # use bevy_ineffable::prelude::*;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(pulse)]
+    ///     Shoot,
+    /// }
+    /// let config = InputConfig::builder()
+    ///     .bind(ineff!(PlayerInput::Shoot), PulseBinding::just_pressed(KeyCode::Space))
+    ///     .build();
+    /// # let mut app = App::new();
+    /// # app.add_plugins((MinimalPlugins, AssetPlugin::default(), InputPlugin, IneffablePlugin))
+    /// #     .register_input_action::<PlayerInput>();
+    /// # app.world_mut().run_system_once(move |mut ineffable: IneffableCommands| {
+    /// #     ineffable.set_config(&config);
+    /// # });
+    /// # let since = |app: &App| app.world().resource::<Ineffable>().time_since_pulse(ineff!(PlayerInput::Shoot));
+    /// // Every update takes exactly 100 milliseconds.
+    /// app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(100)));
+    /// app.update();
+    /// assert_eq!(since(&app), None);
+    ///
+    /// app.world_mut().resource_mut::<ButtonInput<KeyCode>>().press(KeyCode::Space);
+    /// app.update();
+    /// assert_eq!(since(&app), Some(Duration::ZERO));
+    ///
+    /// app.update();
+    /// assert_eq!(since(&app), Some(Duration::from_millis(100)));
+    /// ```
+    ///
+    /// Call like this: `ineffable.time_since_pulse(ineff!(ExampleInput::ExampleVariant))`
+    pub fn time_since_pulse<I: InputAction>(&self, action: IAWrp<I, Pulse>) -> Option<Duration> {
+        pulse::bound_action(self, action).and_then(|binding| binding.time_since_pulse())
+    }
+
+    /// Returns how far the player has progressed through a sequence bound to the given pulse action,
+    /// as (current step, total steps). Useful for showing feedback like "2/5" in a combo meter.
+    ///
+    /// Double-clicks count as a sequence of two steps. If the action has multiple sequence bindings, the one
+    /// that is furthest along is returned. Progress resets to zero when the player takes too long between inputs.
+    /// What happens when the player completes the sequence depends on its `SequenceResetPolicy`: with `FullReset`,
+    /// progress starts over at zero, and with `SlidingWindow`, it continues from the steps that are reused for the
+    /// next attempt.
+    ///
+    /// Returns None if the action is not bound to any sequence or double-click.
+    ///
+    /// Call like this: `ineffable.sequence_progress(ineff!(ExampleInput::ExampleVariant))`
+    pub fn sequence_progress<I: InputAction>(
+        &self,
+        action: IAWrp<I, Pulse>,
+    ) -> Option<(usize, usize)> {
+        pulse::bound_action(self, action).and_then(|binding| binding.sequence_progress())
+    }
 }
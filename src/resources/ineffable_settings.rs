@@ -1,6 +1,6 @@
 use std::time::Duration;
 
-use bevy::prelude::{Reflect, Res, Resource, Timer, TimerMode};
+use bevy::prelude::{Reflect, Res, Resource, Timer, TimerMode, Vec2};
 use bevy::time::Time;
 use bevy::utils::default;
 use serde::{Deserialize, Serialize};
@@ -11,7 +11,14 @@ use crate::config::{DurationInMillis, InputConfig};
 /// This is the same as the default value in Microsoft Windows.
 const DEFAULT_DOUBLE_CLICK_TIMING: DurationInMillis = 500;
 
-#[derive(Debug, Resource, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq)]
+/// The default number of actions from which on they are updated in parallel.
+const DEFAULT_PARALLEL_THRESHOLD: usize = 1024;
+
+/// Upper limit for the motion smoothing factor. At one, the smoothed motion would never catch up with the mouse.
+const MAX_MOTION_SMOOTHING: f32 = 0.95;
+
+/// The settings that are derived from the current `InputConfig`.
+#[derive(Debug, Resource, Serialize, Deserialize, Reflect, Clone, PartialEq)]
 pub struct IneffableSettings {
     /// The maximum delay between the first and second clicks of a double-click action.
     pub double_click_timing: Duration,
@@ -23,6 +30,27 @@ pub struct IneffableSettings {
     /// players, including a simple cooldown period where no further input is recognised for a short period
     /// afterwards can avoid this."
     pub post_acceptance_delay: Option<PostAcceptanceDelay>,
+    /// When a continuous action deactivates, its charge time is retained for this long.
+    /// If the action activates again within this window, it continues charging where it left off.
+    pub charge_retain_window: Duration,
+    /// An accessibility setting for players who can't hold down keys.
+    /// If enabled, pressing a hold binding toggles its continuous action on or off, instead of keeping it active
+    /// for as long as the input is held. Toggle bindings are not affected.
+    pub hold_to_toggle: bool,
+    /// The factor by which every analog axis is scaled.
+    pub global_sensitivity: f32,
+    /// The factor by which mouse motion is scaled, per axis.
+    pub mouse_sensitivity: Vec2,
+    /// The weight of the previous ticks' motion when smoothing mouse motion, at 60 frames per second.
+    /// Zero means no smoothing.
+    pub motion_smoothing: f32,
+    /// If true, axes that are bound to multiple devices only listen to the device that was used last.
+    pub last_device_wins: bool,
+    /// Actions are updated in parallel when at least this many of them are updated together. Below this number, the
+    /// overhead of spreading the work over multiple threads outweighs the gains. Set it to `usize::MAX` to always
+    /// update serially. This is not part of the `InputConfig`, so setting a new config doesn't change it.
+    #[serde(default = "default_parallel_threshold")]
+    pub parallel_threshold: usize,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq)]
@@ -41,6 +69,18 @@ impl PostAcceptanceDelay {
     pub(crate) fn input_detected(&mut self) {
         self.should_activate = true;
     }
+    /// Takes over the progress of another post-acceptance-delay, as captured in an `InputSnapshot`. The duration of
+    /// this delay stays the same. Passing None means that no delay was configured, so nothing is blocking input.
+    pub(crate) fn restore(&mut self, snapshot: Option<&PostAcceptanceDelay>) {
+        self.should_activate = snapshot.is_some_and(|snapshot| snapshot.should_activate);
+        self.timer = snapshot
+            .and_then(|snapshot| snapshot.timer.as_ref())
+            .map(|timer| {
+                let mut restored = Timer::new(self.delay, TimerMode::Once);
+                restored.set_elapsed(timer.elapsed());
+                restored
+            });
+    }
     fn is_blocking_input(&self) -> bool {
         self.timer.is_some()
     }
@@ -57,11 +97,22 @@ impl PostAcceptanceDelay {
     }
 }
 
+fn default_parallel_threshold() -> usize {
+    DEFAULT_PARALLEL_THRESHOLD
+}
+
 impl Default for IneffableSettings {
     fn default() -> Self {
         Self {
             double_click_timing: Duration::from_millis(DEFAULT_DOUBLE_CLICK_TIMING),
             post_acceptance_delay: None,
+            charge_retain_window: Duration::ZERO,
+            hold_to_toggle: false,
+            global_sensitivity: 1.,
+            mouse_sensitivity: Vec2::ONE,
+            motion_smoothing: 0.,
+            last_device_wins: false,
+            parallel_threshold: default_parallel_threshold(),
         }
     }
 }
@@ -85,6 +136,18 @@ impl IneffableSettings {
                 delay: Duration::from_millis(millis),
                 ..default()
             });
+        self.charge_retain_window =
+            Duration::from_millis(config.charge_retain_window.unwrap_or_default());
+        self.hold_to_toggle = config.hold_to_toggle.unwrap_or_default();
+        self.global_sensitivity = number_or(config.global_sensitivity, 1.);
+        self.mouse_sensitivity = number_or(config.mouse_sensitivity, 1.)
+            * Vec2::new(
+                number_or(config.mouse_sensitivity_x, 1.),
+                number_or(config.mouse_sensitivity_y, 1.),
+            );
+        self.motion_smoothing =
+            number_or(config.motion_smoothing, 0.).clamp(0., MAX_MOTION_SMOOTHING);
+        self.last_device_wins = config.last_device_wins.unwrap_or_default();
     }
     #[must_use]
     pub(crate) fn input_blocked_by_pad(&self) -> bool {
@@ -95,3 +158,8 @@ impl IneffableSettings {
         }
     }
 }
+
+/// Returns the value from the config, or the default if it is missing, infinite or NaN.
+fn number_or(value: Option<f32>, default: f32) -> f32 {
+    value.filter(|value| value.is_finite()).unwrap_or(default)
+}
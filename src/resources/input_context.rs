@@ -0,0 +1,20 @@
+use bevy::prelude::Reflect;
+use serde::{Deserialize, Serialize};
+
+/// A layer of action groups, pushed onto the context stack with `Ineffable::push_context()`.
+///
+/// Contexts higher on the stack consume the inputs they handle: while an action in a context is using an input, that
+/// input counts as not pressed for all contexts below it. Groups that are not part of any context form the lowest
+/// layer. This way, a pause menu can use the same key as a gameplay action without triggering both.
+///
+/// A consumed key or button stays consumed until it is released, so removing a context while one of its keys is held
+/// down doesn't trigger the actions below it. A `KeyGroup` consumes only the keys of the group that are held down.
+/// Analog inputs are consumed while they move. Swipe bindings follow the pointer rather than any specific input, so
+/// they don't consume anything.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone, PartialEq, Eq, Hash)]
+pub struct InputContext {
+    /// Uniquely identifies the context on the stack.
+    pub id: String,
+    /// The action groups in this context. These are the names of the `InputAction` enums.
+    pub groups: Vec<String>,
+}
@@ -0,0 +1,101 @@
+//! Resolves `KeyCode`s to the characters they produce on the player's keyboard layout, for display purposes.
+
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::{ButtonInput, EventReader, KeyCode, Res, ResMut, Resource};
+use bevy::utils::{HashMap, HashSet};
+
+use crate::bindings::BinaryInput;
+
+/// Remembers which character each physical key produces on the player's keyboard layout.
+///
+/// A `KeyCode` names the physical location of a key on a US QWERTY keyboard. On other layouts, such as AZERTY or
+/// QWERTZ, the same key has a different character printed on it. Showing the player "W" when their key says "Z" is
+/// confusing, so use this resource to label keys in your control settings screen or button prompts.
+///
+/// Bevy doesn't offer a way to query the layout directly, so the labels are learned from `KeyboardInput` events:
+/// a key is labelled after it has been pressed at least once without any modifiers, like Shift or AltGr, held down.
+/// Until then, the name of the `KeyCode` is used.
+/// This only affects how keys are displayed, not how inputs are matched.
+///
+/// This is opt-in: call `app.init_resource::<KeyLabels>()` to start learning labels.
+///
+/// # Examples
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_ineffable::resources::key_labels::KeyLabels;
+/// App::new().init_resource::<KeyLabels>();
+///
+/// fn show_prompt(labels: Res<KeyLabels>) {
+///     // Shows "Press Z to move forward" on an AZERTY keyboard, once the player has pressed that key.
+///     info!("Press {} to move forward", labels.label(KeyCode::KeyW));
+/// }
+/// ```
+#[derive(Debug, Default, Resource)]
+pub struct KeyLabels {
+    labels: HashMap<KeyCode, String>,
+}
+
+impl KeyLabels {
+    /// Returns the character that the given key produces on the player's layout, if it is known.
+    /// Otherwise, returns the name of the `KeyCode`.
+    #[must_use]
+    pub fn label(&self, key: KeyCode) -> String {
+        self.labels
+            .get(&key)
+            .cloned()
+            .unwrap_or_else(|| format!("{key:?}"))
+    }
+    /// Returns a label for the given input. Keys are resolved with `label()`, all other inputs use their name.
+    #[must_use]
+    pub fn describe(&self, input: &BinaryInput) -> String {
+        match input {
+            BinaryInput::Key(key) => self.label(*key),
+            other => format!("{other:?}"),
+        }
+    }
+}
+
+/// Keys that change the character that other keys produce, like Shift turning "1" into "!".
+const MODIFIERS: [KeyCode; 8] = [
+    KeyCode::ShiftLeft,
+    KeyCode::ShiftRight,
+    KeyCode::ControlLeft,
+    KeyCode::ControlRight,
+    KeyCode::AltLeft,
+    KeyCode::AltRight,
+    KeyCode::SuperLeft,
+    KeyCode::SuperRight,
+];
+
+pub(crate) fn learn_key_labels(
+    mut key_labels: ResMut<'_, KeyLabels>,
+    keys: Res<'_, ButtonInput<KeyCode>>,
+    mut keyboard_events: EventReader<'_, '_, KeyboardInput>,
+) {
+    // Follow the modifiers through the events, so a modifier pressed or released in the same tick as another key
+    // only affects the keys that come after it.
+    let mut modifiers: HashSet<KeyCode> = MODIFIERS
+        .into_iter()
+        .filter(|modifier| keys.pressed(*modifier))
+        .collect();
+    for event in keyboard_events.read() {
+        if MODIFIERS.contains(&event.key_code) {
+            match event.state {
+                ButtonState::Pressed => modifiers.insert(event.key_code),
+                ButtonState::Released => modifiers.remove(&event.key_code),
+            };
+            continue;
+        }
+        if !modifiers.is_empty() {
+            continue;
+        }
+        // Keys that don't produce a character, like the arrow keys, are best described by their name.
+        if let Key::Character(character) = &event.logical_key {
+            key_labels
+                .labels
+                .insert(event.key_code, character.to_uppercase());
+        }
+    }
+}
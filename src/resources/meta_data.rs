@@ -1,31 +1,137 @@
 use bevy::prelude::Resource;
 use bevy::utils::HashMap;
 
-use crate::input_action::InputKind;
+use crate::input_action::{ActionId, InputKind};
 
+/// Information about every `InputAction` that was registered with `app.register_input_action()` or
+/// `app.register_dynamic_action()`. Available as a read-only resource.
 #[derive(Debug, Default, Resource)]
-pub(crate) struct IneffableMetaData {
-    pub(crate) map: HashMap<&'static str, Vec<IneffableMetaItem>>,
+pub struct IneffableMetaData {
+    pub(crate) map: HashMap<String, Vec<IneffableMetaItem>>,
+    /// The full type name of the `InputAction` enum that registered each group.
+    /// Groups that only contain dynamic actions have no entry.
+    pub(crate) type_names: HashMap<String, &'static str>,
 }
 
+/// Describes a single registered `InputAction`. Obtained from `IneffableMetaData::registered_actions()`.
 #[derive(Debug, Eq, PartialEq, Clone)]
-pub(crate) struct IneffableMetaItem {
+pub struct IneffableMetaItem {
     pub(crate) group_id: String,
     pub(crate) action_id: String,
     pub(crate) kind: InputKind,
-    /// The enum variant index.
+    /// The enum variant index. For dynamic actions, this is the order in which they were registered.
     pub(crate) index: usize,
+    pub(crate) label: String,
+    pub(crate) category: String,
+}
+
+impl IneffableMetaItem {
+    #[must_use]
+    pub fn group_id(&self) -> &str {
+        &self.group_id
+    }
+    #[must_use]
+    pub fn action_id(&self) -> &str {
+        &self.action_id
+    }
+    #[must_use]
+    pub fn kind(&self) -> InputKind {
+        self.kind
+    }
+    /// The position of the action within its group, in enum declaration order.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+    /// A human-friendly name for the action, set with `#[ineffable(pulse, label = "Fire Weapon")]`.
+    /// Defaults to the action_id.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::prelude::*;
+    /// # use bevy_ineffable::resources::meta_data::IneffableMetaData;
+    /// #[derive(InputAction)]
+    /// pub enum PlayerInput {
+    ///     #[ineffable(dual_axis, category = "Movement")]
+    ///     Walk,
+    ///     #[ineffable(pulse, label = "Fire Weapon", category = "Combat")]
+    ///     Fire,
+    /// }
+    /// fn build_controls_menu(meta_data: Res<IneffableMetaData>) {
+    ///     for action in meta_data.registered_actions() {
+    ///         info!("[{}] {}", action.category(), action.label());
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+    /// The section of a controls menu that the action belongs in, set with `#[ineffable(pulse, category = "Combat")]`.
+    /// Defaults to the group_id.
+    #[must_use]
+    pub fn category(&self) -> &str {
+        &self.category
+    }
 }
 
 impl IneffableMetaData {
+    /// Lists every registered action. Groups are sorted alphabetically by group_id, and the actions within each
+    /// group are in enum declaration order. Useful for building a controls menu that lets players remap everything.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::resources::meta_data::IneffableMetaData;
+    /// fn build_controls_menu(meta_data: Res<IneffableMetaData>) {
+    ///     for action in meta_data.registered_actions() {
+    ///         info!("{}::{} ({:?})", action.group_id(), action.action_id(), action.kind());
+    ///     }
+    /// }
+    /// ```
+    pub fn registered_actions(&self) -> impl Iterator<Item = &IneffableMetaItem> {
+        let mut group_ids: Vec<&String> = self.map.keys().collect();
+        group_ids.sort();
+        group_ids.into_iter().flat_map(|group_id| {
+            let mut group: Vec<&IneffableMetaItem> = self.map[group_id].iter().collect();
+            group.sort_by_key(|action| action.index);
+            group
+        })
+    }
+    /// Looks up the `InputKind` of the action with the given identifier.
+    /// Returns None if no such action was registered.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use bevy::prelude::*;
+    /// # use bevy_ineffable::input_action::ActionId;
+    /// # use bevy_ineffable::prelude::*;
+    /// # use bevy_ineffable::resources::meta_data::IneffableMetaData;
+    /// fn check_mod_action(meta_data: Res<IneffableMetaData>) {
+    ///     if meta_data.kind(&ActionId::new("ModAbilities", "Fireball")) != Some(InputKind::Pulse) {
+    ///         warn!("The Fireball action is missing, or is not a pulse action.");
+    ///     }
+    /// }
+    /// ```
+    #[must_use]
+    pub fn kind(&self, id: &ActionId) -> Option<InputKind> {
+        self.action(&id.group, &id.action).map(|action| action.kind)
+    }
     pub(crate) fn group_exists(&self, group_id: &str) -> bool {
         self.map.contains_key(group_id)
     }
     pub(crate) fn group(&self, group_id: &str) -> Option<&Vec<IneffableMetaItem>> {
         self.map.get(group_id)
     }
+    pub(crate) fn type_name(&self, group_id: &str) -> Option<&'static str> {
+        self.type_names.get(group_id).copied()
+    }
     pub(crate) fn group_ids(&self) -> Vec<String> {
-        self.map.keys().map(|key| (*key).to_string()).collect()
+        self.map.keys().cloned().collect()
     }
     pub(crate) fn action_ids(&self, group_id: &str) -> Vec<String> {
         self.map
@@ -43,4 +149,17 @@ impl IneffableMetaData {
     pub(crate) fn actions(&self, group_id: &str) -> &Vec<IneffableMetaItem> {
         self.map.get(group_id).expect("")
     }
+    /// Maps every registered group_id and action_id to the index of the action within its group.
+    pub(crate) fn action_indices(&self) -> HashMap<String, HashMap<String, usize>> {
+        self.map
+            .iter()
+            .map(|(group_id, group)| {
+                let indices = group
+                    .iter()
+                    .map(|action| (action.action_id.clone(), action.index))
+                    .collect();
+                (group_id.clone(), indices)
+            })
+            .collect()
+    }
 }
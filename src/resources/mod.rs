@@ -1,6 +1,15 @@
-pub use ineffable::Ineffable;
+pub use action_delta::{ActionDelta, ContinuousDelta};
+pub use action_descriptor::{ActionDescriptor, ActionValue};
+pub use ineffable::{AngleReference, Ineffable};
+pub use input_context::InputContext;
+pub use snapshot::InputSnapshot;
 
+mod action_delta;
+mod action_descriptor;
 mod ineffable;
 pub mod ineffable_settings;
-pub(crate) mod meta_data;
+mod input_context;
+pub mod key_labels;
+pub mod meta_data;
+mod snapshot;
 pub(crate) mod sources;
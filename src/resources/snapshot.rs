@@ -0,0 +1,22 @@
+use bevy::prelude::Reflect;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+
+use crate::processed::bound_action::ActionState;
+use crate::resources::ineffable_settings::PostAcceptanceDelay;
+
+/// A copy of the transient input state of every `InputAction` at a certain tick. Obtained from
+/// `IneffableCommands::snapshot()` and restored with `IneffableCommands::restore()`.
+///
+/// This includes toggle states, charge times, pulse timers, the current values of all actions and the
+/// post-acceptance-delay. It can be serialized, which makes it suitable for rollback netcode.
+///
+/// The state is stored per action, by group id and action id, and never includes the bindings themselves. This means
+/// that a snapshot can safely be restored after the player rebound some keys: the new bindings stay in place. Progress
+/// that belongs to individual bindings, such as a partially entered sequence or the travelled distance of a swipe, is
+/// not part of the snapshot.
+#[derive(Debug, Default, Serialize, Deserialize, Reflect, Clone)]
+pub struct InputSnapshot {
+    pub(crate) actions: HashMap<String, HashMap<String, ActionState>>,
+    pub(crate) post_acceptance_delay: Option<PostAcceptanceDelay>,
+}
@@ -12,6 +12,12 @@ pub struct IneffableEventSources {
     /// The distance in lines scrolled.
     /// When scrolling the mouse wheel, this tends to give values of one or two.
     pub mouse_scroll: Vec2,
+    /// The distance that the first finger on the touch screen moved since the last tick.
+    /// None if there is no finger on the touch screen.
+    pub touch_motion: Option<Vec2>,
+    /// The change in distance between the first two fingers on the touch screen since the last tick.
+    /// Positive when they spread apart.
+    pub touch_pinch: f32,
 }
 
 impl IneffableEventSources {
@@ -0,0 +1,106 @@
+//! Tests that the configs in the `assets` folder, which were written in older versions of the format, still load.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum CheatCodes {
+    #[ineffable(pulse)]
+    Password,
+    #[ineffable(pulse)]
+    KonamiCode,
+}
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(dual_axis)]
+    Movement,
+    #[ineffable(single_axis)]
+    Rotate,
+    #[ineffable(continuous)]
+    Blush,
+    #[ineffable(pulse)]
+    Teleport,
+}
+
+/// Parses a config, and checks that it has no errors for the actions that are registered in the app.
+fn load(app: &mut App, ron: &str) -> InputConfig {
+    let config: InputConfig = ron::from_str(ron).unwrap();
+    let validated = config.clone();
+    let report = app.commands(move |ineffable| ineffable.validate(&validated));
+    assert!(!report.has_errors(), "{report:?}");
+    config
+}
+
+#[test]
+fn cheat_codes_config_still_loads() {
+    let mut app = common::app::<CheatCodes>();
+    // The sequences in this config were written before there were strict sequences and per-step timeouts.
+    let config = load(&mut app, include_str!("../assets/cheat_codes.input.ron"));
+    let password = PulseBinding::sequence(KeyCode::KeyP)
+        .followed_by(KeyCode::KeyA)
+        .followed_by(KeyCode::KeyS)
+        .followed_by(KeyCode::KeyS)
+        .followed_by(KeyCode::KeyW)
+        .followed_by(KeyCode::KeyO)
+        .followed_by(KeyCode::KeyR)
+        .followed_by(KeyCode::KeyD)
+        .with_timing(Duration::from_millis(1000));
+    assert_eq!(
+        config.bindings["CheatCodes"]["Password"],
+        InputConfig::builder()
+            .bind(ineff!(CheatCodes::Password), password)
+            .build()
+            .bindings["CheatCodes"]["Password"]
+    );
+    app.set_config(&config);
+    // Tolerant by default: a wrong key halfway through doesn't break the sequence.
+    let mut pulsed = false;
+    for key in [
+        KeyCode::KeyP,
+        KeyCode::KeyA,
+        KeyCode::KeyX,
+        KeyCode::KeyS,
+        KeyCode::KeyS,
+        KeyCode::KeyW,
+        KeyCode::KeyO,
+        KeyCode::KeyR,
+        KeyCode::KeyD,
+    ] {
+        pulsed = app
+            .press(key)
+            .tick()
+            .ineffable()
+            .just_pulsed(ineff!(CheatCodes::Password));
+        app.release(key).tick();
+    }
+    assert!(pulsed);
+}
+
+#[test]
+fn basics_config_still_loads() {
+    let mut app = common::app::<PlayerInput>();
+    // The hold bindings in this config were written before they had an activation delay or could be inverted.
+    let config = load(&mut app, include_str!("../assets/basics.input.ron"));
+    app.set_config(&config);
+    app.press(KeyCode::ShiftLeft)
+        .press(KeyCode::ArrowRight)
+        .press(KeyCode::KeyW)
+        .press(KeyCode::Space)
+        .tick();
+    let ineffable = app.ineffable();
+    // Without an activation delay, holding Shift activates Blush right away.
+    assert!(ineffable.just_activated(ineff!(PlayerInput::Blush)));
+    assert_eq!(ineffable.direction_1d(ineff!(PlayerInput::Rotate)), 1.);
+    assert_eq!(
+        ineffable.direction_2d(ineff!(PlayerInput::Movement)),
+        Vec2::new(0., 1.)
+    );
+    assert!(ineffable.just_pulsed(ineff!(PlayerInput::Teleport)));
+}
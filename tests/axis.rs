@@ -0,0 +1,199 @@
+use bevy::prelude::*;
+use bevy_ineffable::bindings::AnalogInput;
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(dual_axis)]
+    Movement,
+    #[ineffable(single_axis)]
+    Steer,
+    #[ineffable(single_axis)]
+    Pitch,
+    #[ineffable(single_axis)]
+    InvertedPitch,
+    #[ineffable(single_axis)]
+    Throttle,
+}
+
+#[test]
+fn dpad_behaves_like_wasd() {
+    let (mut app, gamepad) = common::app_with_gamepad::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(ineff!(PlayerInput::Movement), DualAxisBinding::dpad())
+            .build(),
+    );
+    let mut movement = |buttons: &[GamepadButtonType]| {
+        let mut input = app.world_mut().resource_mut::<ButtonInput<GamepadButton>>();
+        input.release_all();
+        for button in buttons {
+            input.press(GamepadButton::new(gamepad, *button));
+        }
+        app.tick()
+            .ineffable()
+            .direction_2d(ineff!(PlayerInput::Movement))
+    };
+    assert_eq!(movement(&[GamepadButtonType::DPadUp]), Vec2::new(0., 1.));
+    assert_eq!(
+        movement(&[GamepadButtonType::DPadLeft, GamepadButtonType::DPadDown]),
+        Vec2::new(-1., -1.)
+    );
+    // Opposing directions cancel each other out.
+    assert_eq!(
+        movement(&[GamepadButtonType::DPadUp, GamepadButtonType::DPadDown]),
+        Vec2::ZERO
+    );
+}
+
+#[test]
+fn outer_dead_zone_rescales_a_worn_stick() {
+    let (mut app, gamepad) = common::app_with_gamepad::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Steer),
+                SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::LeftStickX))
+                    .set_outer_dead_zone(0.9)
+                    .build(),
+            )
+            .build(),
+    );
+    let mut steer = |raw: f32| {
+        app.set_gamepad_axis(gamepad, GamepadAxisType::LeftStickX, raw)
+            .tick()
+            .ineffable()
+            .direction_1d(ineff!(PlayerInput::Steer))
+    };
+    // The worn stick only reaches 0.9, but that now counts as full deflection.
+    assert_eq!(steer(0.9), 1.0);
+    assert_eq!(steer(-0.45), -0.5);
+}
+
+#[test]
+fn inverted_hold_swaps_directions() {
+    let mut app = common::app::<PlayerInput>();
+    let pitch = || {
+        SingleAxisBinding::hold()
+            .set_negative(KeyCode::KeyS)
+            .set_positive(KeyCode::KeyW)
+    };
+    app.set_config(
+        &InputConfig::builder()
+            .bind(ineff!(PlayerInput::Pitch), pitch().build())
+            .bind(ineff!(PlayerInput::InvertedPitch), pitch().invert().build())
+            .build(),
+    );
+    app.press(KeyCode::KeyW).tick();
+    let ineffable = app.ineffable();
+    assert_eq!(ineffable.direction_1d(ineff!(PlayerInput::Pitch)), 1.);
+    assert_eq!(
+        ineffable.direction_1d(ineff!(PlayerInput::InvertedPitch)),
+        -1.
+    );
+}
+
+#[test]
+fn accumulate_integrates_scrolling_and_decays() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Throttle),
+                SingleAxisBinding::accumulate(AnalogInput::ScrollWheelY)
+                    .set_sensitivity(0.25)
+                    .set_clamp(0., 1.)
+                    .set_decay(1.)
+                    .build(),
+            )
+            .build(),
+    );
+    let mut scroll = |y: f32| {
+        app.scroll(y)
+            .tick()
+            .ineffable()
+            .direction_1d(ineff!(PlayerInput::Throttle))
+    };
+    // Every tick takes 50 milliseconds, so the throttle decays by 0.05 per tick.
+    assert_eq!(scroll(2.), 0.5);
+    assert!((scroll(0.) - 0.45).abs() < 1e-6);
+    // The throttle can't exceed its bounds.
+    assert_eq!(scroll(10.), 1.);
+    assert_eq!(scroll(-10.), 0.);
+}
+
+#[test]
+fn accumulate_accepts_bounds_in_either_order() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .global_sensitivity(2.)
+            .bind(
+                ineff!(PlayerInput::Throttle),
+                SingleAxisBinding::accumulate(AnalogInput::ScrollWheelY)
+                    .set_sensitivity(0.25)
+                    .set_clamp(1., 0.)
+                    .build(),
+            )
+            .build(),
+    );
+    let mut scroll = |y: f32| {
+        app.scroll(y)
+            .tick()
+            .ineffable()
+            .direction_1d(ineff!(PlayerInput::Throttle))
+    };
+    // The global sensitivity doubles every notch.
+    assert_eq!(scroll(1.), 0.5);
+    assert_eq!(scroll(10.), 1.);
+    assert_eq!(scroll(-10.), 0.);
+}
+
+#[test]
+fn hold_without_inversion_still_loads() {
+    // This is how hold bindings were written before they could be inverted, for example in `basics.input.ron`.
+    let config: InputConfig = ron::from_str(
+        r#"( bindings: { "PlayerInput": { "Steer": [ SingleAxis(Hold([Key(KeyA)], [Key(KeyD)])) ] } } )"#,
+    )
+    .unwrap();
+    let round_trip: InputConfig = ron::from_str(&ron::to_string(&config).unwrap()).unwrap();
+    assert_eq!(round_trip, config);
+
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(&config);
+    app.press(KeyCode::KeyD).tick();
+    assert_eq!(app.ineffable().direction_1d(ineff!(PlayerInput::Steer)), 1.);
+}
+
+#[test]
+fn inverting_an_action_also_inverts_holds_and_toggles() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Pitch),
+                SingleAxisBinding::hold()
+                    .set_negative(KeyCode::KeyS)
+                    .set_positive(KeyCode::KeyW)
+                    .build(),
+            )
+            .bind(
+                ineff!(PlayerInput::Throttle),
+                SingleAxisBinding::toggle()
+                    .set_negative(PulseBinding::just_pressed(KeyCode::KeyQ))
+                    .set_positive(PulseBinding::just_pressed(KeyCode::KeyE))
+                    .build(),
+            )
+            .invert_for(ineff!(PlayerInput::Pitch), true)
+            .invert_for(ineff!(PlayerInput::Throttle), true)
+            .build(),
+    );
+    app.press(KeyCode::KeyW).press(KeyCode::KeyE).tick();
+    let ineffable = app.ineffable();
+    assert_eq!(ineffable.direction_1d(ineff!(PlayerInput::Pitch)), -1.);
+    assert_eq!(ineffable.direction_1d(ineff!(PlayerInput::Throttle)), -1.);
+}
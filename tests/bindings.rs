@@ -0,0 +1,120 @@
+use bevy::input::touch::{TouchInput, TouchPhase};
+use bevy::prelude::*;
+use bevy_ineffable::bindings::{AnalogInput, KeyGroup};
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum EditorInput {
+    #[ineffable(pulse)]
+    Save,
+    #[ineffable(single_axis)]
+    Precision,
+    #[ineffable(single_axis)]
+    Zoom,
+}
+
+#[test]
+fn chord_needs_every_input() {
+    let mut app = common::app::<EditorInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(EditorInput::Save),
+                PulseBinding::just_pressed((KeyCode::ControlLeft, KeyCode::KeyS)),
+            )
+            .build(),
+    );
+    app.press(KeyCode::KeyS).tick();
+    assert!(!app.ineffable().just_pulsed(ineff!(EditorInput::Save)));
+    app.press(KeyCode::ControlLeft).tick();
+    assert!(app.ineffable().just_pulsed(ineff!(EditorInput::Save)));
+}
+
+#[test]
+fn chords_of_several_keys_load_from_ron() {
+    let mut app = common::app::<EditorInput>();
+    let config = InputConfig::builder()
+        .bind(
+            ineff!(EditorInput::Save),
+            PulseBinding::just_pressed((KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::KeyS)),
+        )
+        .build();
+    // Chords are still written as a plain list.
+    let ron = ron::to_string(&config).unwrap();
+    assert!(ron.contains("[Key(ControlLeft),Key(ShiftLeft),Key(KeyS)]"));
+    let loaded: InputConfig = ron::from_str(&ron).unwrap();
+    assert_eq!(loaded, config);
+
+    app.set_config(&loaded);
+    app.press(KeyCode::ControlLeft).press(KeyCode::KeyS).tick();
+    assert!(!app.ineffable().just_pulsed(ineff!(EditorInput::Save)));
+    app.press(KeyCode::ShiftLeft).tick();
+    assert!(app.ineffable().just_pulsed(ineff!(EditorInput::Save)));
+}
+
+#[test]
+fn key_group_matches_either_side_of_the_keyboard() {
+    let mut app = common::app::<EditorInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(EditorInput::Precision),
+                SingleAxisBinding::hold()
+                    .set_negative(KeyGroup::Shift)
+                    .set_positive(KeyGroup::Alt)
+                    .build(),
+            )
+            .build(),
+    );
+    let mut precision = |keys: &[KeyCode]| {
+        app.hold_only(keys)
+            .tick()
+            .ineffable()
+            .direction_1d(ineff!(EditorInput::Precision))
+    };
+    assert_eq!(precision(&[]), 0.);
+    assert_eq!(precision(&[KeyCode::ShiftLeft]), -1.);
+    assert_eq!(precision(&[KeyCode::ShiftRight]), -1.);
+    assert_eq!(precision(&[KeyCode::AltRight]), 1.);
+    assert_eq!(precision(&[KeyCode::ShiftLeft, KeyCode::AltLeft]), 0.);
+}
+
+#[test]
+fn touch_pinch_reports_the_change_in_distance() {
+    let mut app = common::app_with_input_plugin::<EditorInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(EditorInput::Zoom),
+                SingleAxisBinding::analog(AnalogInput::TouchPinch).build(),
+            )
+            .build(),
+    );
+    let mut touch = |id: u64, x: f32, phase: TouchPhase| {
+        app.world_mut().send_event(TouchInput {
+            phase,
+            position: Vec2::new(x, 0.),
+            window: Entity::PLACEHOLDER,
+            force: None,
+            id,
+        });
+        app.tick()
+            .ineffable()
+            .direction_1d(ineff!(EditorInput::Zoom))
+    };
+    touch(0, 100., TouchPhase::Started);
+    // The second finger landing far away from the first is not a pinch.
+    assert_eq!(touch(1, 300., TouchPhase::Started), 0.);
+    assert_eq!(touch(1, 340., TouchPhase::Moved), 40.);
+    // Holding still stops zooming.
+    assert_eq!(
+        app.tick()
+            .ineffable()
+            .direction_1d(ineff!(EditorInput::Zoom)),
+        0.
+    );
+}
@@ -0,0 +1,267 @@
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::{ActionDelta, ContinuousDelta};
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(dual_axis)]
+    Walk,
+    #[ineffable(single_axis)]
+    Turn,
+    #[ineffable(continuous)]
+    Sprint,
+    #[ineffable(continuous)]
+    Crouch,
+    #[ineffable(pulse)]
+    Jump,
+}
+
+#[test]
+fn rejected_config_keeps_the_previous_bindings() {
+    let mut app = common::app::<PlayerInput>();
+    let config = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Jump),
+            PulseBinding::just_pressed(KeyCode::Space),
+        )
+        .build();
+    // This config binds the pulse action as if it were a continuous action.
+    let broken: InputConfig = ron::from_str(
+        r#"( bindings: { "PlayerInput": { "Jump": [ Continuous(Hold([Key(Enter)])) ] } } )"#,
+    )
+    .unwrap();
+    app.commands(move |mut ineffable| {
+        assert!(ineffable.try_set_config(&config).is_ok());
+        let report = ineffable.try_set_config(&broken).unwrap_err();
+        assert!(report.has_errors());
+    });
+    app.press(KeyCode::Space).tick();
+    assert!(app.ineffable().just_pulsed(ineff!(PlayerInput::Jump)));
+}
+
+#[test]
+fn activate_profile_switches_bindings() {
+    let mut app = common::app::<PlayerInput>();
+    let config: InputConfig = ron::from_str(
+        r#"(
+            bindings: { "PlayerInput": { "Jump": [ Pulse(JustPressed([Key(Space)])) ] } },
+            profiles: {
+                "Lefty": ( bindings: { "PlayerInput": { "Jump": [ Pulse(JustPressed([Key(Enter)])) ] } } ),
+            },
+        )"#,
+    )
+    .unwrap();
+    app.set_config(&config);
+    app.commands(|mut ineffable| {
+        assert!(ineffable.activate_profile("Lefty").is_some());
+        assert!(ineffable.activate_profile("Claw").is_none());
+    });
+    app.press(KeyCode::Enter).tick();
+    let ineffable = app.ineffable();
+    assert_eq!(ineffable.active_profile(), Some("Lefty"));
+    assert!(ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+    assert_eq!(
+        ineffable.current_config(),
+        config.with_profile("Lefty").as_ref()
+    );
+}
+
+#[test]
+fn reset_to_defaults_restores_the_default_config() {
+    let mut app = common::app::<PlayerInput>();
+    let defaults = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Sprint),
+            ContinuousBinding::hold(KeyCode::ShiftLeft),
+        )
+        .build();
+    let remapped = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Sprint),
+            ContinuousBinding::hold(KeyCode::KeyR),
+        )
+        .build();
+    let merged = defaults.merge_replace(&remapped);
+    app.commands(move |mut ineffable| {
+        ineffable.set_default_config(&defaults);
+        ineffable.set_config(&merged);
+        // The player later presses the "reset to defaults" button:
+        ineffable.reset_to_defaults();
+    });
+    // Sprinting with Shift works again.
+    app.press(KeyCode::ShiftLeft).tick();
+    let ineffable = app.ineffable();
+    assert!(ineffable.is_active(ineff!(PlayerInput::Sprint)));
+    assert_eq!(ineffable.current_config(), ineffable.default_config());
+}
+
+#[test]
+fn reset_state_turns_toggles_off() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Crouch),
+                ContinuousBinding::toggle(PulseBinding::just_pressed(KeyCode::KeyC)),
+            )
+            .build(),
+    );
+    let crouching = |app: &App| app.ineffable().is_active(ineff!(PlayerInput::Crouch));
+    app.press(KeyCode::KeyC).tick();
+    assert!(crouching(&app));
+
+    // The player loads a save game.
+    app.commands(|mut ineffable| ineffable.reset_state());
+    assert!(!crouching(&app));
+    // Still holding C doesn't toggle crouching again.
+    app.tick();
+    assert!(!crouching(&app));
+}
+
+#[test]
+fn disabled_actions_are_inactive() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .build(),
+    );
+    let set_enabled = |app: &mut App, enabled: bool| {
+        app.commands(move |mut ineffable| {
+            ineffable.set_action_enabled(ineff!(PlayerInput::Sprint), enabled);
+        });
+    };
+    let sprint = |app: &App| {
+        let ineffable = app.ineffable();
+        (
+            ineffable.is_active(ineff!(PlayerInput::Sprint)),
+            ineffable.just_activated(ineff!(PlayerInput::Sprint)),
+            ineffable.just_deactivated(ineff!(PlayerInput::Sprint)),
+        )
+    };
+    app.press(KeyCode::ShiftLeft).tick();
+    assert_eq!(sprint(&app), (true, true, false));
+
+    set_enabled(&mut app, false);
+    assert!(!app
+        .ineffable()
+        .is_action_enabled(ineff!(PlayerInput::Sprint)));
+    app.tick();
+    assert_eq!(sprint(&app), (false, false, false));
+
+    // Still holding shift: re-enabling doesn't count as a fresh activation.
+    set_enabled(&mut app, true);
+    app.tick();
+    assert_eq!(sprint(&app), (true, false, false));
+}
+
+#[test]
+fn apply_delta_changes_the_state_until_the_next_update() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(ineff!(PlayerInput::Walk), DualAxisBinding::dpad())
+            .bind(
+                ineff!(PlayerInput::Turn),
+                SingleAxisBinding::hold()
+                    .set_negative(KeyCode::KeyA)
+                    .build(),
+            )
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .build(),
+    );
+    let apply = |app: &mut App, action_id: &'static str, delta: ActionDelta| {
+        app.commands(move |mut ineffable| ineffable.apply_delta("PlayerInput", action_id, delta))
+    };
+    app.tick();
+    assert!(apply(
+        &mut app,
+        "Walk",
+        ActionDelta::DualAxis(Vec2::new(0., 1.))
+    ));
+    assert!(apply(&mut app, "Turn", ActionDelta::SingleAxis(-0.5)));
+    assert!(apply(
+        &mut app,
+        "Sprint",
+        ActionDelta::Continuous(ContinuousDelta::Start)
+    ));
+    assert!(apply(&mut app, "Jump", ActionDelta::Pulse));
+
+    let ineffable = app.ineffable();
+    assert_eq!(
+        ineffable.direction_2d(ineff!(PlayerInput::Walk)),
+        Vec2::new(0., 1.)
+    );
+    assert_eq!(ineffable.direction_1d(ineff!(PlayerInput::Turn)), -0.5);
+    assert!(ineffable.just_activated(ineff!(PlayerInput::Sprint)));
+    assert!(ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+
+    // The delta must match the kind of the action.
+    assert!(!apply(&mut app, "Jump", ActionDelta::SingleAxis(1.)));
+    assert!(!apply(&mut app, "Prone", ActionDelta::Pulse));
+
+    // The next update overwrites the deltas with the state of the bindings.
+    app.tick();
+    let ineffable = app.ineffable();
+    assert_eq!(
+        ineffable.direction_2d(ineff!(PlayerInput::Walk)),
+        Vec2::ZERO
+    );
+    assert_eq!(ineffable.direction_1d(ineff!(PlayerInput::Turn)), 0.);
+    assert!(ineffable.just_deactivated(ineff!(PlayerInput::Sprint)));
+    assert!(!ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+
+    assert!(apply(
+        &mut app,
+        "Sprint",
+        ActionDelta::Continuous(ContinuousDelta::End)
+    ));
+    assert!(!app.ineffable().is_active(ineff!(PlayerInput::Sprint)));
+}
+
+#[test]
+fn reset_state_keeps_held_actions_active() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .build(),
+    );
+    let sprint = |app: &App| {
+        let ineffable = app.ineffable();
+        (
+            ineffable.is_active(ineff!(PlayerInput::Sprint)),
+            ineffable.just_activated(ineff!(PlayerInput::Sprint)),
+        )
+    };
+    app.press(KeyCode::ShiftLeft).ticks(3);
+    assert_eq!(sprint(&app), (true, false));
+
+    // Shift is held down throughout the reset.
+    app.commands(|mut ineffable| ineffable.reset_state());
+    assert_eq!(sprint(&app), (true, false));
+    app.tick();
+    assert_eq!(sprint(&app), (true, false));
+    // The charge time starts over.
+    assert_eq!(
+        app.ineffable().charge_time(ineff!(PlayerInput::Sprint)),
+        Some(common::TICK)
+    );
+}
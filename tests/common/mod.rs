@@ -0,0 +1,188 @@
+//! Shared setup for the integration tests. Every test builds a headless app on top of the `IneffableMinimalPlugin`,
+//! simulates input by writing directly to bevy's input resources, and reads the results from the `Ineffable` resource.
+
+// Not every test file uses every helper.
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo};
+use bevy::input::mouse::{MouseMotion, MouseScrollUnit, MouseWheel};
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy::time::TimeUpdateStrategy;
+use bevy_ineffable::prelude::*;
+
+/// How much time passes during every update of a test app.
+pub const TICK: Duration = Duration::from_millis(50);
+
+/// Creates a headless app in which every update takes exactly one `TICK`, and registers the given actions.
+pub fn app<I: InputAction>() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, IneffableMinimalPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+        .register_input_action::<I>();
+    // The very first update doesn't advance time, so get it out of the way.
+    app.update();
+    app
+}
+
+/// Same as `app()`, but also adds bevy's `InputPlugin`, for tests that need touches or gamepads.
+pub fn app_with_input_plugin<I: InputAction>() -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, IneffableMinimalPlugin))
+        .insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+        .register_input_action::<I>();
+    app.update();
+    app
+}
+
+/// Same as `app_with_input_plugin()`, but also connects a single gamepad, which is returned.
+pub fn app_with_gamepad<I: InputAction>() -> (App, Gamepad) {
+    let mut app = app_with_input_plugin::<I>();
+    let gamepad = Gamepad::new(0);
+    let info = GamepadInfo {
+        name: String::from("Test gamepad"),
+    };
+    app.world_mut().send_event(GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Connected(info),
+    ));
+    app.update();
+    (app, gamepad)
+}
+
+/// Convenience methods for driving a test app.
+pub trait TestApp {
+    /// Applies the config, panicking if it is rejected.
+    fn set_config(&mut self, config: &InputConfig) -> &mut Self;
+    /// Gives access to `IneffableCommands`, for anything that isn't covered by the other helpers.
+    fn commands<T>(&mut self, f: impl FnOnce(IneffableCommands) -> T + Send + Sync + 'static) -> T
+    where
+        T: Send + 'static;
+    fn press(&mut self, key: KeyCode) -> &mut Self;
+    fn release(&mut self, key: KeyCode) -> &mut Self;
+    /// Releases all keys, then presses only the given ones.
+    fn hold_only(&mut self, keys: &[KeyCode]) -> &mut Self;
+    fn press_mouse(&mut self, button: MouseButton) -> &mut Self;
+    fn release_mouse(&mut self, button: MouseButton) -> &mut Self;
+    fn press_gamepad(&mut self, gamepad: Gamepad, button: GamepadButtonType) -> &mut Self;
+    fn release_gamepad(&mut self, gamepad: Gamepad, button: GamepadButtonType) -> &mut Self;
+    fn set_gamepad_axis(
+        &mut self,
+        gamepad: Gamepad,
+        axis: GamepadAxisType,
+        value: f32,
+    ) -> &mut Self;
+    fn scroll(&mut self, y: f32) -> &mut Self;
+    fn move_mouse(&mut self, delta: Vec2) -> &mut Self;
+    /// Runs a single update.
+    fn tick(&mut self) -> &mut Self;
+    /// Runs the given number of updates.
+    fn ticks(&mut self, count: usize) -> &mut Self;
+    fn ineffable(&self) -> &Ineffable;
+}
+
+impl TestApp for App {
+    fn set_config(&mut self, config: &InputConfig) -> &mut Self {
+        let config = config.clone();
+        self.commands(move |mut ineffable| {
+            if let Err(report) = ineffable.try_set_config(&config) {
+                panic!("The config was rejected: {report:?}");
+            }
+        });
+        self
+    }
+    fn commands<T>(&mut self, f: impl FnOnce(IneffableCommands) -> T + Send + Sync + 'static) -> T
+    where
+        T: Send + 'static,
+    {
+        let mut f = Some(f);
+        self.world_mut()
+            .run_system_once(move |ineffable: IneffableCommands| {
+                (f.take().expect("Only runs once."))(ineffable)
+            })
+    }
+    fn press(&mut self, key: KeyCode) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        self
+    }
+    fn release(&mut self, key: KeyCode) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .release(key);
+        self
+    }
+    fn hold_only(&mut self, keys: &[KeyCode]) -> &mut Self {
+        let mut input = self.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        input.release_all();
+        for key in keys {
+            input.press(*key);
+        }
+        self
+    }
+    fn press_mouse(&mut self, button: MouseButton) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .press(button);
+        self
+    }
+    fn release_mouse(&mut self, button: MouseButton) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<MouseButton>>()
+            .release(button);
+        self
+    }
+    fn press_gamepad(&mut self, gamepad: Gamepad, button: GamepadButtonType) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<GamepadButton>>()
+            .press(GamepadButton::new(gamepad, button));
+        self
+    }
+    fn release_gamepad(&mut self, gamepad: Gamepad, button: GamepadButtonType) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<ButtonInput<GamepadButton>>()
+            .release(GamepadButton::new(gamepad, button));
+        self
+    }
+    fn set_gamepad_axis(
+        &mut self,
+        gamepad: Gamepad,
+        axis: GamepadAxisType,
+        value: f32,
+    ) -> &mut Self {
+        self.world_mut()
+            .resource_mut::<Axis<GamepadAxis>>()
+            .set(GamepadAxis::new(gamepad, axis), value);
+        self
+    }
+    fn scroll(&mut self, y: f32) -> &mut Self {
+        self.world_mut().send_event(MouseWheel {
+            unit: MouseScrollUnit::Line,
+            x: 0.,
+            y,
+            window: Entity::PLACEHOLDER,
+        });
+        self
+    }
+    fn move_mouse(&mut self, delta: Vec2) -> &mut Self {
+        self.world_mut().send_event(MouseMotion { delta });
+        self
+    }
+    fn tick(&mut self) -> &mut Self {
+        self.update();
+        self
+    }
+    fn ticks(&mut self, count: usize) -> &mut Self {
+        for _ in 0..count {
+            self.update();
+        }
+        self
+    }
+    fn ineffable(&self) -> &Ineffable {
+        self.world().resource::<Ineffable>()
+    }
+}
@@ -0,0 +1,244 @@
+use bevy::prelude::*;
+use bevy_ineffable::bindings::{AnalogInput, InputDeviceCategory, KeyGroup, Threshold};
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(pulse)]
+    Jump,
+    #[ineffable(continuous)]
+    Crouch,
+    #[ineffable(single_axis)]
+    Look,
+    #[ineffable(pulse)]
+    NextWeapon,
+    #[ineffable(pulse)]
+    SkipCutscene,
+}
+
+#[derive(InputAction)]
+pub enum MenuInput {
+    #[ineffable(pulse)]
+    Confirm,
+    #[ineffable(continuous)]
+    Modifier,
+    #[ineffable(single_axis)]
+    Scroll,
+    #[ineffable(pulse)]
+    NextTab,
+}
+
+/// Creates an app with both groups registered and the given config, with the menu on top of the context stack.
+fn app_with_menu(config: &InputConfig) -> App {
+    let mut app = common::app::<PlayerInput>();
+    app.register_input_action::<MenuInput>();
+    app.set_config(config);
+    app.world_mut()
+        .resource_mut::<Ineffable>()
+        .push_context("Menu", ["MenuInput"]);
+    app
+}
+
+#[test]
+fn top_context_consumes_shared_inputs() {
+    let mut app = common::app::<PlayerInput>();
+    app.register_input_action::<MenuInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .bind(
+                ineff!(MenuInput::Confirm),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .build(),
+    );
+    // The menu opens:
+    app.world_mut()
+        .resource_mut::<Ineffable>()
+        .push_context("Menu", ["MenuInput"]);
+
+    app.press(KeyCode::Space).tick();
+    let ineffable = app.ineffable();
+    assert!(ineffable.just_pulsed(ineff!(MenuInput::Confirm)));
+    assert!(!ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+}
+
+#[test]
+fn key_groups_consume_the_keys_that_are_held_down() {
+    let mut app = app_with_menu(
+        &InputConfig::builder()
+            .bind(
+                ineff!(MenuInput::Confirm),
+                PulseBinding::just_pressed(KeyGroup::Enter),
+            )
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Enter),
+            )
+            .bind(
+                ineff!(MenuInput::Modifier),
+                ContinuousBinding::hold(KeyCode::ControlLeft),
+            )
+            .bind(
+                ineff!(PlayerInput::Crouch),
+                ContinuousBinding::hold(KeyGroup::Control),
+            )
+            .build(),
+    );
+    app.press(KeyCode::Enter).tick();
+    let ineffable = app.ineffable();
+    assert!(ineffable.just_pulsed(ineff!(MenuInput::Confirm)));
+    assert!(!ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+
+    // The left control key is consumed by the menu, but the right one is still free.
+    let crouching = |app: &mut App, keys: &[KeyCode]| {
+        app.hold_only(keys)
+            .tick()
+            .ineffable()
+            .is_active(ineff!(PlayerInput::Crouch))
+    };
+    assert!(!crouching(&mut app, &[KeyCode::ControlLeft]));
+    assert!(!crouching(&mut app, &[]));
+    assert!(crouching(&mut app, &[KeyCode::ControlRight]));
+}
+
+#[test]
+fn analog_inputs_are_consumed_while_they_move() {
+    let mut app = app_with_menu(
+        &InputConfig::builder()
+            .bind(
+                ineff!(MenuInput::Scroll),
+                SingleAxisBinding::analog(AnalogInput::MouseMotionY).build(),
+            )
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::analog(AnalogInput::MouseMotionY).build(),
+            )
+            .bind(
+                ineff!(MenuInput::NextTab),
+                PulseBinding::notches(AnalogInput::ScrollWheelY, Threshold::new(1.)),
+            )
+            .bind(
+                ineff!(PlayerInput::NextWeapon),
+                PulseBinding::notches(AnalogInput::ScrollWheelY, Threshold::new(1.)),
+            )
+            .build(),
+    );
+    app.move_mouse(Vec2::new(0., 10.)).scroll(2.).tick();
+    let ineffable = app.ineffable();
+    assert_eq!(ineffable.direction_1d(ineff!(MenuInput::Scroll)), 10.);
+    assert_eq!(ineffable.direction_1d(ineff!(PlayerInput::Look)), 0.);
+    assert_eq!(ineffable.pulse_count(ineff!(MenuInput::NextTab)), 2);
+    assert_eq!(ineffable.pulse_count(ineff!(PlayerInput::NextWeapon)), 0);
+}
+
+#[test]
+fn removing_a_context_does_not_press_held_keys() {
+    let mut app = app_with_menu(
+        &InputConfig::builder()
+            .bind(
+                ineff!(MenuInput::Confirm),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .bind(
+                ineff!(PlayerInput::Crouch),
+                ContinuousBinding::hold(KeyCode::Space),
+            )
+            .build(),
+    );
+    // Confirming closes the menu, but the player keeps holding space a little longer.
+    app.press(KeyCode::Space).tick();
+    assert!(app.ineffable().just_pulsed(ineff!(MenuInput::Confirm)));
+    app.world_mut()
+        .resource_mut::<Ineffable>()
+        .remove_context("Menu");
+    app.tick();
+    let ineffable = app.ineffable();
+    assert!(!ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+    assert!(!ineffable.is_active(ineff!(PlayerInput::Crouch)));
+
+    // Pressing it again works as usual.
+    app.release(KeyCode::Space).tick();
+    app.press(KeyCode::Space).tick();
+    let ineffable = app.ineffable();
+    assert!(ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+    assert!(ineffable.just_activated(ineff!(PlayerInput::Crouch)));
+}
+
+#[test]
+fn disabled_actions_do_not_consume_inputs() {
+    let mut app = app_with_menu(
+        &InputConfig::builder()
+            .post_acceptance_delay(std::time::Duration::from_millis(200))
+            .ignore_post_acceptance_delay_for(ineff!(PlayerInput::Crouch), true)
+            .bind(
+                ineff!(MenuInput::Modifier),
+                ContinuousBinding::hold(KeyCode::Space),
+            )
+            .bind(
+                ineff!(PlayerInput::Crouch),
+                ContinuousBinding::hold(KeyCode::Space),
+            )
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Enter),
+            )
+            .build(),
+    );
+    app.commands(|mut ineffable| {
+        ineffable.set_action_enabled(ineff!(MenuInput::Modifier), false);
+    });
+    // The disabled action neither takes space away from the player, nor starts the post-acceptance-delay.
+    app.press(KeyCode::Space).tick();
+    app.press(KeyCode::Enter).tick();
+    let ineffable = app.ineffable();
+    assert!(ineffable.is_active(ineff!(PlayerInput::Crouch)));
+    assert!(ineffable.just_pulsed(ineff!(PlayerInput::Jump)));
+    let modifier = ineffable
+        .iter_actions()
+        .find(|action| action.action_id == "Modifier")
+        .unwrap();
+    assert_eq!(
+        modifier.value,
+        bevy_ineffable::resources::ActionValue::Continuous(false)
+    );
+}
+
+#[test]
+fn any_input_ignores_consumed_inputs() {
+    let mut app = app_with_menu(
+        &InputConfig::builder()
+            .bind(
+                ineff!(MenuInput::Confirm),
+                PulseBinding::just_pressed(KeyCode::Enter),
+            )
+            .bind(
+                ineff!(PlayerInput::SkipCutscene),
+                PulseBinding::any_input(InputDeviceCategory::All),
+            )
+            .build(),
+    );
+    let mut tap = |key: KeyCode| {
+        let ineffable = app.press(key).tick().ineffable();
+        let pulses = (
+            ineffable.just_pulsed(ineff!(MenuInput::Confirm)),
+            ineffable.just_pulsed(ineff!(PlayerInput::SkipCutscene)),
+        );
+        app.release(key).tick();
+        pulses
+    };
+    // Confirming in the menu doesn't also skip the cutscene behind it.
+    assert_eq!(tap(KeyCode::Enter), (true, false));
+    assert_eq!(tap(KeyCode::KeyX), (false, true));
+}
@@ -0,0 +1,110 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(continuous)]
+    Sprint,
+    #[ineffable(continuous)]
+    Jump,
+}
+
+#[test]
+fn activation_delay_filters_out_brief_taps() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold_builder(KeyCode::ShiftLeft)
+                    .activation_delay(Duration::from_millis(100))
+                    .build(),
+            )
+            .build(),
+    );
+    let mut sprint = |held: bool| {
+        let ineffable = app
+            .hold_only(if held { &[KeyCode::ShiftLeft] } else { &[] })
+            .tick()
+            .ineffable();
+        (
+            ineffable.is_active(ineff!(PlayerInput::Sprint)),
+            ineffable.just_activated(ineff!(PlayerInput::Sprint)),
+        )
+    };
+    // Every tick takes 50 milliseconds. Returns (is_active, just_activated).
+    assert_eq!(sprint(true), (false, false));
+    assert_eq!(sprint(true), (true, true));
+    assert_eq!(sprint(true), (true, false));
+    // Deactivates immediately on release.
+    assert_eq!(sprint(false), (false, false));
+    // A brief tap never activates.
+    assert_eq!(sprint(true), (false, false));
+    assert_eq!(sprint(false), (false, false));
+}
+
+#[test]
+fn release_grace_keeps_the_action_active_for_a_while() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Jump),
+                ContinuousBinding::hold_builder(KeyCode::Space)
+                    .release_grace(Duration::from_millis(100))
+                    .build(),
+            )
+            .build(),
+    );
+    let mut jump = |held: bool| {
+        let ineffable = app
+            .hold_only(if held { &[KeyCode::Space] } else { &[] })
+            .tick()
+            .ineffable();
+        (
+            ineffable.is_active(ineff!(PlayerInput::Jump)),
+            ineffable.just_deactivated(ineff!(PlayerInput::Jump)),
+        )
+    };
+    // Every tick takes 50 milliseconds. Returns (is_active, just_deactivated).
+    assert_eq!(jump(true), (true, false));
+    // Still active for a while after release.
+    assert_eq!(jump(false), (true, false));
+    // Pressing again during the grace period cancels it.
+    assert_eq!(jump(true), (true, false));
+    assert_eq!(jump(false), (true, false));
+    assert_eq!(jump(false), (false, true));
+}
+
+#[test]
+fn charge_time_is_kept_for_the_tick_the_action_deactivates() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Jump),
+                ContinuousBinding::hold(KeyCode::Space),
+            )
+            .build(),
+    );
+    let mut charge = |keys: &[KeyCode]| {
+        app.hold_only(keys)
+            .tick()
+            .ineffable()
+            .charge_time(ineff!(PlayerInput::Jump))
+    };
+    // Every tick takes 50 milliseconds.
+    assert_eq!(charge(&[KeyCode::Space]), Some(common::TICK));
+    assert_eq!(charge(&[KeyCode::Space]), Some(2 * common::TICK));
+    // Letting go still reports the full charge, for one tick.
+    assert_eq!(charge(&[]), Some(2 * common::TICK));
+    assert_eq!(charge(&[]), None);
+    // Without a retain window, the next press starts over.
+    assert_eq!(charge(&[KeyCode::Space]), Some(common::TICK));
+}
@@ -0,0 +1,204 @@
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo};
+use bevy::prelude::*;
+use bevy_ineffable::bindings::AnalogInput;
+use bevy_ineffable::events::GamepadConnectionChanged;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::{ActionDescriptor, ActionValue, AngleReference};
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(single_axis)]
+    Walk,
+    #[ineffable(single_axis)]
+    Throttle,
+    #[ineffable(dual_axis)]
+    Aim,
+}
+
+#[derive(InputAction)]
+pub enum CharacterInput {
+    #[ineffable(continuous)]
+    Sprint,
+    #[ineffable(pulse)]
+    Jump,
+}
+
+#[test]
+fn connected_gamepads_follow_connection_events() {
+    let mut app = common::app_with_input_plugin::<PlayerInput>();
+    let mut reader = app
+        .world()
+        .resource::<Events<GamepadConnectionChanged>>()
+        .get_reader();
+    // Events only live for two ticks, so read them every time.
+    let mut read_changes = |app: &App| -> Vec<_> {
+        let events = app.world().resource::<Events<GamepadConnectionChanged>>();
+        reader.read(events).copied().collect()
+    };
+    let gamepad = Gamepad::new(0);
+    let info = GamepadInfo {
+        name: String::from("Test gamepad"),
+    };
+    app.world_mut().send_event(GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Connected(info),
+    ));
+    app.ticks(2);
+    assert_eq!(app.ineffable().connected_gamepads(), &[gamepad]);
+    let mut changes = read_changes(&app);
+
+    app.world_mut().send_event(GamepadConnectionEvent::new(
+        gamepad,
+        GamepadConnection::Disconnected,
+    ));
+    app.ticks(2);
+    assert!(app.ineffable().connected_gamepads().is_empty());
+    changes.extend(read_changes(&app));
+    assert_eq!(
+        changes,
+        vec![
+            GamepadConnectionChanged::Connected(gamepad),
+            GamepadConnectionChanged::Disconnected(gamepad)
+        ]
+    );
+}
+
+#[test]
+fn iter_actions_reports_current_values() {
+    let mut app = common::app::<CharacterInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(CharacterInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .bind(
+                ineff!(CharacterInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .build(),
+    );
+    app.press(KeyCode::ShiftLeft).tick();
+    let actions: Vec<ActionDescriptor<'_>> = app.ineffable().iter_actions().collect();
+    assert_eq!(
+        actions,
+        vec![
+            ActionDescriptor {
+                group_id: "CharacterInput",
+                action_id: "Sprint",
+                kind: InputKind::Continuous,
+                value: ActionValue::Continuous(true),
+            },
+            ActionDescriptor {
+                group_id: "CharacterInput",
+                action_id: "Jump",
+                kind: InputKind::Pulse,
+                value: ActionValue::Pulse(false),
+            },
+        ]
+    );
+}
+
+#[test]
+fn polar_direction_keeps_its_angle_when_released() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Aim),
+                DualAxisBinding::builder()
+                    .set_x(
+                        SingleAxisBinding::hold()
+                            .set_negative(KeyCode::ArrowLeft)
+                            .set_positive(KeyCode::ArrowRight)
+                            .build(),
+                    )
+                    .set_y(
+                        SingleAxisBinding::hold()
+                            .set_negative(KeyCode::ArrowDown)
+                            .set_positive(KeyCode::ArrowUp)
+                            .build(),
+                    )
+                    .build(),
+            )
+            .build(),
+    );
+    let polar = |app: &App, reference| {
+        app.ineffable()
+            .direction_2d_polar(ineff!(PlayerInput::Aim), reference)
+    };
+    app.press(KeyCode::ArrowUp).tick();
+    assert_eq!(polar(&app, AngleReference::PositiveX), (FRAC_PI_2, 1.));
+    assert_eq!(polar(&app, AngleReference::PositiveY), (0., 1.));
+
+    // Letting go keeps the angle, but the magnitude drops to zero.
+    app.release(KeyCode::ArrowUp).tick();
+    assert_eq!(polar(&app, AngleReference::PositiveX), (FRAC_PI_2, 0.));
+}
+
+#[test]
+fn strongest_single_axis_binding_wins() {
+    let (mut app, gamepad) = common::app_with_gamepad::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Walk),
+                SingleAxisBinding::hold()
+                    .set_negative(KeyCode::KeyA)
+                    .set_positive(KeyCode::KeyD)
+                    .build(),
+            )
+            .bind(
+                ineff!(PlayerInput::Walk),
+                SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::LeftStickX))
+                    .build(),
+            )
+            .build(),
+    );
+    let mut walk = |stick: f32, keys: &[KeyCode]| {
+        app.set_gamepad_axis(gamepad, GamepadAxisType::LeftStickX, stick)
+            .hold_only(keys)
+            .tick()
+            .ineffable()
+            .direction_1d(ineff!(PlayerInput::Walk))
+    };
+    // The stick is pushed slightly to the left, while the key for walking right is held.
+    assert_eq!(walk(-0.3, &[KeyCode::KeyD]), 1.0);
+    // Without the key, the stick wins.
+    assert_eq!(walk(-0.3, &[]), -0.3);
+    // Fully opposite directions cancel each other out.
+    assert_eq!(walk(-1.0, &[KeyCode::KeyD]), 0.0);
+    // Holding both keys cancels them out, so the stick wins.
+    assert_eq!(walk(0.5, &[KeyCode::KeyA, KeyCode::KeyD]), 0.5);
+}
+
+#[test]
+fn direction_1d_mapped_remaps_onto_the_range() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Throttle),
+                SingleAxisBinding::hold()
+                    .set_negative(KeyCode::KeyS)
+                    .set_positive(KeyCode::KeyW)
+                    .build(),
+            )
+            .build(),
+    );
+    let throttle = |app: &App| {
+        app.ineffable()
+            .direction_1d_mapped(ineff!(PlayerInput::Throttle), 0., 100.)
+    };
+    // Throttle in percent: idle is halfway.
+    app.tick();
+    assert_eq!(throttle(&app), 50.);
+    app.press(KeyCode::KeyW).tick();
+    assert_eq!(throttle(&app), 100.);
+}
@@ -0,0 +1,91 @@
+use bevy::input::keyboard::{Key, KeyboardInput};
+use bevy::input::ButtonState;
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::key_labels::KeyLabels;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum NoInput {}
+
+/// Sends a keyboard event, like the windowing backend would.
+fn send(app: &mut App, key_code: KeyCode, logical_key: Key, state: ButtonState) {
+    app.world_mut().send_event(KeyboardInput {
+        key_code,
+        logical_key,
+        state,
+        window: Entity::PLACEHOLDER,
+    });
+}
+
+#[test]
+fn keys_are_labelled_after_being_pressed() {
+    let mut app = common::app_with_input_plugin::<NoInput>();
+    app.init_resource::<KeyLabels>().tick();
+    assert_eq!(
+        app.world().resource::<KeyLabels>().label(KeyCode::KeyW),
+        "KeyW"
+    );
+
+    // On an AZERTY keyboard, the player presses the key where W would be on a QWERTY keyboard.
+    send(
+        &mut app,
+        KeyCode::KeyW,
+        Key::Character("z".into()),
+        ButtonState::Pressed,
+    );
+    app.tick();
+    assert_eq!(
+        app.world().resource::<KeyLabels>().label(KeyCode::KeyW),
+        "Z"
+    );
+}
+
+#[test]
+fn keys_pressed_with_modifiers_are_not_labelled() {
+    let mut app = common::app_with_input_plugin::<NoInput>();
+    app.init_resource::<KeyLabels>().tick();
+    let label = |app: &App| app.world().resource::<KeyLabels>().label(KeyCode::Digit1);
+    // Shift + 1 produces "!", which is not what is printed on the key.
+    send(
+        &mut app,
+        KeyCode::ShiftLeft,
+        Key::Shift,
+        ButtonState::Pressed,
+    );
+    send(
+        &mut app,
+        KeyCode::Digit1,
+        Key::Character("!".into()),
+        ButtonState::Pressed,
+    );
+    app.tick();
+    assert_eq!(label(&app), "Digit1");
+    // Shift is still held down in the next tick.
+    send(
+        &mut app,
+        KeyCode::Digit1,
+        Key::Character("!".into()),
+        ButtonState::Pressed,
+    );
+    app.tick();
+    assert_eq!(label(&app), "Digit1");
+    // Once shift is released, the key is labelled.
+    send(
+        &mut app,
+        KeyCode::ShiftLeft,
+        Key::Shift,
+        ButtonState::Released,
+    );
+    send(
+        &mut app,
+        KeyCode::Digit1,
+        Key::Character("1".into()),
+        ButtonState::Pressed,
+    );
+    app.tick();
+    assert_eq!(label(&app), "1");
+}
@@ -0,0 +1,76 @@
+use bevy::prelude::*;
+use bevy_ineffable::input_action::ActionId;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::meta_data::IneffableMetaData;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(dual_axis, category = "Movement")]
+    Walk,
+    #[ineffable(pulse, label = "Fire Weapon", category = "Combat")]
+    Fire,
+    #[ineffable(pulse)]
+    Emote,
+}
+
+#[derive(InputAction)]
+pub enum MenuInput {
+    #[ineffable(pulse)]
+    Back,
+}
+
+fn meta_data(app: &App) -> &IneffableMetaData {
+    app.world().resource::<IneffableMetaData>()
+}
+
+#[test]
+fn registered_actions_are_sorted_by_group() {
+    let mut app = common::app::<PlayerInput>();
+    app.register_input_action::<MenuInput>();
+    let actions: Vec<_> = meta_data(&app)
+        .registered_actions()
+        .map(|action| (action.group_id(), action.action_id(), action.kind()))
+        .collect();
+    assert_eq!(
+        actions,
+        vec![
+            ("MenuInput", "Back", InputKind::Pulse),
+            ("PlayerInput", "Walk", InputKind::DualAxis),
+            ("PlayerInput", "Fire", InputKind::Pulse),
+            ("PlayerInput", "Emote", InputKind::Pulse),
+        ]
+    );
+}
+
+#[test]
+fn labels_and_categories_default_to_the_ids() {
+    let app = common::app::<PlayerInput>();
+    let menu: Vec<_> = meta_data(&app)
+        .registered_actions()
+        .map(|action| (action.category(), action.label()))
+        .collect();
+    assert_eq!(
+        menu,
+        vec![
+            ("Movement", "Walk"),
+            ("Combat", "Fire Weapon"),
+            ("PlayerInput", "Emote"),
+        ]
+    );
+}
+
+#[test]
+fn kind_is_looked_up_by_id() {
+    let app = common::app::<PlayerInput>();
+    let meta_data = meta_data(&app);
+    assert_eq!(
+        meta_data.kind(&ActionId::from(ineff!(PlayerInput::Fire))),
+        Some(InputKind::Pulse)
+    );
+    assert_eq!(
+        meta_data.kind(&ActionId::new("PlayerInput", "Frostbolt")),
+        None
+    );
+}
@@ -0,0 +1,67 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::ineffable_settings::IneffableSettings;
+
+const ACTIONS: usize = 2000;
+const KEYS: [KeyCode; 4] = [KeyCode::KeyA, KeyCode::KeyS, KeyCode::KeyD, KeyCode::KeyF];
+
+/// Runs the same input on a few thousand actions, and collects their state after every tick.
+fn run(parallel_threshold: usize) -> Vec<Option<bool>> {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, InputPlugin, IneffableMinimalPlugin));
+    let mut builder = InputConfig::builder();
+    for index in 0..ACTIONS {
+        let action_id = format!("Action{index}");
+        let key = KEYS[index % KEYS.len()];
+        if index % 2 == 0 {
+            app.register_dynamic_action("Stress", &action_id, InputKind::Pulse);
+            builder = builder.bind_dynamic("Stress", &action_id, PulseBinding::just_pressed(key));
+        } else {
+            app.register_dynamic_action("Stress", &action_id, InputKind::Continuous);
+            builder = builder.bind_dynamic("Stress", &action_id, ContinuousBinding::hold(key));
+        }
+    }
+    let config = builder.build();
+    app.world_mut()
+        .resource_mut::<IneffableSettings>()
+        .parallel_threshold = parallel_threshold;
+    app.world_mut()
+        .run_system_once(move |mut ineffable: IneffableCommands| {
+            ineffable.set_config(&config);
+        });
+    let mut states = Vec::new();
+    for key in [KeyCode::KeyA, KeyCode::KeyD, KeyCode::KeyS, KeyCode::KeyA] {
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(key);
+        app.update();
+        let ineffable = app.world().resource::<Ineffable>();
+        states.extend((0..ACTIONS).map(|index| {
+            let action_id = format!("Action{index}");
+            ineffable
+                .just_pulsed_by_id("Stress", &action_id)
+                .or_else(|| ineffable.is_active_by_id("Stress", &action_id))
+        }));
+    }
+    states
+}
+
+#[test]
+fn parallel_updates_match_serial_updates() {
+    let serial = run(usize::MAX);
+    assert!(serial.iter().all(Option::is_some));
+    assert!(serial.contains(&Some(true)));
+    assert_eq!(run(0), serial);
+}
+
+#[test]
+fn settings_without_a_parallel_threshold_use_the_default() {
+    let settings = IneffableSettings::default();
+    let serialized = ron::to_string(&settings).unwrap();
+    let field = format!(",parallel_threshold:{}", settings.parallel_threshold);
+    assert!(serialized.contains(&field));
+    let older: IneffableSettings = ron::from_str(&serialized.replace(&field, "")).unwrap();
+    assert_eq!(older, settings);
+}
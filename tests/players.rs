@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+use bevy_ineffable::components::InputDevices;
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(continuous)]
+    Jump,
+}
+
+#[test]
+fn each_player_only_reads_their_own_devices() {
+    let (mut app, gamepad) = common::app_with_gamepad::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Jump),
+                ContinuousBinding::hold(KeyCode::Space),
+            )
+            .bind(
+                ineff!(PlayerInput::Jump),
+                ContinuousBinding::hold(GamepadButtonType::South),
+            )
+            .build(),
+    );
+    // Player one uses the keyboard, player two uses the gamepad.
+    let one = app
+        .world_mut()
+        .spawn(IneffableInput::new(InputDevices::keyboard_mouse()))
+        .id();
+    let two = app
+        .world_mut()
+        .spawn(IneffableInput::new(InputDevices::gamepad(gamepad)))
+        .id();
+    let jumping = |app: &App, player: Entity| {
+        app.world()
+            .get::<IneffableInput>(player)
+            .unwrap()
+            .is_active(ineff!(PlayerInput::Jump))
+    };
+
+    // Only the first player jumps when Space is pressed.
+    app.press(KeyCode::Space).tick();
+    assert!(jumping(&app, one));
+    assert!(!jumping(&app, two));
+
+    // Only the second player jumps when the gamepad button is pressed.
+    app.release(KeyCode::Space)
+        .press_gamepad(gamepad, GamepadButtonType::South)
+        .tick();
+    assert!(!jumping(&app, one));
+    assert!(jumping(&app, two));
+}
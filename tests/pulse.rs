@@ -0,0 +1,197 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ineffable::bindings::{AnalogInput, InputDeviceCategory, SwipeDirection, Threshold};
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(pulse)]
+    Interact,
+    #[ineffable(pulse)]
+    NextWeapon,
+    #[ineffable(pulse)]
+    Fireball,
+}
+
+#[derive(InputAction)]
+pub enum TitleScreenInput {
+    #[ineffable(pulse)]
+    Continue,
+    #[ineffable(pulse)]
+    ContinueWithMouse,
+}
+
+#[test]
+fn single_press_and_sequence_ending_in_the_same_key_both_pulse() {
+    let mut app = common::app::<PlayerInput>();
+    let cheat_code = PulseBinding::sequence(KeyCode::KeyI)
+        .followed_by(KeyCode::KeyD)
+        .followed_by(KeyCode::KeyE)
+        .with_timing(Duration::from_millis(500));
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Interact),
+                PulseBinding::just_pressed(KeyCode::KeyE),
+            )
+            .bind(ineff!(PlayerInput::Interact), cheat_code)
+            .build(),
+    );
+    let mut tap = |key: KeyCode| {
+        let pulses = app
+            .press(key)
+            .tick()
+            .ineffable()
+            .pulse_count(ineff!(PlayerInput::Interact));
+        app.release(key).tick();
+        pulses
+    };
+    // A single press pulses right away, without waiting for the sequence.
+    assert_eq!(tap(KeyCode::KeyE), 1);
+    // The sequence pulses when it is completed, even though E by itself already pulses as well.
+    assert_eq!(tap(KeyCode::KeyI), 0);
+    assert_eq!(tap(KeyCode::KeyD), 0);
+    assert_eq!(tap(KeyCode::KeyE), 1);
+    // Pressing E halfway through the sequence pulses, without breaking the sequence.
+    assert_eq!(tap(KeyCode::KeyI), 0);
+    assert_eq!(tap(KeyCode::KeyE), 1);
+    assert_eq!(tap(KeyCode::KeyD), 0);
+    assert_eq!(tap(KeyCode::KeyE), 1);
+}
+
+#[test]
+fn notches_counts_every_full_notch() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::NextWeapon),
+                PulseBinding::notches(AnalogInput::ScrollWheelY, Threshold::new(1.)),
+            )
+            .build(),
+    );
+    let mut scroll = |y: f32| {
+        app.scroll(y)
+            .tick()
+            .ineffable()
+            .pulse_count(ineff!(PlayerInput::NextWeapon))
+    };
+    // Every notch counts, even when they all arrive in the same tick.
+    assert_eq!(scroll(3.), 3);
+    // Half notches add up.
+    assert_eq!(scroll(0.5), 0);
+    assert_eq!(scroll(0.5), 1);
+    // Changing direction discards partial notches.
+    assert_eq!(scroll(0.5), 0);
+    assert_eq!(scroll(-0.5), 0);
+    assert_eq!(scroll(0.5), 0);
+    assert_eq!(scroll(0.5), 1);
+}
+
+#[test]
+fn notches_ignore_movement_against_the_notch() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::NextWeapon),
+                PulseBinding::notches(AnalogInput::ScrollWheelY, Threshold::new(-1.)),
+            )
+            .build(),
+    );
+    let mut scroll = |y: f32| {
+        app.scroll(y)
+            .tick()
+            .ineffable()
+            .pulse_count(ineff!(PlayerInput::NextWeapon))
+    };
+    // Scrolling up never pulses, no matter how far.
+    assert_eq!(scroll(5.), 0);
+    assert_eq!(scroll(0.7), 0);
+    // Scrolling down starts counting from zero, and keeps whatever is left after the whole notches.
+    assert_eq!(scroll(-0.7), 0);
+    assert_eq!(scroll(-1.6), 2);
+    assert_eq!(scroll(-0.8), 1);
+}
+
+#[test]
+fn swipe_pulses_once_when_the_swipe_ends() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Fireball),
+                PulseBinding::swipe(SwipeDirection::Right, 100., Duration::from_millis(1000)),
+            )
+            .build(),
+    );
+    let mut swipe = |moves: &[Vec2]| {
+        app.press_mouse(MouseButton::Left);
+        let mut pulsed = false;
+        for delta in moves {
+            pulsed |= app
+                .move_mouse(*delta)
+                .tick()
+                .ineffable()
+                .just_pulsed(ineff!(PlayerInput::Fireball));
+        }
+        let released = app
+            .release_mouse(MouseButton::Left)
+            .tick()
+            .ineffable()
+            .just_pulsed(ineff!(PlayerInput::Fireball));
+        (pulsed, released)
+    };
+    // Drag the mouse 120 pixels to the right. It pulses when the mouse button is released.
+    assert_eq!(
+        swipe(&[Vec2::new(60., 0.), Vec2::new(60., 10.)]),
+        (false, true)
+    );
+    // Too short:
+    assert_eq!(swipe(&[Vec2::new(60., 0.)]), (false, false));
+    // Mostly downwards:
+    assert_eq!(swipe(&[Vec2::new(120., 150.)]), (false, false));
+}
+
+#[test]
+fn any_input_only_pulses_on_new_button_presses() {
+    let mut app = common::app::<TitleScreenInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(TitleScreenInput::Continue),
+                PulseBinding::any_input(InputDeviceCategory::All),
+            )
+            .bind(
+                ineff!(TitleScreenInput::ContinueWithMouse),
+                PulseBinding::any_input(InputDeviceCategory::Mouse),
+            )
+            .build(),
+    );
+    let mut press = |key: Option<KeyCode>, mouse_button: Option<MouseButton>| {
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.clear();
+        if let Some(key) = key {
+            keys.press(key);
+        }
+        let mut mouse_buttons = app.world_mut().resource_mut::<ButtonInput<MouseButton>>();
+        mouse_buttons.clear();
+        if let Some(mouse_button) = mouse_button {
+            mouse_buttons.press(mouse_button);
+        }
+        let ineffable = app.tick().ineffable();
+        (
+            ineffable.just_pulsed(ineff!(TitleScreenInput::Continue)),
+            ineffable.just_pulsed(ineff!(TitleScreenInput::ContinueWithMouse)),
+        )
+    };
+    assert_eq!(press(Some(KeyCode::KeyQ), None), (true, false));
+    // Holding the key doesn't pulse again.
+    assert_eq!(press(None, None), (false, false));
+    assert_eq!(press(None, Some(MouseButton::Right)), (true, true));
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use bevy_ineffable::input_action::ActionId;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::meta_data::IneffableMetaData;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum MenuInput {
+    #[ineffable(pulse)]
+    Open,
+}
+
+/// Has the same name as the `MenuInput` enum above, which is a mistake.
+mod modded {
+    use bevy_ineffable::prelude::*;
+
+    #[derive(InputAction)]
+    pub enum MenuInput {
+        #[ineffable(continuous)]
+        Scroll,
+    }
+}
+
+#[derive(Resource, Default)]
+struct MenuOpened(u32);
+
+#[test]
+fn dynamic_actions_are_queried_by_id() {
+    let mut app = common::app::<MenuInput>();
+    app.register_dynamic_action("ModAbilities", "Fireball", InputKind::Pulse);
+    app.set_config(
+        &InputConfig::builder()
+            .bind_dynamic(
+                "ModAbilities",
+                "Fireball",
+                PulseBinding::just_pressed(KeyCode::KeyF),
+            )
+            .build(),
+    );
+    app.press(KeyCode::KeyF).tick();
+    let ineffable = app.ineffable();
+    assert_eq!(
+        ineffable.just_pulsed_by_id("ModAbilities", "Fireball"),
+        Some(true)
+    );
+    // Unknown actions, or actions of a different kind, return None.
+    assert_eq!(
+        ineffable.just_pulsed_by_id("ModAbilities", "Frostbolt"),
+        None
+    );
+    assert_eq!(ineffable.is_active_by_id("ModAbilities", "Fireball"), None);
+}
+
+#[test]
+fn on_pulse_runs_the_system_once_per_pulse() {
+    let mut app = common::app::<MenuInput>();
+    app.init_resource::<MenuOpened>().on_pulse(
+        ineff!(MenuInput::Open),
+        |mut opened: ResMut<MenuOpened>| {
+            opened.0 += 1;
+        },
+    );
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(MenuInput::Open),
+                PulseBinding::just_pressed(KeyCode::Escape),
+            )
+            .build(),
+    );
+    app.press(KeyCode::Escape).ticks(2);
+    // The key was held down for two ticks, but it only pulsed once.
+    assert_eq!(app.world().resource::<MenuOpened>().0, 1);
+}
+
+#[test]
+#[should_panic(expected = "Try adding the IneffablePlugin first")]
+fn dynamic_actions_need_the_plugin() {
+    // The plugin would replace the meta data, so registering before adding it would lose the action.
+    App::new().register_dynamic_action("ModAbilities", "Fireball", InputKind::Pulse);
+}
+
+#[test]
+fn dynamic_actions_cant_be_added_to_an_enum() {
+    let mut app = common::app::<MenuInput>();
+    app.register_dynamic_action("MenuInput", "Close", InputKind::Pulse);
+    let meta_data = app.world().resource::<IneffableMetaData>();
+    assert_eq!(meta_data.kind(&ActionId::new("MenuInput", "Close")), None);
+    assert_eq!(meta_data.registered_actions().count(), 1);
+}
+
+#[test]
+fn enums_with_the_same_name_are_not_mixed_up() {
+    use modded::MenuInput as ModdedMenuInput;
+    let mut app = common::app::<MenuInput>();
+    app.register_input_action::<ModdedMenuInput>();
+    // The first enum keeps the group, and none of the actions of the second one are registered.
+    let meta_data = app.world().resource::<IneffableMetaData>();
+    let actions: Vec<_> = meta_data
+        .registered_actions()
+        .map(|action| (action.action_id(), action.kind()))
+        .collect();
+    assert_eq!(actions, vec![("Open", InputKind::Pulse)]);
+    // Binding the actions of the second enum is rejected, instead of silently routing input to the wrong action.
+    let config = InputConfig::builder()
+        .bind(
+            ineff!(ModdedMenuInput::Scroll),
+            ContinuousBinding::hold(KeyCode::ArrowDown),
+        )
+        .build();
+    let report = app.commands(move |ineffable| ineffable.validate(&config));
+    assert!(report.has_errors());
+}
@@ -0,0 +1,259 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum Combo {
+    #[ineffable(pulse)]
+    Tolerant,
+    #[ineffable(pulse)]
+    Strict,
+    #[ineffable(pulse)]
+    FullReset,
+    #[ineffable(pulse)]
+    SlidingWindow,
+    #[ineffable(pulse)]
+    Special,
+    #[ineffable(pulse)]
+    Finisher,
+    #[ineffable(pulse)]
+    ChargedPunch,
+}
+
+/// Taps a key, which takes two ticks, and reads the state of the actions right after the key was pressed.
+fn tap<T>(app: &mut App, key: KeyCode, read: impl Fn(&Ineffable) -> T) -> T {
+    let state = read(app.press(key).tick().ineffable());
+    app.release(key).tick();
+    state
+}
+
+#[test]
+fn strict_sequences_reset_on_inputs_from_other_steps() {
+    let mut app = common::app::<Combo>();
+    let sequence = || {
+        PulseBinding::sequence(KeyCode::KeyA)
+            .followed_by(KeyCode::KeyB)
+            .followed_by(KeyCode::KeyC)
+    };
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(Combo::Tolerant),
+                sequence().with_timing(Duration::from_millis(1000)),
+            )
+            .bind(
+                ineff!(Combo::Strict),
+                sequence().strict().with_timing(Duration::from_millis(1000)),
+            )
+            .build(),
+    );
+    let mut tap = |key| {
+        tap(&mut app, key, |ineffable| {
+            [
+                ineffable.just_pulsed(ineff!(Combo::Tolerant)),
+                ineffable.just_pulsed(ineff!(Combo::Strict)),
+            ]
+        })
+    };
+    // Pressing C halfway through only breaks the strict sequence.
+    assert_eq!(tap(KeyCode::KeyA), [false, false]);
+    assert_eq!(tap(KeyCode::KeyC), [false, false]);
+    assert_eq!(tap(KeyCode::KeyB), [false, false]);
+    assert_eq!(tap(KeyCode::KeyC), [true, false]);
+
+    // Keys that are not part of the sequence are ignored, even by the strict sequence.
+    // A wrong input that is the first step of the sequence starts a new attempt.
+    assert_eq!(tap(KeyCode::KeyA), [false, false]);
+    assert_eq!(tap(KeyCode::KeyX), [false, false]);
+    assert_eq!(tap(KeyCode::KeyA), [false, false]);
+    assert_eq!(tap(KeyCode::KeyB), [false, false]);
+    assert_eq!(tap(KeyCode::KeyC), [true, true]);
+}
+
+#[test]
+fn sliding_window_reuses_the_end_of_the_previous_attempt() {
+    let mut app = common::app::<Combo>();
+    let sequence = || {
+        PulseBinding::sequence(KeyCode::KeyA)
+            .followed_by(KeyCode::KeyB)
+            .followed_by(KeyCode::KeyA)
+            .followed_by(KeyCode::KeyB)
+    };
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(Combo::FullReset),
+                sequence().with_timing(Duration::from_millis(1000)),
+            )
+            .bind(
+                ineff!(Combo::SlidingWindow),
+                sequence()
+                    .sliding_window()
+                    .with_timing(Duration::from_millis(1000)),
+            )
+            .build(),
+    );
+    let mut tap = |key| {
+        tap(&mut app, key, |ineffable| {
+            [
+                ineffable.just_pulsed(ineff!(Combo::FullReset)),
+                ineffable.just_pulsed(ineff!(Combo::SlidingWindow)),
+            ]
+        })
+    };
+    // Both sequences fire the first time.
+    assert_eq!(tap(KeyCode::KeyA), [false, false]);
+    assert_eq!(tap(KeyCode::KeyB), [false, false]);
+    assert_eq!(tap(KeyCode::KeyA), [false, false]);
+    assert_eq!(tap(KeyCode::KeyB), [true, true]);
+
+    // The sliding window reuses the last `A B` of the previous attempt, so it fires again after only two inputs.
+    assert_eq!(tap(KeyCode::KeyA), [false, false]);
+    assert_eq!(tap(KeyCode::KeyB), [false, true]);
+    assert_eq!(tap(KeyCode::KeyA), [false, false]);
+    assert_eq!(tap(KeyCode::KeyB), [true, true]);
+    // After completing the sequence, only the sliding window has made progress towards the next attempt.
+    let ineffable = app.ineffable();
+    assert_eq!(
+        ineffable.sequence_progress(ineff!(Combo::FullReset)),
+        Some((0, 4))
+    );
+    assert_eq!(
+        ineffable.sequence_progress(ineff!(Combo::SlidingWindow)),
+        Some((2, 4))
+    );
+}
+
+#[test]
+fn chord_steps_need_the_whole_chord() {
+    let mut app = common::app::<Combo>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(Combo::Special),
+                PulseBinding::sequence((KeyCode::KeyA, KeyCode::KeyB))
+                    .followed_by(KeyCode::KeyC)
+                    .with_timing(Duration::from_millis(1000)),
+            )
+            .build(),
+    );
+    let mut tick = |keys: &[KeyCode]| {
+        app.hold_only(keys)
+            .tick()
+            .ineffable()
+            .just_pulsed(ineff!(Combo::Special))
+    };
+    // Pressing only part of the first chord does not advance the sequence.
+    assert!(!tick(&[KeyCode::KeyA]));
+    assert!(!tick(&[KeyCode::KeyC]));
+    // Pressing the whole chord does.
+    assert!(!tick(&[KeyCode::KeyA]));
+    assert!(!tick(&[KeyCode::KeyA, KeyCode::KeyB]));
+    assert!(tick(&[KeyCode::KeyC]));
+}
+
+#[test]
+fn followed_within_limits_the_delay_before_a_step() {
+    let mut app = common::app::<Combo>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(Combo::Finisher),
+                PulseBinding::sequence(KeyCode::KeyA)
+                    .followed_by(KeyCode::KeyB)
+                    .followed_within(Duration::from_millis(100), KeyCode::KeyC)
+                    .with_timing(Duration::from_millis(1000)),
+            )
+            .build(),
+    );
+    // Every tick takes 50 milliseconds; holding a key down and letting go takes two ticks.
+    let mut tap = |key: Option<KeyCode>| match key {
+        Some(key) => tap(&mut app, key, |ineffable| {
+            ineffable.just_pulsed(ineff!(Combo::Finisher))
+        }),
+        None => {
+            app.ticks(2);
+            false
+        }
+    };
+    assert!(!tap(Some(KeyCode::KeyA)));
+    assert!(!tap(None));
+    assert!(!tap(Some(KeyCode::KeyB)));
+    assert!(tap(Some(KeyCode::KeyC)));
+
+    // Waiting too long before pressing C breaks the sequence.
+    assert!(!tap(Some(KeyCode::KeyA)));
+    assert!(!tap(Some(KeyCode::KeyB)));
+    assert!(!tap(None));
+    assert!(!tap(Some(KeyCode::KeyC)));
+}
+
+#[test]
+fn held_for_requires_holding_a_step() {
+    let mut app = common::app::<Combo>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(Combo::ChargedPunch),
+                PulseBinding::sequence(KeyCode::ArrowLeft)
+                    .held_for(Duration::from_millis(200))
+                    .followed_by(KeyCode::KeyP)
+                    .with_timing(Duration::from_millis(500)),
+            )
+            .build(),
+    );
+    // Every tick takes 50 milliseconds.
+    let mut tick = |keys: &[KeyCode]| {
+        app.hold_only(keys)
+            .tick()
+            .ineffable()
+            .just_pulsed(ineff!(Combo::ChargedPunch))
+    };
+    // Tapping back is not enough.
+    assert!(!tick(&[KeyCode::ArrowLeft]));
+    assert!(!tick(&[]));
+    assert!(!tick(&[KeyCode::KeyP]));
+    assert!(!tick(&[]));
+
+    // Holding back for 200 milliseconds is.
+    for _ in 0..4 {
+        assert!(!tick(&[KeyCode::ArrowLeft]));
+    }
+    assert!(!tick(&[]));
+    assert!(tick(&[KeyCode::KeyP]));
+}
+
+#[test]
+fn sequence_progress_counts_steps_and_resets_after_a_timeout() {
+    let mut app = common::app::<Combo>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(Combo::Tolerant),
+                PulseBinding::sequence(KeyCode::KeyA)
+                    .followed_by(KeyCode::KeyB)
+                    .followed_by(KeyCode::KeyC)
+                    .with_timing(Duration::from_millis(300)),
+            )
+            .build(),
+    );
+    let progress = |app: &App| app.ineffable().sequence_progress(ineff!(Combo::Tolerant));
+    assert_eq!(progress(&app), Some((0, 3)));
+    tap(&mut app, KeyCode::KeyA, |_| ());
+    assert_eq!(progress(&app), Some((1, 3)));
+    tap(&mut app, KeyCode::KeyB, |_| ());
+    assert_eq!(progress(&app), Some((2, 3)));
+    // Every tick takes 50 milliseconds. Waiting longer than 300 milliseconds resets the sequence.
+    app.ticks(8);
+    assert_eq!(progress(&app), Some((0, 3)));
+    // Actions without a sequence have no progress.
+    assert_eq!(
+        app.ineffable().sequence_progress(ineff!(Combo::Strict)),
+        None
+    );
+}
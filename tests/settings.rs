@@ -0,0 +1,399 @@
+//! Tests for the settings in `InputConfig` that change how bindings behave.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ineffable::bindings::AnalogInput;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::ineffable_settings::IneffableSettings;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(continuous)]
+    Sprint,
+    #[ineffable(pulse)]
+    Jump,
+    #[ineffable(continuous)]
+    Charge,
+    #[ineffable(single_axis)]
+    Look,
+}
+
+#[derive(InputAction)]
+pub enum EditorInput {
+    #[ineffable(continuous)]
+    Save,
+    #[ineffable(continuous)]
+    Down,
+}
+
+#[test]
+fn post_acceptance_delay_lets_releases_through() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .post_acceptance_delay(Duration::from_millis(100))
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .build(),
+    );
+    let state = |app: &App| {
+        let ineffable = app.ineffable();
+        (
+            ineffable.is_active(ineff!(PlayerInput::Sprint)),
+            ineffable.just_pulsed(ineff!(PlayerInput::Jump)),
+        )
+    };
+    // Start sprinting, then wait for the delay to pass.
+    app.press(KeyCode::ShiftLeft).tick();
+    assert_eq!(state(&app), (true, false));
+    app.ticks(2);
+    // Jump while sprinting, which starts the delay.
+    app.press(KeyCode::Space).tick();
+    assert_eq!(state(&app), (true, true));
+    // Stop sprinting during the delay.
+    app.release(KeyCode::ShiftLeft).tick();
+    assert_eq!(state(&app), (false, false));
+}
+
+#[test]
+fn charge_is_retained_when_rolling_over_to_another_key() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .charge_retain_window(Duration::from_millis(100))
+            .bind(
+                ineff!(PlayerInput::Charge),
+                ContinuousBinding::hold(KeyCode::KeyA),
+            )
+            .bind(
+                ineff!(PlayerInput::Charge),
+                ContinuousBinding::hold(KeyCode::KeyB),
+            )
+            .build(),
+    );
+    let mut charge = |keys: &[KeyCode]| {
+        app.hold_only(keys)
+            .tick()
+            .ineffable()
+            .charge_time(ineff!(PlayerInput::Charge))
+    };
+    // Hold A for three ticks.
+    charge(&[KeyCode::KeyA]);
+    charge(&[KeyCode::KeyA]);
+    assert_eq!(charge(&[KeyCode::KeyA]), Some(Duration::from_millis(150)));
+    // Then roll over to B, with a tick in between where neither is held.
+    charge(&[]);
+    assert_eq!(charge(&[KeyCode::KeyB]), Some(Duration::from_millis(200)));
+}
+
+#[test]
+fn disabling_chord_blocking_activates_overlapping_chords() {
+    let mut app = common::app::<EditorInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .chord_blocking(false)
+            .bind(
+                ineff!(EditorInput::Save),
+                ContinuousBinding::hold((KeyCode::ControlLeft, KeyCode::KeyS)),
+            )
+            .bind(
+                ineff!(EditorInput::Down),
+                ContinuousBinding::hold(KeyCode::KeyS),
+            )
+            .build(),
+    );
+    app.press(KeyCode::ControlLeft).press(KeyCode::KeyS).tick();
+    let ineffable = app.ineffable();
+    assert!(ineffable.is_active(ineff!(EditorInput::Save)));
+    assert!(ineffable.is_active(ineff!(EditorInput::Down)));
+}
+
+#[test]
+fn per_action_sensitivity_and_inversion_apply_to_the_base_bindings() {
+    let (mut app, gamepad) = common::app_with_gamepad::<PlayerInput>();
+    let base = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Look),
+            SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::RightStickX)).build(),
+        )
+        .build();
+    let player = InputConfig::builder()
+        .sensitivity_for(ineff!(PlayerInput::Look), 2.)
+        .invert_for(ineff!(PlayerInput::Look), true)
+        .build();
+    app.set_config(&base.merge_replace(&player));
+    // Push the stick a quarter of the way to the right.
+    app.set_gamepad_axis(gamepad, GamepadAxisType::RightStickX, 0.25)
+        .tick();
+    assert_eq!(
+        app.ineffable().direction_1d(ineff!(PlayerInput::Look)),
+        -0.5
+    );
+}
+
+#[test]
+fn cooldown_limits_how_fast_a_pulse_can_fire() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .cooldown_for(ineff!(PlayerInput::Jump), Duration::from_millis(250))
+            .build(),
+    );
+    // Mash the button for one second, pressing it every 100 milliseconds.
+    let mut jumps = 0;
+    for tick in 0..20 {
+        if tick % 2 == 0 {
+            app.press(KeyCode::Space);
+        } else {
+            app.release(KeyCode::Space);
+        }
+        if app
+            .tick()
+            .ineffable()
+            .just_pulsed(ineff!(PlayerInput::Jump))
+        {
+            jumps += 1;
+        }
+    }
+    // Ten presses, but only one jump per 250 milliseconds.
+    assert_eq!(jumps, 4);
+}
+
+#[test]
+fn exempt_actions_ignore_the_post_acceptance_delay() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .post_acceptance_delay(Duration::from_millis(100))
+            .ignore_post_acceptance_delay_for(ineff!(PlayerInput::Sprint), true)
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .bind(
+                ineff!(PlayerInput::Jump),
+                PulseBinding::just_pressed(KeyCode::Space),
+            )
+            .build(),
+    );
+    // Jumping starts the delay.
+    app.press(KeyCode::Space).tick();
+    assert!(app.ineffable().just_pulsed(ineff!(PlayerInput::Jump)));
+    // Sprinting still works during the delay.
+    app.press(KeyCode::ShiftLeft).tick();
+    assert!(app.ineffable().is_active(ineff!(PlayerInput::Sprint)));
+}
+
+#[test]
+fn hold_to_toggle_turns_holds_into_toggles() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .hold_to_toggle(true)
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .build(),
+    );
+    let mut sprint = |held: bool| {
+        app.hold_only(if held { &[KeyCode::ShiftLeft] } else { &[] })
+            .tick()
+            .ineffable()
+            .is_active(ineff!(PlayerInput::Sprint))
+    };
+    // Tap Shift once to start sprinting. It stays on after letting go.
+    assert!(sprint(true));
+    assert!(sprint(false));
+    // Tap it again to stop.
+    assert!(!sprint(true));
+    assert!(!sprint(false));
+}
+
+/// Moves the mouse once, then reports the value of the `Look` action for the given number of ticks.
+fn look(config: InputConfig, ticks: usize) -> Vec<f32> {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(&config);
+    app.move_mouse(Vec2::new(10., 0.));
+    (0..ticks)
+        .map(|_| {
+            app.tick()
+                .ineffable()
+                .direction_1d(ineff!(PlayerInput::Look))
+        })
+        .collect()
+}
+
+#[test]
+fn global_sensitivity_scales_every_axis() {
+    let config = |global_sensitivity: f32| {
+        InputConfig::builder()
+            .global_sensitivity(global_sensitivity)
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::analog(AnalogInput::MouseMotionX)
+                    .set_sensitivity(2.)
+                    .build(),
+            )
+            .build()
+    };
+    assert_eq!(look(config(1.), 1), vec![20.]);
+    assert_eq!(look(config(0.5), 1), vec![10.]);
+}
+
+#[test]
+fn mouse_sensitivity_scales_mouse_motion() {
+    let config = |sensitivity: f32| {
+        InputConfig::builder()
+            .mouse_sensitivity(sensitivity)
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::analog(AnalogInput::MouseMotionX).build(),
+            )
+            .build()
+    };
+    assert_eq!(look(config(1.), 1), vec![10.]);
+    assert_eq!(look(config(2.), 1), vec![20.]);
+}
+
+#[test]
+fn motion_smoothing_spreads_out_a_jerk() {
+    let config = |smoothing: f32| {
+        InputConfig::builder()
+            .motion_smoothing(smoothing)
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::analog(AnalogInput::MouseMotionX).build(),
+            )
+            .build()
+    };
+    // The mouse jerks once, and then lies still.
+    assert_eq!(look(config(0.), 3), vec![10., 0., 0.]);
+    // The factor applies at 60 frames per second. Every tick takes 50 milliseconds, which is three such frames.
+    let smoothed = look(config(0.5), 3);
+    for (actual, expected) in smoothed.into_iter().zip([8.75, 1.09375, 0.13671875]) {
+        assert!((actual - expected).abs() < 1e-4, "{actual} != {expected}");
+    }
+}
+
+#[test]
+fn last_device_wins_ignores_a_drifting_stick() {
+    let (mut app, gamepad) = common::app_with_gamepad::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .last_device_wins(true)
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::LeftStickX))
+                    .build(),
+            )
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::hold()
+                    .set_negative(KeyCode::KeyA)
+                    .set_positive(KeyCode::KeyD)
+                    .build(),
+            )
+            .build(),
+    );
+    let mut look = |stick: f32, keys: &[KeyCode]| {
+        app.set_gamepad_axis(gamepad, GamepadAxisType::LeftStickX, stick)
+            .hold_only(keys)
+            .tick()
+            .ineffable()
+            .direction_1d(ineff!(PlayerInput::Look))
+    };
+    // The stick drifts a little, but nobody has touched anything yet.
+    assert_eq!(look(0.15, &[]), 0.15);
+    // Once the keyboard is used, the drifting stick is ignored, even after the key is released.
+    assert_eq!(look(0.15, &[KeyCode::KeyD]), 1.);
+    assert_eq!(look(0.15, &[]), 0.);
+    assert_eq!(look(0.2, &[]), 0.);
+    // Deliberately moving the stick makes it win again.
+    assert_eq!(look(-0.8, &[]), -0.8);
+}
+
+#[test]
+fn last_device_wins_listens_to_every_binding_of_that_device() {
+    let (mut app, gamepad) = common::app_with_gamepad::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .last_device_wins(true)
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::analog(AnalogInput::GamePad(GamepadAxisType::LeftStickX))
+                    .build(),
+            )
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::hold()
+                    .set_negative(GamepadButtonType::DPadLeft)
+                    .set_positive(GamepadButtonType::DPadRight)
+                    .build(),
+            )
+            .bind(
+                ineff!(PlayerInput::Look),
+                SingleAxisBinding::hold()
+                    .set_positive(KeyCode::KeyD)
+                    .build(),
+            )
+            .build(),
+    );
+    let mut look = |stick: f32, dpad: bool, keys: &[KeyCode]| {
+        if dpad {
+            app.press_gamepad(gamepad, GamepadButtonType::DPadRight);
+        } else {
+            app.release_gamepad(gamepad, GamepadButtonType::DPadRight);
+        }
+        app.set_gamepad_axis(gamepad, GamepadAxisType::LeftStickX, stick)
+            .hold_only(keys)
+            .tick()
+            .ineffable()
+            .direction_1d(ineff!(PlayerInput::Look))
+    };
+    assert_eq!(look(-0.8, false, &[]), -0.8);
+    // The D-pad is on the same gamepad, so pressing it doesn't shut out the stick.
+    assert_eq!(look(-0.8, true, &[]), 1.);
+    assert_eq!(look(-0.8, false, &[]), -0.8);
+    // The keyboard is another device.
+    assert_eq!(look(-0.8, false, &[KeyCode::KeyD]), 1.);
+    assert_eq!(look(-0.8, false, &[]), 0.);
+}
+
+#[test]
+fn settings_ignore_sensitivities_that_are_not_finite() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(
+        &InputConfig::builder()
+            .global_sensitivity(f32::NAN)
+            .mouse_sensitivity(f32::NAN)
+            .motion_smoothing(f32::INFINITY)
+            .build(),
+    );
+    let settings = app.world().resource::<IneffableSettings>();
+    assert_eq!(settings, &IneffableSettings::default());
+    // An infinite sensitivity times a sensitivity of zero would be NaN.
+    app.set_config(
+        &InputConfig::builder()
+            .mouse_sensitivity(f32::INFINITY)
+            .mouse_sensitivity_axes(0., 1.)
+            .build(),
+    );
+    let settings = app.world().resource::<IneffableSettings>();
+    assert_eq!(settings.mouse_sensitivity, Vec2::new(0., 1.));
+}
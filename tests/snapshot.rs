@@ -0,0 +1,92 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::resources::InputSnapshot;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(continuous)]
+    Crouch,
+    #[ineffable(pulse)]
+    Jump,
+}
+
+fn crouch_on(key: KeyCode) -> InputConfig {
+    InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Crouch),
+            ContinuousBinding::toggle(PulseBinding::just_pressed(key)),
+        )
+        .build()
+}
+
+/// Taps a key, which takes two ticks, and reports whether the player is crouching afterwards.
+fn tap(app: &mut App, key: KeyCode) -> bool {
+    app.press(key).tick().release(key).tick();
+    app.ineffable().is_active(ineff!(PlayerInput::Crouch))
+}
+
+#[test]
+fn snapshots_survive_serialization() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(&crouch_on(KeyCode::KeyC));
+    assert!(tap(&mut app, KeyCode::KeyC));
+    let snapshot = app.commands(|ineffable| ineffable.snapshot());
+    // Snapshots can be serialized, for example to send them over the network.
+    let snapshot: InputSnapshot = ron::from_str(&ron::to_string(&snapshot).unwrap()).unwrap();
+
+    assert!(!tap(&mut app, KeyCode::KeyC));
+    app.commands(move |mut ineffable| ineffable.restore(&snapshot));
+    assert!(app.ineffable().is_active(ineff!(PlayerInput::Crouch)));
+}
+
+#[test]
+fn restoring_keeps_the_current_bindings() {
+    let mut app = common::app::<PlayerInput>();
+    app.set_config(&crouch_on(KeyCode::KeyC));
+    assert!(tap(&mut app, KeyCode::KeyC));
+
+    // Rebinding resets the toggle, restoring the snapshot brings it back.
+    app.commands(|mut ineffable| {
+        let snapshot = ineffable.snapshot();
+        ineffable.set_config(&crouch_on(KeyCode::ControlLeft));
+        ineffable.restore(&snapshot);
+    });
+    assert!(app.ineffable().is_active(ineff!(PlayerInput::Crouch)));
+
+    // The old key no longer does anything, the new key toggles crouching off.
+    assert!(tap(&mut app, KeyCode::KeyC));
+    assert!(!tap(&mut app, KeyCode::ControlLeft));
+}
+
+#[test]
+fn snapshots_include_the_post_acceptance_delay() {
+    let mut app = common::app::<PlayerInput>();
+    let config = InputConfig::builder()
+        .post_acceptance_delay(Duration::from_millis(200))
+        .bind(
+            ineff!(PlayerInput::Jump),
+            PulseBinding::just_pressed(KeyCode::Space),
+        )
+        .bind(
+            ineff!(PlayerInput::Crouch),
+            ContinuousBinding::toggle(PulseBinding::just_pressed(KeyCode::KeyC)),
+        )
+        .build();
+    app.set_config(&config);
+    let snapshot = app.commands(|ineffable| ineffable.snapshot());
+
+    // Jumping starts the delay, which blocks crouching.
+    app.press(KeyCode::Space).tick().release(KeyCode::Space);
+    assert!(app.ineffable().just_pulsed(ineff!(PlayerInput::Jump)));
+    assert!(!tap(&mut app, KeyCode::KeyC));
+
+    // Rolling back to before the jump also rolls back the delay.
+    app.commands(move |mut ineffable| ineffable.restore(&snapshot));
+    assert!(tap(&mut app, KeyCode::KeyC));
+}
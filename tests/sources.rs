@@ -0,0 +1,97 @@
+use bevy::ecs::system::RunSystemOnce;
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent, GamepadInfo};
+use bevy::prelude::*;
+use bevy_ineffable::bindings::{AnalogInput, BinaryInput, Threshold};
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::processed::updating::InputSources;
+use bevy_ineffable::resources::ineffable_settings::IneffableSettings;
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum NoInput {}
+
+#[test]
+fn is_chord_pressed_needs_every_input() {
+    let mut app = common::app::<NoInput>();
+    // While holding Ctrl-S:
+    app.press(KeyCode::ControlLeft).press(KeyCode::KeyS);
+    app.world_mut().run_system_once(|sources: InputSources| {
+        assert!(sources.is_pressed(&BinaryInput::Key(KeyCode::KeyS)));
+        assert!(sources.is_chord_pressed(&[
+            BinaryInput::Key(KeyCode::ControlLeft),
+            BinaryInput::Key(KeyCode::KeyS),
+        ]));
+        assert!(!sources.is_chord_pressed(&[
+            BinaryInput::Key(KeyCode::ControlLeft),
+            BinaryInput::Key(KeyCode::KeyA),
+        ]));
+        let trigger = AnalogInput::GamePadLeftTrigger2.at_threshold(Threshold::preset_pos());
+        assert!(!sources.is_pressed(&trigger));
+    });
+}
+
+#[test]
+fn analog_value_reads_unbound_inputs() {
+    let mut app = common::app::<NoInput>();
+    app.scroll(3.).tick();
+    app.world_mut().run_system_once(|sources: InputSources| {
+        assert_eq!(sources.analog_value(&AnalogInput::ScrollWheelY), 3.);
+        assert_eq!(sources.analog_value(&AnalogInput::ScrollWheelX), 0.);
+    });
+}
+
+#[test]
+fn analog_value_ignores_the_mouse_sensitivity() {
+    let mut app = common::app::<NoInput>();
+    app.set_config(&InputConfig::builder().mouse_sensitivity(2.).build());
+    app.move_mouse(Vec2::new(10., -5.)).tick();
+    app.world_mut().run_system_once(|sources: InputSources| {
+        assert_eq!(sources.analog_value(&AnalogInput::MouseMotionX), 10.);
+        assert_eq!(sources.analog_value(&AnalogInput::MouseMotionY), -5.);
+    });
+}
+
+#[test]
+fn gamepad_axis_reads_a_single_gamepad() {
+    let (mut app, first) = common::app_with_gamepad::<NoInput>();
+    let second = Gamepad::new(1);
+    let info = GamepadInfo {
+        name: String::from("Second test gamepad"),
+    };
+    app.world_mut().send_event(GamepadConnectionEvent::new(
+        second,
+        GamepadConnection::Connected(info),
+    ));
+    app.tick()
+        .set_gamepad_axis(first, GamepadAxisType::LeftStickX, 0.5)
+        .set_gamepad_axis(second, GamepadAxisType::LeftStickX, -0.25);
+    app.world_mut()
+        .run_system_once(move |sources: InputSources| {
+            assert_eq!(
+                sources.gamepad_axis(first, GamepadAxisType::LeftStickX),
+                0.5
+            );
+            assert_eq!(
+                sources.gamepad_axis(second, GamepadAxisType::LeftStickX),
+                -0.25
+            );
+            assert_eq!(
+                sources.gamepad_axis(Gamepad::new(2), GamepadAxisType::LeftStickX),
+                0.
+            );
+        });
+}
+
+#[test]
+fn input_sources_only_read_the_settings() {
+    let mut app = common::app::<NoInput>();
+    // Taking both in one system would panic if `InputSources` needed write access to the settings.
+    app.world_mut()
+        .run_system_once(|sources: InputSources, settings: Res<IneffableSettings>| {
+            assert!(!sources.is_pressed(&BinaryInput::Key(KeyCode::KeyA)));
+            assert!(settings.post_acceptance_delay.is_none());
+        });
+}
@@ -0,0 +1,334 @@
+//! Tests for the problems that are reported when validating a config.
+
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_ineffable::bindings::{AnalogInput, InputDeviceCategory, SwipeDirection};
+use bevy_ineffable::prelude::*;
+use bevy_ineffable::reporting::{InputConfigProblem, InputConfigReport};
+
+use crate::common::TestApp;
+
+mod common;
+
+#[derive(InputAction)]
+pub enum PlayerInput {
+    #[ineffable(continuous)]
+    Sprint,
+    #[ineffable(pulse)]
+    Save,
+}
+
+#[derive(InputAction)]
+pub enum MenuInput {
+    #[ineffable(pulse)]
+    Confirm,
+    #[ineffable(continuous)]
+    Scroll,
+}
+
+/// Validates the config against the actions that are registered in the app.
+fn validate(app: &mut App, config: &InputConfig) -> InputConfigReport {
+    let config = config.clone();
+    app.commands(move |ineffable| ineffable.validate(&config))
+}
+
+/// Returns true if the report contains a problem that matches the pattern.
+macro_rules! reports {
+    ($report:expr, $pattern:pat) => {
+        $report
+            .problems()
+            .iter()
+            .any(|item| matches!(item.problem, $pattern))
+    };
+}
+
+#[test]
+fn merge_append_reports_actions_with_mixed_kinds() {
+    let mut app = common::app::<PlayerInput>();
+    let base = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Sprint),
+            ContinuousBinding::hold(KeyCode::ShiftLeft),
+        )
+        .build();
+    // This override was edited by hand, and binds Sprint as a pulse action.
+    let wrong = InputConfig::builder()
+        .bind_dynamic(
+            "PlayerInput",
+            "Sprint",
+            PulseBinding::just_pressed(KeyCode::KeyR),
+        )
+        .build();
+    // Sprint is registered as a continuous action, so the pulse binding is reported as the wrong kind instead.
+    let report = validate(&mut app, &base.merge_append(&wrong));
+    assert!(reports!(report, InputConfigProblem::ActionWrongKind { .. }));
+    assert!(!reports!(
+        report,
+        InputConfigProblem::ActionHasMixedKinds { .. }
+    ));
+    // Without knowing the registered kind, only the mixed kinds can be reported.
+    let (_, report) = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Sprint),
+            ContinuousBinding::hold(KeyCode::ShiftLeft),
+        )
+        .bind_dynamic(
+            "PlayerInput",
+            "Sprint",
+            PulseBinding::just_pressed(KeyCode::KeyR),
+        )
+        .build_validated();
+    assert!(reports!(
+        report,
+        InputConfigProblem::ActionHasMixedKinds { .. }
+    ));
+}
+
+#[test]
+fn shadowed_chords_are_reported_unless_chord_blocking_is_off() {
+    let mut app = common::app::<PlayerInput>();
+    let builder = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Save),
+            PulseBinding::just_pressed(KeyCode::KeyS),
+        )
+        .bind(
+            ineff!(PlayerInput::Save),
+            PulseBinding::just_pressed((KeyCode::ControlLeft, KeyCode::KeyS)),
+        );
+    let shadowed = builder.build();
+    let unblocked = builder
+        .chord_blocking_for(ineff!(PlayerInput::Save), false)
+        .build();
+    let report = validate(&mut app, &shadowed);
+    assert_eq!(report.problems().len(), 1);
+    let InputConfigProblem::ChordShadowedInSameAction {
+        narrower, broader, ..
+    } = &report.problems()[0].problem
+    else {
+        panic!("{report:?}");
+    };
+    // Holding Ctrl-S blocks the plain S binding.
+    assert_eq!((narrower.index, broader.index), (0, 1));
+    assert!(validate(&mut app, &unblocked).is_empty());
+}
+
+#[derive(InputAction)]
+pub enum BoatInput {
+    #[ineffable(single_axis)]
+    Throttle,
+}
+
+#[test]
+fn accumulate_with_empty_bounds_is_rejected() {
+    let mut app = common::app::<BoatInput>();
+    let config = InputConfig::builder()
+        .bind(
+            ineff!(BoatInput::Throttle),
+            SingleAxisBinding::accumulate(AnalogInput::ScrollWheelY)
+                .set_clamp(0.5, 0.5)
+                .build(),
+        )
+        .build();
+    let report = validate(&mut app, &config);
+    assert!(reports!(
+        report,
+        InputConfigProblem::AccumulateClampIsEmpty { .. }
+    ));
+    // Setting it anyway doesn't break anything: the value just stays put.
+    app.commands(move |mut ineffable| ineffable.set_config_silent(&config));
+    app.scroll(1.).tick();
+    assert_eq!(
+        app.ineffable().direction_1d(ineff!(BoatInput::Throttle)),
+        0.5
+    );
+}
+
+#[test]
+fn swipes_that_can_never_work_are_rejected() {
+    let mut app = common::app::<PlayerInput>();
+    let swipe = |min_distance: f32, max_duration: u64| {
+        InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Save),
+                PulseBinding::swipe(
+                    SwipeDirection::Left,
+                    min_distance,
+                    Duration::from_millis(max_duration),
+                ),
+            )
+            .build()
+    };
+    let report = validate(&mut app, &swipe(100., 500));
+    assert!(!report.has_errors());
+    let report = validate(&mut app, &swipe(0., 500));
+    assert!(reports!(
+        report,
+        InputConfigProblem::SwipeDistanceNotPositive { .. }
+    ));
+    let report = validate(&mut app, &swipe(f32::NAN, 500));
+    assert!(reports!(
+        report,
+        InputConfigProblem::SwipeDistanceNotPositive { .. }
+    ));
+    // Probably meant to be half a second.
+    let report = validate(&mut app, &swipe(100., 0));
+    assert!(reports!(
+        report,
+        InputConfigProblem::SwipeUnrealisticTiming {
+            actual_millis: 0,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn profiles_are_validated_on_top_of_the_config() {
+    let mut app = common::app::<PlayerInput>();
+    let config = |save: KeyCode| {
+        InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(KeyCode::ShiftLeft),
+            )
+            .bind(ineff!(PlayerInput::Save), PulseBinding::just_pressed(save))
+    };
+    let profile = |save: KeyCode| {
+        InputConfig::builder()
+            .bind(ineff!(PlayerInput::Save), PulseBinding::just_pressed(save))
+            .build()
+    };
+    let overlaps = |report: &InputConfigReport| {
+        report
+            .problems()
+            .iter()
+            .filter(|item| {
+                matches!(
+                    item.problem,
+                    InputConfigProblem::PulseOverlapsSustained { .. }
+                )
+            })
+            .count()
+    };
+    // By itself, the profile is fine. It only clashes with the sprint binding of the config.
+    assert!(validate(&mut app, &profile(KeyCode::ShiftLeft)).is_empty());
+    let clashing = config(KeyCode::KeyS)
+        .profile("Lefty", profile(KeyCode::ShiftLeft))
+        .build();
+    assert_eq!(overlaps(&validate(&mut app, &clashing)), 1);
+
+    // A problem in the config itself is reported once, not once more for every profile.
+    let broken = config(KeyCode::ShiftLeft)
+        .profile("Lefty", profile(KeyCode::KeyL))
+        .profile("Claw", InputConfig::default())
+        .build();
+    assert_eq!(overlaps(&validate(&mut app, &broken)), 1);
+}
+
+#[test]
+fn keyboard_and_mouse_count_as_a_single_device() {
+    let mut app = common::app::<PlayerInput>();
+    let single_device = |report: &InputConfigReport| {
+        reports!(report, InputConfigProblem::ActionUsesSingleDevice { .. })
+    };
+    // A swipe works with any device, so it doesn't make this gamepad-only config use a second kind of device.
+    let gamepad = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Sprint),
+            ContinuousBinding::hold(GamepadButtonType::LeftThumb),
+        )
+        .bind(
+            ineff!(PlayerInput::Save),
+            PulseBinding::swipe(SwipeDirection::Up, 100., Duration::from_millis(500)),
+        )
+        .build();
+    assert!(!single_device(&validate(&mut app, &gamepad)));
+    // Sprinting with the keyboard and saving with the mouse only uses one kind of device as well.
+    let keyboard_and_mouse = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Sprint),
+            ContinuousBinding::hold(KeyCode::ShiftLeft),
+        )
+        .bind(
+            ineff!(PlayerInput::Save),
+            PulseBinding::just_pressed(MouseButton::Middle),
+        )
+        .build();
+    assert!(!single_device(&validate(&mut app, &keyboard_and_mouse)));
+    // Adding a gamepad binding for one action leaves the other one only usable with the keyboard and mouse.
+    let mixed = keyboard_and_mouse.merge_append(
+        &InputConfig::builder()
+            .bind(
+                ineff!(PlayerInput::Sprint),
+                ContinuousBinding::hold(GamepadButtonType::LeftThumb),
+            )
+            .build(),
+    );
+    let report = validate(&mut app, &mixed);
+    assert!(reports!(
+        report,
+        InputConfigProblem::ActionUsesSingleDevice {
+            device: InputDeviceCategory::Mouse,
+            ..
+        }
+    ));
+    assert_eq!(report.problems().len(), 1);
+}
+
+#[test]
+fn pulses_only_overlap_held_actions_in_the_same_group() {
+    let mut app = common::app::<PlayerInput>();
+    app.register_input_action::<MenuInput>();
+    let overlap = |report: &InputConfigReport| {
+        reports!(report, InputConfigProblem::PulseOverlapsSustained { .. })
+    };
+    let save = InputConfig::builder()
+        .bind(
+            ineff!(PlayerInput::Save),
+            PulseBinding::just_pressed(KeyCode::Enter),
+        )
+        .build();
+    let held = |binding| {
+        save.merge_append(
+            &InputConfig::builder()
+                .bind(ineff!(PlayerInput::Sprint), binding)
+                .build(),
+        )
+    };
+    assert!(overlap(&validate(
+        &mut app,
+        &held(ContinuousBinding::hold(KeyCode::Enter))
+    )));
+    // Toggling sprint with the same key doesn't keep it active while the key is held.
+    assert!(!overlap(&validate(
+        &mut app,
+        &held(ContinuousBinding::toggle(PulseBinding::just_pressed(
+            KeyCode::Enter
+        )))
+    )));
+    // The menu is another group, which would normally be in another context.
+    let menu = save.merge_append(
+        &InputConfig::builder()
+            .bind(
+                ineff!(MenuInput::Scroll),
+                ContinuousBinding::hold(KeyCode::Enter),
+            )
+            .build(),
+    );
+    assert!(!overlap(&validate(&mut app, &menu)));
+}
+
+#[test]
+fn empty_sequence_steps_are_reported() {
+    let mut app = common::app::<PlayerInput>();
+    let config: InputConfig = ron::from_str(
+        r#"( bindings: { "PlayerInput": { "Save": [ Pulse(Sequence(1000, [ [Key(KeyS)], [], [Key(KeyS)] ])) ] } } )"#,
+    )
+    .unwrap();
+    let report = validate(&mut app, &config);
+    assert!(reports!(
+        report,
+        InputConfigProblem::SequenceStepEmpty { step: 1, .. }
+    ));
+}